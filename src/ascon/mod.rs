@@ -0,0 +1,78 @@
+//! # Ascon (NIST SP 800-232)
+//! The Ascon-p permutation and the modes built on it. Byte strings are
+//! loaded into the 64-bit state words little-endian, as in the final
+//! standard (Ascon v1.2 used big-endian).
+
+pub mod aead;
+pub mod hash;
+
+/// Ascon-p[rounds] on the state words `S0..S4`, using the last `rounds` of
+/// the 16 round constants.
+pub fn permute(s: &mut [u64; 5], rounds: usize) {
+    for c in &RC[16 - rounds..] {
+        rnd(s, *c);
+    }
+}
+
+fn rnd(s: &mut [u64; 5], c: u64) {
+    let [mut x0, mut x1, mut x2, mut x3, mut x4] = *s;
+
+    // Constant addition
+    x2 ^= c;
+
+    // Substitution layer
+    x0 ^= x4;
+    x4 ^= x3;
+    x2 ^= x1;
+
+    let t = [!x0 & x1, !x1 & x2, !x2 & x3, !x3 & x4, !x4 & x0];
+
+    x0 ^= t[1];
+    x1 ^= t[2];
+    x2 ^= t[3];
+    x3 ^= t[4];
+    x4 ^= t[0];
+
+    x1 ^= x0;
+    x0 ^= x4;
+    x3 ^= x2;
+    x2 = !x2;
+
+    // Linear diffusion layer
+    *s = [
+        x0 ^ x0.rotate_right(19) ^ x0.rotate_right(28),
+        x1 ^ x1.rotate_right(61) ^ x1.rotate_right(39),
+        x2 ^ x2.rotate_right(1) ^ x2.rotate_right(6),
+        x3 ^ x3.rotate_right(10) ^ x3.rotate_right(17),
+        x4 ^ x4.rotate_right(7) ^ x4.rotate_right(41),
+    ];
+}
+
+/// Up to 8 bytes as a little-endian word, with the padding byte `0x01`
+/// appended if `bytes` is shorter than 8.
+fn load(bytes: &[u8]) -> u64 {
+    let mut block = [0u8; 8];
+    block[..bytes.len()].copy_from_slice(bytes);
+
+    if bytes.len() < 8 {
+        block[bytes.len()] = 0x01;
+    }
+
+    u64::from_le_bytes(block)
+}
+
+/// Split `msg` into `rate` byte blocks, the last one shorter than `rate`
+/// (possibly empty) so that it carries the padding.
+fn blocks(msg: &[u8], rate: usize) -> impl Iterator<Item = &[u8]> {
+    let full = msg.len() / rate * rate;
+
+    msg[..full]
+        .chunks_exact(rate)
+        .chain(std::iter::once(&msg[full..]))
+}
+
+// Constants
+
+static RC: [u64; 16] = [
+    0x3c, 0x2d, 0x1e, 0x0f, 0xf0, 0xe1, 0xd2, 0xc3, 0xb4, 0xa5, 0x96, 0x87, 0x78, 0x69, 0x5a, 0x4b,
+];
@@ -0,0 +1,363 @@
+//! # Heap-allocated integers
+//! [`BoxedUint`] holds an unsigned integer of any size, without leading zero
+//! limbs. Arithmetic is schoolbook and variable time.
+
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    ops::{Add, Div, Mul, Rem, Shl, Shr, Sub},
+};
+
+use super::{
+    add_assign_limbs, be_bytes_to_limbs, montgomery::Montgomery, mul_limbs, sub_assign_limbs,
+};
+use crate::rng;
+
+/// An unsigned integer of any size.
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BoxedUint {
+    /// Little-endian, with no zero limbs at the top; zero is empty.
+    limbs: Vec<u64>,
+}
+
+impl Debug for BoxedUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BoxedUint(0x")?;
+
+        match self.limbs.split_last() {
+            Some((top, rest)) => {
+                write!(f, "{top:x}")?;
+
+                for limb in rest.iter().rev() {
+                    write!(f, "{limb:016x}")?;
+                }
+            }
+            None => write!(f, "0")?,
+        }
+
+        write!(f, ")")
+    }
+}
+
+impl BoxedUint {
+    pub fn zero() -> BoxedUint {
+        BoxedUint { limbs: vec![] }
+    }
+
+    pub fn one() -> BoxedUint {
+        BoxedUint::from_u64(1)
+    }
+
+    pub fn from_u64(x: u64) -> BoxedUint {
+        BoxedUint::from_limbs(vec![x])
+    }
+
+    /// Little-endian limbs, with or without leading zeros.
+    pub fn from_limbs(mut limbs: Vec<u64>) -> BoxedUint {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+
+        BoxedUint { limbs }
+    }
+
+    /// The little-endian limbs, without leading zeros.
+    pub fn as_limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+
+    /// The limbs zero-padded to `len`, which must fit the value.
+    pub fn to_limbs_padded(&self, len: usize) -> Vec<u64> {
+        assert!(self.limbs.len() <= len, "integer does not fit");
+
+        let mut limbs = self.limbs.clone();
+        limbs.resize(len, 0);
+
+        limbs
+    }
+
+    /// A big-endian integer of any length.
+    pub fn from_be_bytes(bytes: &[u8]) -> BoxedUint {
+        BoxedUint::from_limbs(be_bytes_to_limbs(bytes))
+    }
+
+    /// The shortest big-endian encoding, which is empty for zero.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let bytes = self
+            .limbs
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes())
+            .collect::<Vec<_>>();
+        let zeros = bytes.iter().take_while(|b| **b == 0).count();
+
+        bytes[zeros..].to_vec()
+    }
+
+    /// The big-endian encoding in exactly `len` bytes, the `I2OSP` of
+    /// PKCS #1, or `None` if the value is too large.
+    pub fn to_be_bytes_padded(&self, len: usize) -> Option<Vec<u8>> {
+        let bytes = self.to_be_bytes();
+
+        (bytes.len() <= len).then(|| [vec![0; len - bytes.len()], bytes].concat())
+    }
+
+    /// The bit length, 0 for zero.
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => 64 * self.limbs.len() - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Bit `i`, counting from the least significant.
+    pub fn bit(&self, i: usize) -> bool {
+        self.limbs
+            .get(i / 64)
+            .is_some_and(|limb| (limb >> (i % 64)) & 1 == 1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().is_some_and(|limb| limb & 1 == 1)
+    }
+
+    /// `self - rhs`, or `None` if `rhs > self`.
+    pub fn checked_sub(&self, rhs: &BoxedUint) -> Option<BoxedUint> {
+        if rhs > self {
+            return None;
+        }
+
+        let mut limbs = self.limbs.clone();
+        sub_assign_limbs(&mut limbs, &rhs.limbs);
+
+        Some(BoxedUint::from_limbs(limbs))
+    }
+
+    /// Quotient and remainder by long division, a bit at a time. Panics if
+    /// `rhs` is zero.
+    pub fn div_rem(&self, rhs: &BoxedUint) -> (BoxedUint, BoxedUint) {
+        assert!(!rhs.is_zero(), "division by zero");
+
+        if self < rhs {
+            return (BoxedUint::zero(), self.clone());
+        }
+
+        // The quotient has at most shift + 1 bits, and the bits of self above
+        // them are a remainder below rhs already.
+        let shift = self.bits() - rhs.bits();
+        let mut q = vec![0u64; self.limbs.len()];
+        // The remainder stays below 2 rhs, so one extra limb is enough.
+        let mut r = (self >> (shift + 1)).to_limbs_padded(rhs.limbs.len() + 1);
+
+        for i in (0..=shift).rev() {
+            shl1(&mut r, self.bit(i) as u64);
+
+            let mut diff = r.clone();
+            if sub_assign_limbs(&mut diff, &rhs.limbs) == 0 {
+                r = diff;
+                q[i / 64] |= 1 << (i % 64);
+            }
+        }
+
+        (BoxedUint::from_limbs(q), BoxedUint::from_limbs(r))
+    }
+
+    /// `self mod m` for a small `m`, e.g. for trial division. Panics if `m`
+    /// is zero.
+    pub fn rem_u64(&self, m: u64) -> u64 {
+        self.limbs
+            .iter()
+            .rev()
+            .fold(0u128, |r, limb| ((r << 64) | *limb as u128) % m as u128) as u64
+    }
+
+    /// The greatest common divisor, by Euclid's algorithm.
+    pub fn gcd(&self, other: &BoxedUint) -> BoxedUint {
+        let (mut a, mut b) = (self.clone(), other.clone());
+
+        while !b.is_zero() {
+            (a, b) = (b.clone(), &a % &b);
+        }
+
+        a
+    }
+
+    /// `self^-1 mod m`, or `None` if they are not coprime or `m < 2`.
+    pub fn mod_inverse(&self, m: &BoxedUint) -> Option<BoxedUint> {
+        if *m < BoxedUint::from_u64(2) {
+            return None;
+        }
+
+        // Extended Euclid, keeping the coefficients of self modulo m so that
+        // they stay non-negative.
+        let (mut r0, mut r1) = (m.clone(), self % m);
+        let (mut t0, mut t1) = (BoxedUint::zero(), BoxedUint::one());
+
+        while !r1.is_zero() {
+            let (q, r2) = r0.div_rem(&r1);
+            let t2 = (&t0 + &(m - &(&(&q * &t1) % m))) % m;
+
+            (r0, r1) = (r1, r2);
+            (t0, t1) = (t1, t2);
+        }
+
+        (r0 == BoxedUint::one()).then_some(t0)
+    }
+
+    /// `self^exp mod m` in constant time for the given lengths, see
+    /// [`Montgomery::pow`]. Panics unless `m` is odd and above 1.
+    pub fn pow_mod(&self, exp: &BoxedUint, m: &BoxedUint) -> BoxedUint {
+        Montgomery::new(m)
+            .expect("modulus must be odd and above 1")
+            .pow(self, exp)
+    }
+
+    /// A uniformly random integer below `2^bits`.
+    pub fn random_bits(bits: usize) -> BoxedUint {
+        let mut bytes = vec![0u8; bits.div_ceil(8)];
+        rng::fill(&mut bytes);
+
+        if !bits.is_multiple_of(8) {
+            bytes[0] &= (1 << (bits % 8)) - 1;
+        }
+
+        BoxedUint::from_be_bytes(&bytes)
+    }
+
+    /// A uniformly random integer in `0..bound`, by rejection sampling.
+    /// Panics if `bound` is zero.
+    pub fn random_below(bound: &BoxedUint) -> BoxedUint {
+        assert!(!bound.is_zero(), "empty range");
+
+        loop {
+            let x = BoxedUint::random_bits(bound.bits());
+
+            if x < *bound {
+                return x;
+            }
+        }
+    }
+}
+
+impl PartialOrd for BoxedUint {
+    fn partial_cmp(&self, other: &BoxedUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BoxedUint {
+    fn cmp(&self, other: &BoxedUint) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl Add for &BoxedUint {
+    type Output = BoxedUint;
+
+    fn add(self, rhs: &BoxedUint) -> BoxedUint {
+        let mut limbs = self.limbs.clone();
+        limbs.resize(self.limbs.len().max(rhs.limbs.len()) + 1, 0);
+        add_assign_limbs(&mut limbs, &rhs.limbs);
+
+        BoxedUint::from_limbs(limbs)
+    }
+}
+
+impl Sub for &BoxedUint {
+    type Output = BoxedUint;
+
+    /// Panics if `rhs > self`.
+    fn sub(self, rhs: &BoxedUint) -> BoxedUint {
+        self.checked_sub(rhs).expect("subtraction underflow")
+    }
+}
+
+impl Mul for &BoxedUint {
+    type Output = BoxedUint;
+
+    fn mul(self, rhs: &BoxedUint) -> BoxedUint {
+        BoxedUint::from_limbs(mul_limbs(&self.limbs, &rhs.limbs))
+    }
+}
+
+impl Div for &BoxedUint {
+    type Output = BoxedUint;
+
+    fn div(self, rhs: &BoxedUint) -> BoxedUint {
+        self.div_rem(rhs).0
+    }
+}
+
+impl Rem for &BoxedUint {
+    type Output = BoxedUint;
+
+    fn rem(self, rhs: &BoxedUint) -> BoxedUint {
+        self.div_rem(rhs).1
+    }
+}
+
+impl Rem<&BoxedUint> for BoxedUint {
+    type Output = BoxedUint;
+
+    fn rem(self, rhs: &BoxedUint) -> BoxedUint {
+        self.div_rem(rhs).1
+    }
+}
+
+impl Shl<usize> for &BoxedUint {
+    type Output = BoxedUint;
+
+    fn shl(self, k: usize) -> BoxedUint {
+        let (words, bits) = (k / 64, k % 64);
+        let mut limbs = vec![0u64; words + self.limbs.len() + 1];
+
+        for (i, limb) in self.limbs.iter().enumerate() {
+            limbs[words + i] |= limb << bits;
+
+            if bits != 0 {
+                limbs[words + i + 1] = limb >> (64 - bits);
+            }
+        }
+
+        BoxedUint::from_limbs(limbs)
+    }
+}
+
+impl Shr<usize> for &BoxedUint {
+    type Output = BoxedUint;
+
+    fn shr(self, k: usize) -> BoxedUint {
+        let (words, bits) = (k / 64, k % 64);
+
+        let limbs = (words..self.limbs.len())
+            .map(|i| {
+                let hi = match bits {
+                    0 => 0,
+                    _ => self.limbs.get(i + 1).map_or(0, |x| x << (64 - bits)),
+                };
+
+                self.limbs[i] >> bits | hi
+            })
+            .collect();
+
+        BoxedUint::from_limbs(limbs)
+    }
+}
+
+/// `x = 2 x + bit`, dropping the top bit.
+fn shl1(x: &mut [u64], bit: u64) {
+    let mut carry = bit;
+
+    for limb in x.iter_mut() {
+        let top = *limb >> 63;
+        *limb = *limb << 1 | carry;
+        carry = top;
+    }
+}
@@ -0,0 +1,207 @@
+//! # Arbitrary-precision integers
+//! Unsigned integers for RSA and finite-field Diffie-Hellman: [`Uint`] of a
+//! fixed number of 64-bit limbs, [`BoxedUint`] of any size on the heap, and
+//! [`Montgomery`] arithmetic modulo an odd [`BoxedUint`] chosen at runtime.
+//! Fixed moduli known at compile time are better served by
+//! [`crate::ecc::modular`].
+//!
+//! Limbs are little-endian `u64`s throughout. Plain [`BoxedUint`]
+//! arithmetic, division, gcd and inversion take time depending on their
+//! values and are meant for public data or key generation; modular
+//! multiplication and [`Montgomery::pow`] are constant time for a given
+//! modulus.
+
+pub mod boxed;
+pub mod montgomery;
+pub mod uint;
+
+pub use self::{boxed::BoxedUint, montgomery::Montgomery, uint::Uint};
+
+/// `a + b` into `a`, over the length of `a`, returning the carry.
+fn add_assign_limbs(a: &mut [u64], b: &[u64]) -> u64 {
+    let mut carry = 0;
+
+    for (i, a_i) in a.iter_mut().enumerate() {
+        let t = *a_i as u128 + *b.get(i).unwrap_or(&0) as u128 + carry as u128;
+        *a_i = t as u64;
+        carry = (t >> 64) as u64;
+    }
+
+    carry
+}
+
+/// `a - b` into `a`, over the length of `a`, returning the borrow.
+fn sub_assign_limbs(a: &mut [u64], b: &[u64]) -> u64 {
+    let mut borrow = 0;
+
+    for (i, a_i) in a.iter_mut().enumerate() {
+        let t = (*a_i as u128).wrapping_sub(*b.get(i).unwrap_or(&0) as u128 + borrow as u128);
+        *a_i = t as u64;
+        borrow = (t >> 127) as u64;
+    }
+
+    borrow
+}
+
+/// The full product of `a` and `b`, `a.len() + b.len()` limbs long.
+fn mul_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut res = vec![0u64; a.len() + b.len()];
+
+    for (i, a_i) in a.iter().enumerate() {
+        let mut c = 0u128;
+
+        for (j, b_j) in b.iter().enumerate() {
+            let x = res[i + j] as u128 + *a_i as u128 * *b_j as u128 + c;
+            res[i + j] = x as u64;
+            c = x >> 64;
+        }
+
+        res[i + b.len()] = c as u64;
+    }
+
+    res
+}
+
+/// Big-endian bytes of any length as little-endian limbs, the top one
+/// zero-padded.
+fn be_bytes_to_limbs(bytes: &[u8]) -> Vec<u64> {
+    bytes
+        .rchunks(8)
+        .map(|chunk| {
+            let mut limb = [0u8; 8];
+            limb[8 - chunk.len()..].copy_from_slice(chunk);
+
+            u64::from_be_bytes(limb)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex;
+
+    // Known answers from Python's integers, as big-endian hex.
+
+    const DIV_REM: [(&str, &str, &str, &str); 9] = [
+        ("d4644417871be4434b9a3682eb66f9888c75603722a8ff1c07e70715d7d8a6c30df0fadcd3393b0fa1d551dc51f10900c87ced6d11a64ad207c7ac10083d0a2fcd6a4292f27baaf989bc15a5956f5c7126e7581a84060c46a27056f73a818b9fe338e970dc1afab8963f389496afcff50a3aee4966660879138dda71e3658966", "f5a75f2013069e53d4a4405777321e857881549127f6e6495c41c3db7a8efdebb4895688f96fe97365e12e6a17c9b326c33709e3ef3ca884b6989668f7c8122a", "dd564378f79023ddb5c9059fae335fad1406c2603a729a68e8b42af9170046581da7cec1cd0a9660073e5995ef4551b03716cd92076680a7da5843ecd738ec0e", "15fdf813ed41dbc0304794e8bddb21a0e19273c854f8ea6ba14f754621d61edb59811762c6662fbe242262c900a0956559956746a8e5d9ed91dbe48aa685d31a"),
+        ("9840ede51b4ea5c2273fe1f7466af77fb762bba6c74b7a7471a7124481aeb810562d3d5c39f2a8a76ec30d101c0072e59e8c85898b5f46afb24b5692bfb63d9e", "de6e9b4a1da45e02cfcd7bd648afeed2c197cae81088ed14506c3a2ead3e63e2b486f065b634090ab77631029c3b69acfe7c2b113f8754ce95524b291d668a64d43cc23d34228470c6b54165c5efc8e65b4c42944d3e6fc36244ba182f09e29867f0a484df312a0775dc203b55e8dc409bf05d6111ac0c76e06ced8c5ad02341", "00", "9840ede51b4ea5c2273fe1f7466af77fb762bba6c74b7a7471a7124481aeb810562d3d5c39f2a8a76ec30d101c0072e59e8c85898b5f46afb24b5692bfb63d9e"),
+        ("fd23cb58b47796426096c8d878924c40a9646b9bba54b87a479da393d1dff51ada1f6b63c7d322c38acc1123618e0145c2a45da122c139392d8aaecea68c14a570d0ec196457fd25136527ab15f67847f03b07d55d96efab3c11b061c65f96c63294f39108f81be3ba8f04c1a640f59c845c621305ee6e4ef51c8c5bf0a2aa46a93d12f4931bcb73677760a44d040862de0333332f7ca606e32c19e4b74308ffac4daa1600422358926634972c9a2e5c56530c9fdbb6cdefbdcee8d019c6a57f64f10ef410d971d8308173468ae86effb8543de82beb79f7d50eb76d9510b807a3348bff91902796a97cac00c050341287a12b595da86b482ed777538f62cb88", "ca40ca4c2621aac2", "014068cfc6a74e1b7687ec77c2eac85d64413411d38e858e2f552fa3a0ee45305a4fcfd6a3d726c528eec02149e1467d120626e3715998c5e9173bfe7de28c89e2b312c8b780d0785efe124add93406a7e27f8faa941b3e6d8b6053b42ad6185595573f55a455a1aa6c4e1b70b188127ac75c548c0d368e6847ace69879368649f0f7f231af39255388e63260e1fd2e9d5e9c4557157845b1ed290a858008fa45ea411724d2eeeca8978c1f53ab3a23deb8fa5cc0fea20838529fc4bd235618a282f58d32c84e13f14f6a2ae1f9104f531ec39dc7879208ae7932e2ceef03dfc6e0db487fff099033a2b4603ff94db5bd59bbcbad586476f2c", "505d739280c15430"),
+        ("0b2f0d871cb81be31cc40a7f28b0616d8f0521c97862613abd2d4fc69cb4a8778c8d6226ce53", "06acb99278609d28ef52a7fc834e46ecb74e51c04ffdd381c9fd60ac44851efa7a0ff9cfe96d", "01", "048253f4a4577eba2d716282a5621a80d7b6d00928648db8f32fef1a582f897d127d6856e4e6"),
+        ("0100000000000000000000000000000000", "ffffffffffffffff", "010000000000000001", "01"),
+        ("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", "0100000000000000000000000000000001", "ffffffffffffffffffffffffffffffff", "00"),
+        ("90c1e5273f82232e598c42858dddb8982c58bdbe225f403df813e5da68ec768b659968c4b594d4149feb2a3a586990f5089bc71b56673ba6c2aed4b205b069917ccb931a47cf1cae6fe96e1607a4965c3df3a1369a7ce3a1b4836b79d09d18acd5a192cf1226dcef59307d0e542e9525cd1c243c62f6474703af2b9936", "09576697e10ea4039d36c559ad25b07b66a74d140768b20b6c03b36574a7fc28bdbd5907715e01c21f9afe35f81eb8a1fc8b9be589c23832553ae170f99babbfc0168eb9cb65805679b7b981f6a108953cbcbd61638d6279eaa83fb24304b7bea3005c3267ace525ba2b312aa34ba20f83", "0f7f0e6211b557e41427c5d612", "00"),
+        ("0169b7759cff9939d2e79ae2d5f4cfff4445a71091d0250bbb8c13238dcdd17f5262d920838d037dbfd9eaf75f6bef619d1ffad968db8c3a66776fd16b80a3b94acc4b2c3e9c54f2b179c2fe59afb5f405b90fa86a71ef1eb3177d614695866a720f", "01", "0169b7759cff9939d2e79ae2d5f4cfff4445a71091d0250bbb8c13238dcdd17f5262d920838d037dbfd9eaf75f6bef619d1ffad968db8c3a66776fd16b80a3b94acc4b2c3e9c54f2b179c2fe59afb5f405b90fa86a71ef1eb3177d614695866a720f", "00"),
+        ("00", "3a5f6824a70418e3f3", "00", "00"),
+    ];
+
+    const MOD_INVERSE: [(&str, &str, Option<&str>); 7] = [
+        ("9f915b423d748a9797681ec8455e6d498bf2c0d279ab7fe79c7947f07d8328a5155a8713fb898e36ac9a7916a45c5a3799a5e8ffdd4afe2bbf7253b29758a9c15b9dbac6fe85af4554e49e2f4b9c3e2bc413d5dd4c0fb6a13b8ed064543878382eee836b1e71c130ee1bb9f34917f395282a24c8a05389ea63d67fda4151c9d7", "aafb11c2d6489e7b995189fecfaa40475242a14a0b3a78d26cf39663c9d6d9e110437e1aaeadfeeac8dddd1d2028b99127c4e0316e9e09843c9790abef80af7816f14206cb953ba5910fff3bb5545559ddd82b942e1118386ec208e387ba951df9c6e13dee7f92a26ce43adaa7794331c9d5d8be7b2496396a7d7239e88a0eeb", None),
+        ("b845abe3d3f3971fc313ed66e98c3dbd27c5d08405827a833c5eb87a73bf28688e8375ee179b557c484605c923f4a6011669411ee9ced53ff553da555609b8b92784c7410c4eb242a1ba326e78ef565fd02bb4bdb8c364edfe1cc32bc5d4844cafb6bba33efecceef228f80100664c4da66bc7ff597fbce1fba84887fb8a88952b33834e34d2d8f959a287f9575563b050ab96d76b3d30854cd06f4654acd97c380274c125f7e8a7e28df03b76704a0bf904a623c841f68d6e2567c89f0f6904485185997d04013f5e2ae6c947646c9e34871dde931763bfc8483015215882c20a2a417d84be59c7dcf3a7511b3bb06f539c42f99e54177e301c179811aa5956", "9aea55d2dc51ba72dc12e8e05320e0085e1b066ef874ff3496110b6e65718c1249b57083a4acb862813059a963f4e03794ff0414d5c94105bc9ccbb13009a66440ae68cd798f2b7c544f58ea3cc6c979009bdb0a1eb0b0565cfd9342c5fb4a85f4ce113ec4131470df4b16c7fa69cf853500ae8bdfb131972d382dbb282e989f", Some("2fe28ca320e1dcc040700bc4df66cde62fff235237eeb1b8366ca1432fc5221e849883cd935269839c5f5a329a56ca0fe155caf1ea4e1123d48de9b8d3f9ab8598b023de54a1c89612bf215df5efb5928dfc242e1425068925148f229a9fdde6358c739c4d6db8c2323c15237e0c4c750813cde5fe04ce4b27e111e997356b30")),
+        ("3039", "ffffffffffffffc5", Some("58cbe3378369483b")),
+        ("044b8f39cd196b6d2c1ef6c66122a97c39aab608a67c70ba82a664db650224c68687f87a659f086bdf71faa97c7101797632e8a57fcdc530f7f6e10d0f3bd6143e", "02410c1e0d2318d17f9393472ae71c7edb24cca3a6e9ef8317f9f7a7b56e2704d18e8418a96505c92d2e44c60bb1a9140aac3328ff7743bfd704ef360821373b7c", None),
+        ("05", "01", None),
+        ("00", "85626594a845bc7dce5655f03f20f267b3bfcc3dfc0f07c061", None),
+        ("ba8c047209796060", "010000000000000001", Some("c92ad171d6d59e56")),
+    ];
+
+    const GCD: [(&str, &str, &str); 5] = [
+        ("27735a7345e136af96d85152b4f21058d8a5cb23341c5868af078b6c1c28d2b957fac5cde36cd8e59d70b70ccb7235cd953cec9ebb61d01fc087db147c93f4c8ad", "3b82b6564b0e90bfdce025105458a540f213c2e931124cc6e4d60f02994fc0fb27fe40ff1f895d881d087c772522fb7a9ffbee4361196f9c73cfa23f94459ee4dc", "4d"),
+        ("eec89731aae0e77ea1b7984fd87d2f7f23ef3db694e924c55cfb2f0152dbeefbad8b0bda50eda27c0970bb19b4c84d0e9811cba23083b06f6eed270c5e6fedba08d630709a5572080bc57fbf4d6815ed4f0d3bdd2d56b95f0e5c3346db342d5be0190554cc2a1354d3fc8c97f5b7f74cda55f4d052334619264cecf0f54b541d", "e05263e5b4599998b4365cb752b625cd2fd80e12b80560c885b404a355c920b2cb6f035b5491d27b1b781f4fa11324d8aba43441be75c983f3e11f2c3d2c0a42afdaf949d112a2662a155a9fffd2bc07efc627dd837ea8d48b3e35f3f1c534e25f0377468e16e7cb93842f132d121d97f432d91867f6a5da5c438d14a7533090", "01"),
+        ("00", "0dcc26f9af58ffb8073f526bfe562703ba1a2cff1709f456182f9df60215bb205ea6680710d9", "0dcc26f9af58ffb8073f526bfe562703ba1a2cff1709f456182f9df60215bb205ea6680710d9"),
+        ("082afbc9954fc06214bc781cb7f9d60457b38d3dd16c899ad2a2c915db5d5ad6a48959f8443f", "00", "082afbc9954fc06214bc781cb7f9d60457b38d3dd16c899ad2a2c915db5d5ad6a48959f8443f"),
+        ("0300000000000000000000000000000000000000000000000000", "90000000000000000000000000", "30000000000000000000000000"),
+    ];
+
+    const POW: [(&str, &str, &str, &str); 9] = [
+        ("fd063dccc03c13e47f41c17085b013a7a47c5bc839ebe3580e0a1ef5d24f83f6f5d663956bd8944a81c3990f91ae139d799fa70a6e310e7accdbdb23ad02626ef99434a83b702c7e23d36c1de3dfef709172bb651f0402cbe91cacd479617f40c6ddc5fe6a84f223920b51661b39ae726bda2d19c9bb00569b9c4d785b011dd373d6605196df51661737d51d7a4c7e37906dd0f74e8e00f4ca50b0791d895c9f479c6bdcffd8bf1c8f25c38666092d56e931d9be5c3d9049ef010578fdafb6719b98de8f7decb2ae6d339d2db80a969b0d33202793a408d43f4cfe38242975acdf9e4a3caf42fe03ed4e1202e718300b80206c6d88b8e42931d2c8306a364591", "ff866bb3c726633533d04bf7e3dbd5267c97e22e7fb61accb5d4a3e1fe059a1f2756fe9bc9ead09fd97c8cc2c2d2c52e225b4624c2e5c5ae3e2a02b9c82efe348290cafafb17ea83da1363a34929e5033988df7e18dcd318b6bad3e7cfc64338fc79a95c5262a25c5595144ae7a314eaab098d97b14d3589fcf45d1633bae3fb47813dd06e5b5eb3929602dd2d5a5a85b927207a6551d83755c7767bb109edd97e7b4c0481649c68f6a4ed398f4586501c02441ef52d576072e7782f291900774b04d0c9c59efa003332ed0ef28bd23b65aeb7d4fa430ab6da302d4337a062c05c3f00c094b96bec2ec48d8c38c1f58bec31eea8ded12d1e2cdc1a81d90d1b46", "f694a48b5da244f2f33754e7bb4e03ef8504d78ccb161edbeaa4e2c3e0caa8f0d32ef8621f45fea1cdba20d246b8fe07c7405acb5ca16366c1292fa0189c91eef690ca7f6f5a1d1f1ba99a7a64bf825cde1c369706ca46e2e48524ba31fb79a62de057c77f1898964d0742d5a323c82313d23c304d0a91e26b23134c780aad26cac4d51ef96e5ea9293a1b07d748274fe3c22bf209d54f3a96b3193f78ec2c46786ca993c461f71d2d358ad1d8ca1ab7d0d222dbefdcb78ff55f56cecffa3ed520eb9f2d0f478f26c38191f43282bcf4d42118bf5717fbf706aeb3f158a768476a5a2256acecc33907d45405519248b877bcd4f08294cdb006d10934d8602bbf", "1dd0b028f7300bc388ea710ad3c69907d18e7a995117fe7d948fb6c8aaebf7063cd4fe17a945bb2b90af71e35f3d28071644a5172367c573a86defddb335a15ace5dd705d93d889a27236af6d5fb8a08c62e03111fa9a9a50c06a0317f8d5a29a9d5cefa5c50e42a0d2bab75353380d932ce1e32bac626a721a3af736e23277caf72291e0e0391a35e6779b9b32e53e4dfac32288c5e0f5ae9bd1b62ddb7c93b61de76f876a120e14b518bacd769deecc5c10e8e011137faaf3a5b2f36957d5793abf1723066bc51f10d3ed8d67e627d46d9137b2c01b0016e9c66f0129f0213f74da2d7da7e13d401275c0af9256d3b4b87beef435c4dc77894e47191d73b17"),
+        ("8337f2075ea1a7db6960fea086a0ce0b9d45c6cbf32a6bafb44b6051656ce842bde50fb23662e708fd7505948cb303eee10c09877e0610b939cb9e5f22d821c076e38c137e8928d521be272fc30346ade47d7ae70f5d7d756940ab9b404e3b388ab79773ed1c6359e0d5b40cbf175f154be1e15c4a63ef9be1d22922b4a18458", "80cc27f08f303116827ff1d5afc8a3201211146e9d0161c86abea8219a1ad9f2c2717a0713c82d363b5de85cf67934de40b7d8ae5b803dba1d81b70e161a0bb7541ee35bb0957631ff6e4d79df73ff9d39500965c2a1eee6189a9dfcdbe91788f8bdfc11bc7d43175887dc0da07986cb045683f9faafe03dc82814049129dae843866e787890141681f10918b35f89a5ac71a13a19d496fe6edcfddab487afd3b99f5f676e21bf3b12c06b51ca6201f67b69499baa9313fd7e8c261563f65bc9a36b72b159ff9dbeedbcb7d28a3d04f19703ba363bf429a2fabdac83f8b2d36b81b686810d407ea2d4bda57148a79d9bb945d5e55a7bf0f94ad5431b07825e830a8a046f99bae0a749114d0a75082edb8e46229e18628d091046f829ba143a132a3a4f96944d79a9269b011f1c0b811cb41cc745a504f6b266be46e407ac50d51d5b4fcf7809ff3c3a9cb3f5f8fdf9204d10a94fa2b0b381b284e1e760c5f51d73f971b8e6dbfde8ddad37d2cdc757aa0a04f406374eb6", "e4b7ffb42f76b4deb5b8f599f0478de65ab2caefef6bedd0695772b76194fa60f35d2c659ba653a25fec935ea77dc9a57dbc5cfa2cf96c7cb9d38034d0f8dead684362e1a5de26b85493ec1a341951e6427ed63980d31938617210462ca008a4bfd8f4e786688e046cd0d39627c593434ba7a99c0a5a00d5b54f7c8b4bda3ba3", "86f49466416455a8cd1e3f99b10823838a9638aa4ebff093f6e19e8c7c3c41c68c148b1b12b767f23031b6b30f275edd9982f413eda002e6ce4e089ba7740bd3cef3ce3deaa80ce4dd08d832b92a6cc5ad3b497b1c3d4a427f74e793b6ecb08bf337bc3bcaefea4849ab735b44fcbab3d42e3f5086ec962e9f86d866da5ea146"),
+        ("b1542dee20c9915195c84ab414e3d4e6c213f0ae61f2e418af4ac7ce98938aa00e91d9adfcca2d0c4f80e7e7bd12d3a87c7d2eb3805ed12c08fa2eeded3d37db1fdae2cc7a570efb52d0181a32f4e65ebf4c8e40998235e0a3d26dcbf4fa3392206203a9c4a398a5ababe753a4c7e201781dab8741edde85d33121d375015ab73f4b296876d0a2c3512f1a2c2be6cb0f6d0113df7ff9ea8d1b8bd3eab35d805c55ce1b6ee1d8c2ef62693fd6cff61b55c7f7a8f39410563c0748f1ee5251f44acc0f091270ec1d88ecd032e9ae8905fff18cdeda5263c125f78832de259b45f5d77e4b9e368e3a53a6a01318b7e60e8a0c6a1fd042a4546b908ed156f1e87785f1ceddf6a87a425a50cfd3a74ff8e98b827f8048bf69298e78f4931109ec5a4ab4a8425f58d6244908de42c1713f58260f19a14e44c6634ef8eaa9c130bf8f4d3512962ae8ce7744d4c562501bd909a25a3c69b6a938ffb75363347f66089a04c7753285a2811363191a3b02352f890e00d03f09a668ff25322ac33fd8a4bf010d73dbac23eccb558e9931185740175b6b89a0dca8c0afd8ab02f73347cfd40c1447a7e684829f45de0ca7ee223e294feabd76e64cafbf8b6aefc0f31c4d5a32dbe2f6a7233357d12abe12e7caa9cfe96a1f4f00d33d2e969c8ec4ea18ecd329dbe30a44c8d9ce2e35f8be9ea7c5e6a6fd32a2ed408f56c9a87da33ae92efa46", "bd38f89163a6895eb886c38c8d911463efc2e3244b0e092cbcfd284a2b612f2e44a8fd0c5af1346c941db83927e2e6184131f30fc59eb783d7758eaf3b7881b85d274fb49ed31ecfbeeef4ecdfe4e9c9ed5216988c6d19b0868489afde7f9983985c05efe24b37a92144c51158f8ff0062d517daa5e8614e4316852d817e8d05", "bc035ec2525407f4886428f15839f2200f4f555df75a7404477d935960cd2f50e14c06cce5bd4273a19b5b6f3fe52671c7aac119831205cd3a0afba9312d265602bf530fcc7d53ee8d25e5ea4f2ec9f323bb3c9a8b5a288664f523d00bb659844a69ef59feff8e1b2e867618f30d6f31dfc5aab4a9a094132e699f1753e7ecb3", "32d961b917e37912db20a789ce6503ff7a83f86e5dc5ffe82e54a9e7ab15e1a402debd5bcd22580bde9f8ab8e2b59dc396fdf020603cd4f1892f8378195f13e1c066d5f7ce9a2abf8bdd8e02c897fd0ef9474b584c1f28dbadca54c5d53a99c41164f663ae2ee3d7fcf69a36d045988983ebef7b1e3170dc099e8219102a784c"),
+        ("98fec77d7289b691", "e80a36f8fa105dff", "ffffffffffffffc5", "29949c44e58a47d6"),
+        ("f5811a3ba87087ca35f5d8adff2b0f2157880f8a38c51677d07b99eaab89adfd6f6a665fd92ee336802dd84f286a011961326e182e7247bc8ea3475119023831326e5817b76fe5dc92799c", "fa8a71e1fcfbd732", "ffffffffffffffc5", "27ece215bf68c7b0"),
+        ("a1d9e3e9499951cb2281f2c7867367dc62e83a32f3f722fa35788589a10dd4102e563489a571f7d971d2ad19d12c999fe4296b15708d8aa01ab5969c25633d305390a0120b1f7c1c9c28fc353e03c9e3f58af0cdb6d49e1f9103bba0611bb8ec551cbaba25d81a1b6338088f03490b7a5d71393f33302ce32e6ecbb8c99a939d", "00", "ba78f10c60ddb73e37b71ac4b7f6df6da3f1a075981532ed1bdd3bf216ddf707252030d9083295c6b4b469462661f51ee53d2471c169b74479ae811843a24eee5043a80355db8853d63f76cb7545211d604f0ac7dd8daf64d5b195b8e55c796232e19552d1a4a91f2340935907753559649db56c10b23a141a2f25688c59ddc3", "01"),
+        ("00", "0acd215027e7c9a3fb41a1b5c7", "e9b7f627179ee05607c8f2cc2aea18bd18202d65055a5ad0a50621255d0a29a37ac5b3f4b09ff5fb987074c589dc1f77cb29b12a5101ed9f04c18fdec775bacd", "00"),
+        ("9f1b045bdc9fd9a9f60c7c1748e3c964012fed6372fe60d612c48b04c39bba80afd34cb6bf085a0052218bdffe3adf747707d607c8cd670f0d04e8d9326cc074", "01", "884592f842fb757bae1e093ea8152d2c0a51cd9cfc2ec72c51cf6391c150ba6d", "4c83b89e6cc4d6ce9bf6b8dd4f43b62bd6be6e12d4e93ba81f1f75d8151a90de"),
+        ("02", "03fffffffffffffffffffffffffffffffb", "03", "02"),
+    ];
+
+    /// The integer `s`, and the same value built from limbs with leading
+    /// zeros, which must behave identically.
+    fn ints(s: &str) -> [BoxedUint; 2] {
+        let x = BoxedUint::from_be_bytes(&unhex(s));
+        let mut limbs = be_bytes_to_limbs(&unhex(s));
+        limbs.extend([0, 0]);
+
+        [x, BoxedUint::from_limbs(limbs)]
+    }
+
+    fn int(s: &str) -> BoxedUint {
+        BoxedUint::from_be_bytes(&unhex(s))
+    }
+
+    #[test]
+    fn div_rem() {
+        for (a, b, q, r) in DIV_REM {
+            for (a, b) in ints(a).iter().zip(ints(b)) {
+                assert_eq!(a.div_rem(&b), (int(q), int(r)), "{a:?} / {b:?}");
+                assert_eq!(&(&(&int(q) * &b) + &int(r)), a);
+            }
+        }
+    }
+
+    #[test]
+    fn mod_inverse() {
+        for (a, m, inv) in MOD_INVERSE {
+            for (a, m) in ints(a).iter().zip(ints(m)) {
+                assert_eq!(a.mod_inverse(&m), inv.map(int), "{a:?}^-1 mod {m:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn gcd() {
+        for (a, b, gcd) in GCD {
+            for (a, b) in ints(a).iter().zip(ints(b)) {
+                assert_eq!(a.gcd(&b), int(gcd), "gcd({a:?}, {b:?})");
+            }
+        }
+    }
+
+    #[test]
+    fn pow() {
+        for (base, exp, m, expected) in POW {
+            for ((base, exp), m) in ints(base).iter().zip(ints(exp)).zip(ints(m)) {
+                let mont = Montgomery::new(&m).unwrap();
+
+                assert_eq!(mont.pow(base, &exp), int(expected), "{base:?}^{exp:?}");
+                assert_eq!(mont.pow_vartime(base, &exp), int(expected));
+                assert_eq!(base.pow_mod(&exp, &m), int(expected));
+            }
+        }
+    }
+
+    /// Values at and above the modulus, but below `R`, are reduced without
+    /// being compared with it.
+    #[test]
+    fn montgomery_reduction() {
+        let m = int("0100000000000000000000000000000001");
+        let mont = Montgomery::new(&m).unwrap();
+
+        for x in [
+            "0100000000000000000000000000000001",
+            "0100000000000000000000000000000002",
+            "ffffffffffffffffffffffffffffffffffffffffffffffff",
+            "01000000000000000000000000000000000000000000000000",
+        ] {
+            assert_eq!(mont.mul(&int(x), &BoxedUint::one()), &int(x) % &m);
+        }
+    }
+
+    #[test]
+    fn montgomery_moduli() {
+        assert_eq!(Montgomery::new(&BoxedUint::zero()), None);
+        assert_eq!(Montgomery::new(&BoxedUint::one()), None);
+        assert_eq!(Montgomery::new(&BoxedUint::from_u64(1 << 40)), None);
+        assert!(Montgomery::new(&BoxedUint::from_u64(3)).is_some());
+    }
+}
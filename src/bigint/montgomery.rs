@@ -0,0 +1,190 @@
+//! # Montgomery arithmetic
+//! Multiplication and exponentiation modulo an odd [`BoxedUint`] fixed at
+//! runtime, as [`crate::ecc::modular`] does for moduli fixed at compile
+//! time. Values are padded to the length of the modulus, so the time taken
+//! depends on the modulus and the length of the exponent only.
+
+use super::{boxed::BoxedUint, sub_assign_limbs};
+
+/// The precomputed values for arithmetic modulo an odd `m > 1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Montgomery {
+    modulus: BoxedUint,
+    /// `m` padded to `n` limbs, with `R = 2^(64 n)`.
+    m: Vec<u64>,
+    /// `-m^-1 mod 2^64`
+    inv: u64,
+    /// `R mod m`
+    r1: Vec<u64>,
+    /// `R^2 mod m`
+    r2: Vec<u64>,
+}
+
+impl Montgomery {
+    /// `None` unless `modulus` is odd and above 1.
+    pub fn new(modulus: &BoxedUint) -> Option<Montgomery> {
+        if !modulus.is_odd() || *modulus == BoxedUint::one() {
+            return None;
+        }
+
+        let n = modulus.as_limbs().len();
+        let r1 = &(&BoxedUint::one() << (64 * n)) % modulus;
+        let r2 = &(&r1 * &r1) % modulus;
+
+        // Newton's iteration doubles the number of correct low bits each time.
+        let m0 = modulus.as_limbs()[0];
+        let inv = (0..6).fold(1u64, |inv, _| {
+            inv.wrapping_mul(2u64.wrapping_sub(m0.wrapping_mul(inv)))
+        });
+
+        Some(Montgomery {
+            modulus: modulus.clone(),
+            m: modulus.as_limbs().to_vec(),
+            inv: inv.wrapping_neg(),
+            r1: r1.to_limbs_padded(n),
+            r2: r2.to_limbs_padded(n),
+        })
+    }
+
+    pub fn modulus(&self) -> &BoxedUint {
+        &self.modulus
+    }
+
+    /// `a b mod m`
+    pub fn mul(&self, a: &BoxedUint, b: &BoxedUint) -> BoxedUint {
+        let a = self.to_montgomery(a);
+        let b = self.to_montgomery(b);
+
+        self.to_canonical(&self.mont_mul(&a, &b))
+    }
+
+    /// `base^exp mod m` with 4-bit fixed windows and a constant-time table
+    /// lookup. The exponent is padded to the width of `m`, so every exponent
+    /// that fits in it takes the same operations. Safe for secret exponents
+    /// and bases.
+    pub fn pow(&self, base: &BoxedUint, exp: &BoxedUint) -> BoxedUint {
+        let mut table = vec![self.r1.clone(), self.to_montgomery(base)];
+        for i in 2..16 {
+            table.push(self.mont_mul(&table[i - 1], &table[1]));
+        }
+
+        let mut acc = self.r1.clone();
+
+        let width = self.m.len().max(exp.as_limbs().len());
+
+        for limb in exp.to_limbs_padded(width).iter().rev() {
+            for w in (0..16).rev() {
+                for _ in 0..4 {
+                    acc = self.mont_mul(&acc, &acc);
+                }
+
+                let digit = (limb >> (4 * w)) & 0xf;
+                acc = self.mont_mul(&acc, &select(&table, digit));
+            }
+        }
+
+        self.to_canonical(&acc)
+    }
+
+    /// `base^exp mod m` by square-and-multiply, only for public exponents
+    /// such as the RSA `e`.
+    pub fn pow_vartime(&self, base: &BoxedUint, exp: &BoxedUint) -> BoxedUint {
+        let base = self.to_montgomery(base);
+        let mut acc = self.r1.clone();
+
+        for i in (0..exp.bits()).rev() {
+            acc = self.mont_mul(&acc, &acc);
+
+            if exp.bit(i) {
+                acc = self.mont_mul(&acc, &base);
+            }
+        }
+
+        self.to_canonical(&acc)
+    }
+
+    /// `x R mod m`. Any `x < R` goes straight into the multiplication by
+    /// `R^2 mod m`, which reduces it fully, so the value is never compared
+    /// with `m`. Only `x` with more limbs than `m` is reduced first, in
+    /// variable time.
+    fn to_montgomery(&self, x: &BoxedUint) -> Vec<u64> {
+        let x = if x.as_limbs().len() <= self.m.len() {
+            x.to_limbs_padded(self.m.len())
+        } else {
+            (x % &self.modulus).to_limbs_padded(self.m.len())
+        };
+
+        self.mont_mul(&x, &self.r2)
+    }
+
+    fn to_canonical(&self, x: &[u64]) -> BoxedUint {
+        let mut one = vec![0u64; self.m.len()];
+        one[0] = 1;
+
+        BoxedUint::from_limbs(self.mont_mul(x, &one))
+    }
+
+    /// Montgomery multiplication `a b / R mod m` (CIOS), for `a b < R m`.
+    fn mont_mul(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let n = self.m.len();
+
+        // t holds the low n limbs of the accumulator, t_n the limb above them.
+        let mut t = vec![0u64; n];
+        let mut t_n = 0u64;
+
+        for b_i in b {
+            let mut c = 0u128;
+
+            for j in 0..n {
+                let x = t[j] as u128 + a[j] as u128 * *b_i as u128 + c;
+                t[j] = x as u64;
+                c = x >> 64;
+            }
+
+            let x = t_n as u128 + c;
+            t_n = x as u64;
+            let t_n1 = (x >> 64) as u64;
+
+            let u = t[0].wrapping_mul(self.inv);
+            let mut c = (t[0] as u128 + u as u128 * self.m[0] as u128) >> 64;
+
+            for j in 1..n {
+                let x = t[j] as u128 + u as u128 * self.m[j] as u128 + c;
+                t[j - 1] = x as u64;
+                c = x >> 64;
+            }
+
+            let x = t_n as u128 + c;
+            t[n - 1] = x as u64;
+            t_n = t_n1 + (x >> 64) as u64;
+        }
+
+        let mut diff = t.clone();
+        let borrow = sub_assign_limbs(&mut diff, &self.m);
+
+        // t < 2m: subtract m unless that underflows with no carry limb.
+        let keep = ((t_n ^ 1) & borrow).wrapping_neg();
+
+        diff.iter()
+            .zip(&t)
+            .map(|(d, t)| d ^ (keep & (d ^ t)))
+            .collect()
+    }
+}
+
+/// `table[index]`, reading every entry so that the access pattern does not
+/// depend on `index`.
+fn select(table: &[Vec<u64>], index: u64) -> Vec<u64> {
+    let mut res = vec![0u64; table[0].len()];
+
+    for (i, entry) in table.iter().enumerate() {
+        let mask = ((i as u64 ^ index) == 0) as u64;
+        let mask = mask.wrapping_neg();
+
+        for (r, e) in res.iter_mut().zip(entry) {
+            *r |= mask & e;
+        }
+    }
+
+    res
+}
@@ -0,0 +1,416 @@
+//! # Fixed-width integers
+//! [`Uint<N>`] is an unsigned integer of exactly `N` 64-bit limbs, on the
+//! stack. Its arithmetic wraps around modulo `2^(64 N)` and reports the carry
+//! or borrow, and never branches on the values; only [`Uint::div_rem`] is
+//! variable time.
+
+use std::{cmp::Ordering, fmt::Debug};
+
+use super::{add_assign_limbs, boxed::BoxedUint, mul_limbs, sub_assign_limbs};
+
+/// An unsigned integer of `N` little-endian 64-bit limbs.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Uint<const N: usize>(pub [u64; N]);
+
+impl<const N: usize> Debug for Uint<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Uint(0x")?;
+
+        for limb in self.0.iter().rev() {
+            write!(f, "{limb:016x}")?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+impl<const N: usize> Uint<N> {
+    pub const ZERO: Self = Uint([0; N]);
+    pub const ONE: Self = {
+        let mut limbs = [0; N];
+        limbs[0] = 1;
+        Uint(limbs)
+    };
+    pub const MAX: Self = Uint([u64::MAX; N]);
+
+    pub fn from_u64(x: u64) -> Self {
+        let mut limbs = [0; N];
+        limbs[0] = x;
+
+        Uint(limbs)
+    }
+
+    /// A big-endian integer, or `None` if it doesn't fit in `N` limbs.
+    pub fn from_be_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::from_boxed(&BoxedUint::from_be_bytes(bytes))
+    }
+
+    /// The `8 N` byte big-endian encoding.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        self.0
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes())
+            .collect()
+    }
+
+    /// `None` if `x` doesn't fit in `N` limbs.
+    pub fn from_boxed(x: &BoxedUint) -> Option<Self> {
+        (x.as_limbs().len() <= N).then(|| Uint(x.to_limbs_padded(N).try_into().unwrap()))
+    }
+
+    pub fn to_boxed(&self) -> BoxedUint {
+        BoxedUint::from_limbs(self.0.to_vec())
+    }
+
+    /// `self + rhs mod 2^(64 N)`, and whether it overflowed.
+    pub fn overflowing_add(&self, rhs: &Self) -> (Self, bool) {
+        let mut res = self.0;
+        let carry = add_assign_limbs(&mut res, &rhs.0);
+
+        (Uint(res), carry == 1)
+    }
+
+    /// `self - rhs mod 2^(64 N)`, and whether it underflowed.
+    pub fn overflowing_sub(&self, rhs: &Self) -> (Self, bool) {
+        let mut res = self.0;
+        let borrow = sub_assign_limbs(&mut res, &rhs.0);
+
+        (Uint(res), borrow == 1)
+    }
+
+    /// The full `2 N` limb product, as its low and high halves.
+    pub fn widening_mul(&self, rhs: &Self) -> (Self, Self) {
+        let product = mul_limbs(&self.0, &rhs.0);
+
+        (
+            Uint(product[..N].try_into().unwrap()),
+            Uint(product[N..].try_into().unwrap()),
+        )
+    }
+
+    /// `(self / rhs, self % rhs)` by long division, in variable time like
+    /// [`BoxedUint::div_rem`]. Panics if `rhs` is zero.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let (q, r) = self.to_boxed().div_rem(&rhs.to_boxed());
+
+        (Self::from_boxed(&q).unwrap(), Self::from_boxed(&r).unwrap())
+    }
+
+    /// `self * 2^k mod 2^(64 N)`, for `k < 64 N`. Constant time in the value.
+    pub fn shl(&self, k: usize) -> Self {
+        let (words, bits) = (k / 64, k % 64);
+
+        Uint(std::array::from_fn(|i| {
+            let lo = i.checked_sub(words).map_or(0, |j| self.0[j] << bits);
+            let hi = match (i.checked_sub(words + 1), bits) {
+                (Some(j), 1..) => self.0[j] >> (64 - bits),
+                _ => 0,
+            };
+
+            lo | hi
+        }))
+    }
+
+    /// `self / 2^k`, for `k < 64 N`. Constant time in the value.
+    pub fn shr(&self, k: usize) -> Self {
+        let (words, bits) = (k / 64, k % 64);
+
+        Uint(std::array::from_fn(|i| {
+            let lo = self.0.get(i + words).map_or(0, |x| x >> bits);
+            let hi = match (self.0.get(i + words + 1), bits) {
+                (Some(x), 1..) => x << (64 - bits),
+                _ => 0,
+            };
+
+            lo | hi
+        }))
+    }
+
+    /// The bit length, 0 for zero.
+    pub fn bits(&self) -> usize {
+        self.to_boxed().bits()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().fold(0, |acc, x| acc | x) == 0
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        let diff = self
+            .0
+            .iter()
+            .zip(other.0)
+            .fold(0, |acc, (a, b)| acc | (a ^ b));
+
+        diff == 0
+    }
+
+    /// `self < other`, from the borrow of `self - other`.
+    pub fn ct_lt(&self, other: &Self) -> bool {
+        self.overflowing_sub(other).1
+    }
+
+    /// `b` if `choice` else `a`, without branching on `choice`.
+    pub fn select(a: &Self, b: &Self, choice: bool) -> Self {
+        let mask = (choice as u64).wrapping_neg();
+
+        Uint(std::array::from_fn(|i| a.0[i] ^ (mask & (a.0[i] ^ b.0[i]))))
+    }
+}
+
+impl<const N: usize> PartialOrd for Uint<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for Uint<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const N: usize> From<u64> for Uint<N> {
+    fn from(x: u64) -> Self {
+        Uint::from_u64(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex;
+
+    // Known answers from Python's integers, as big-endian hex.
+
+    /// `a`, `b`, `a + b`, its carry, `a - b` and its borrow.
+    const ADD_SUB: [(&str, &str, &str, bool, &str, bool); 7] = [
+        (
+            "e338e970dc1afab8963f389496afcff50a3aee4966660879138dda71e3658966",
+            "cd6a4292f27baaf989bc15a5956f5c7126e7581a84060c46a27056f73a818b9f",
+            "b0a32c03ce96a5b21ffb4e3a2c1f2c6631224663ea6c14bfb5fe31691de71505",
+            true,
+            "15cea6dde99f4fbf0c8322ef01407383e353962ee25ffc32711d837aa8e3fdc7",
+            false,
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "01",
+            "00",
+            true,
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe",
+            false,
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe",
+            true,
+            "00",
+            false,
+        ),
+        ("00", "00", "00", false, "00", false),
+        (
+            "0100000000000000000000000000000000",
+            "ffffffffffffffff",
+            "010000000000000000ffffffffffffffff",
+            false,
+            "ffffffffffffffff0000000000000001",
+            false,
+        ),
+        (
+            "d3a1d551dc51f10900c87ced6d11a64ad207c7ac10083d0a2f",
+            "871be4434b9a3682eb66f9888c75603722a8ff1c07e70715d7d8a6c30df0fadc",
+            "871be4434b9a37568d3c4b64de666937eb25ec89198d51e7dfa052d3162e050b",
+            false,
+            "78e41bbcb465ca50b66e5853c57ba8c9a5d3ee5109bf43bc2fef054cfa4c0f53",
+            true,
+        ),
+        (
+            "f96fe97365e12e6a17c9b326c33709e3ef3ca884b6989668f7c8122a54644417",
+            "07a8efdeb4895688",
+            "f96fe97365e12e6a17c9b326c33709e3ef3ca884b6989668ff71020908ed9a9f",
+            false,
+            "f96fe97365e12e6a17c9b326c33709e3ef3ca884b6989668f01f224b9fdaed8f",
+            false,
+        ),
+    ];
+
+    /// `a`, `b` and the low and high halves of `a b`.
+    const MUL: [(&str, &str, &str, &str); 5] = [
+        (
+            "e338e970dc1afab8963f389496afcff50a3aee4966660879138dda71e3658966",
+            "cd6a4292f27baaf989bc15a5956f5c7126e7581a84060c46a27056f73a818b9f",
+            "4d35e9be0a21c29964e1140db56225686a5682979cfc1c53375eefe9b610b85a",
+            "b652e39722da89d43db11356d46dea7923f1f100ee4dbaa0df91eb8126f027ab",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "01",
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe",
+        ),
+        (
+            "0100000000000000000000000000000000",
+            "ffffffffffffffff",
+            "ffffffffffffffff00000000000000000000000000000000",
+            "00",
+        ),
+        (
+            "d3a1d551dc51f10900c87ced6d11a64ad207c7ac10083d0a2f",
+            "871be4434b9a3682eb66f9888c75603722a8ff1c07e70715d7d8a6c30df0fadc",
+            "f85739cb20d713bfd9fd364cfe7d6818a3bd91b63c14a27cb8d3945d9f76a664",
+            "6fb166436ea9154f7c0dbb9e60d4f5a23bea1cad4a0843d7cd",
+        ),
+        (
+            "f96fe97365e12e6a17c9b326c33709e3ef3ca884b6989668f7c8122a54644417",
+            "07a8efdeb4895688",
+            "1e261025c4aa0283eb6d660211c58b3758a69f684c87d7006efe0d512072e638",
+            "0776aa8bd56bb394",
+        ),
+    ];
+
+    /// `a`, `b`, `a / b` and `a % b`.
+    const DIV_REM: [(&str, &str, &str, &str); 8] = [
+        (
+            "bfb63d9e75a75f2013069e53d4a4405777321e857881549127f6e6495c41c3db",
+            "81aeb810562d3d5c39f2a8a76ec30d101c0072e59e8c85898b5f46afb24b5692",
+            "01",
+            "3e07858e1f7a21c3d913f5ac65e133475b31ab9fd9f4cf079c979f99a9f66d49",
+        ),
+        (
+            "5ad023419840ede51b4ea5c2273fe1f7466af77fb762bba6c74b7a7471a71244",
+            "0155e8dc409bf05d6111ac0c76e06ced8c",
+            "43feb71e783fc174bc99a1cdb39ca868",
+            "010761c9b3d993f9bcf890354c2432b164",
+        ),
+        (
+            "c6b54165c5efc8e65b4c42944d3e6fc36244ba182f09e29867f0a484df312a07",
+            "d43cc23d34228470",
+            "efae5559c97e2186bc8732ef1097abbc0b536ca70a408f41",
+            "4df91bfdde72f997",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "01",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "00",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "010000000000000001",
+            "ffffffffffffffff0000000000000000ffffffffffffffff",
+            "00",
+        ),
+        (
+            "0f3f8754ce95524b291d668a64",
+            "c197cae81088ed14506c3a2ead3e63e2b486f065b634090ab77631029c3b69ac",
+            "00",
+            "0f3f8754ce95524b291d668a64",
+        ),
+        ("00", "07", "00", "00"),
+        (
+            "87a12b595da86b482ed777538f62cb885e6e9b4a1da45e02cfcd7bd648afeed2",
+            "b8543de82beb79f7d50eb76d9510b807a3348bff91902796a97cac00c0503412",
+            "00",
+            "87a12b595da86b482ed777538f62cb885e6e9b4a1da45e02cfcd7bd648afeed2",
+        ),
+    ];
+
+    fn uint(s: &str) -> Uint<4> {
+        Uint::from_be_bytes(&unhex(s)).unwrap()
+    }
+
+    #[test]
+    fn add_sub() {
+        for (a, b, sum, carry, diff, borrow) in ADD_SUB {
+            let (a, b) = (uint(a), uint(b));
+
+            assert_eq!(a.overflowing_add(&b), (uint(sum), carry), "{a:?} + {b:?}");
+            assert_eq!(a.overflowing_sub(&b), (uint(diff), borrow), "{a:?} - {b:?}");
+            assert_eq!(a.ct_lt(&b), a < b);
+        }
+    }
+
+    #[test]
+    fn widening_mul() {
+        for (a, b, lo, hi) in MUL {
+            let (a, b) = (uint(a), uint(b));
+
+            assert_eq!(a.widening_mul(&b), (uint(lo), uint(hi)), "{a:?} * {b:?}");
+            assert_eq!(b.widening_mul(&a), (uint(lo), uint(hi)));
+        }
+    }
+
+    #[test]
+    fn div_rem() {
+        for (a, b, q, r) in DIV_REM {
+            let (a, b) = (uint(a), uint(b));
+
+            assert_eq!(a.div_rem(&b), (uint(q), uint(r)), "{a:?} / {b:?}");
+        }
+    }
+
+    #[test]
+    fn shifts() {
+        let x = uint("56530c9fdbb6cdefbdcee8d019c6a57f64f10ef410d971d8308173468ae86eff");
+
+        for (k, shl, shr) in [
+            (
+                0,
+                "56530c9fdbb6cdefbdcee8d019c6a57f64f10ef410d971d8308173468ae86eff",
+                "56530c9fdbb6cdefbdcee8d019c6a57f64f10ef410d971d8308173468ae86eff",
+            ),
+            (
+                1,
+                "aca6193fb76d9bdf7b9dd1a0338d4afec9e21de821b2e3b06102e68d15d0ddfe",
+                "2b29864feddb66f7dee774680ce352bfb278877a086cb8ec1840b9a34574377f",
+            ),
+            (
+                63,
+                "dee774680ce352bfb278877a086cb8ec1840b9a34574377f8000000000000000",
+                "aca6193fb76d9bdf7b9dd1a0338d4afec9e21de821b2e3b0",
+            ),
+            (
+                64,
+                "bdcee8d019c6a57f64f10ef410d971d8308173468ae86eff0000000000000000",
+                "56530c9fdbb6cdefbdcee8d019c6a57f64f10ef410d971d8",
+            ),
+            (
+                65,
+                "7b9dd1a0338d4afec9e21de821b2e3b06102e68d15d0ddfe0000000000000000",
+                "2b29864feddb66f7dee774680ce352bfb278877a086cb8ec",
+            ),
+            (
+                130,
+                "93c43bd04365c760c205cd1a2ba1bbfc00000000000000000000000000000000",
+                "1594c327f6edb37bef73ba340671a95f",
+            ),
+            (
+                255,
+                "8000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+            ),
+        ] {
+            assert_eq!(x.shl(k), uint(shl), "<< {k}");
+            assert_eq!(x.shr(k), uint(shr), ">> {k}");
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        let x = uint("f96fe97365e12e6a17c9b326c33709e3ef3ca884b6989668f7c8122a54644417");
+
+        assert_eq!(Uint::<4>::from_boxed(&x.to_boxed()), Some(x));
+        assert_eq!(Uint::<4>::from_be_bytes(&x.to_be_bytes()), Some(x));
+        assert_eq!(Uint::<4>::from_be_bytes(&[1; 33]), None);
+        assert_eq!(Uint::<4>::from_be_bytes(&[0; 40]), Some(Uint::ZERO));
+        assert_eq!(Uint::<4>::MAX.bits(), 256);
+        assert!(Uint::<4>::select(&Uint::ONE, &x, true).ct_eq(&x));
+    }
+}
@@ -0,0 +1,305 @@
+//! # BIP-340 Schnorr signatures
+//! Schnorr signatures over [`super::secp256k1`] with x-only public keys, as
+//! used by Taproot.
+//!
+//! Keys and nonces are only ever used with an even y-coordinate, so a point
+//! travels as its 32-byte x-coordinate. Every hash is a tagged hash
+//! `SHA256(SHA256(tag) || SHA256(tag) || msg)`, which starts from the same
+//! 64 bytes for a given tag; their midstates are computed once and reused.
+
+use lazy_static::lazy_static;
+
+use super::secp256k1::{FieldElement, ProjectivePoint, Scalar};
+use crate::{rng, sha::sha256::Sha256};
+
+lazy_static! {
+    static ref AUX: ([u32; 8], u64) = Sha256::tagged(b"BIP0340/aux").midstate().unwrap();
+    static ref NONCE: ([u32; 8], u64) = Sha256::tagged(b"BIP0340/nonce").midstate().unwrap();
+    static ref CHALLENGE: ([u32; 8], u64) =
+        Sha256::tagged(b"BIP0340/challenge").midstate().unwrap();
+}
+
+/// A BIP-340 private key.
+#[derive(Clone)]
+pub struct SigningKey {
+    /// The secret scalar, negated if needed so that `[d] G` has even y.
+    d: Scalar,
+    verifying_key: VerifyingKey,
+}
+
+/// An x-only public key, the point with this x-coordinate and even y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyingKey {
+    bytes: [u8; 32],
+    point: ProjectivePoint,
+}
+
+/// A BIP-340 signature `x(R) || s`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature(pub [u8; 64]);
+
+impl SigningKey {
+    /// A fresh key from the operating system's CSPRNG.
+    pub fn generate() -> SigningKey {
+        loop {
+            if let Some(key) = SigningKey::from_bytes(&rng::bytes()) {
+                return key;
+            }
+        }
+    }
+
+    /// `None` unless `bytes` is a big-endian integer in `1..n`.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<SigningKey> {
+        let d = Scalar::from_canonical_be_bytes(bytes)?;
+
+        if d.is_zero() {
+            return None;
+        }
+
+        let point = ProjectivePoint::mul_base(&d);
+        let (x, y) = point.to_affine()?;
+
+        Some(SigningKey {
+            d: Scalar::select(&d, &-d, y.is_odd()),
+            verifying_key: VerifyingKey {
+                bytes: x.to_be_bytes().try_into().unwrap(),
+                point: ProjectivePoint::select(&point, &-point, y.is_odd()),
+            },
+        })
+    }
+
+    /// The scalar with an even-y public key, which may be the negation of
+    /// the one this key was created from.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.d.to_be_bytes().try_into().unwrap()
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+
+    /// Sign `msg` with 32 bytes of fresh auxiliary randomness.
+    pub fn sign(&self, msg: &[u8]) -> Signature {
+        self.sign_with_aux_rand(msg, &rng::bytes())
+    }
+
+    /// Sign `msg` with the auxiliary randomness `aux_rand`, which is mixed
+    /// into the nonce; all zeros gives deterministic signatures.
+    pub fn sign_with_aux_rand(&self, msg: &[u8], aux_rand: &[u8; 32]) -> Signature {
+        let mut t = tagged_hash(&AUX, &[aux_rand]);
+        for (t_i, d_i) in t.iter_mut().zip(self.d.to_be_bytes()) {
+            *t_i ^= d_i;
+        }
+
+        let rand = tagged_hash(&NONCE, &[&t, &self.verifying_key.bytes, msg]);
+        let k = Scalar::from_be_bytes(&rand);
+
+        // Only hit with negligible probability.
+        assert!(!k.is_zero(), "nonce is zero");
+
+        let (r, y) = ProjectivePoint::mul_base(&k).to_affine().unwrap();
+        let k = Scalar::select(&k, &-k, y.is_odd());
+        let r = r.to_be_bytes();
+
+        let e = challenge(&r, &self.verifying_key.bytes, msg);
+        let s = k + e * self.d;
+
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&r);
+        sig[32..].copy_from_slice(&s.to_be_bytes());
+
+        Signature(sig)
+    }
+}
+
+impl VerifyingKey {
+    /// `None` unless `bytes` is the x-coordinate of a curve point.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<VerifyingKey> {
+        Some(VerifyingKey {
+            bytes: *bytes,
+            point: lift_x(bytes)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    /// Check that `R = [s] G - [e] P` has even y and x-coordinate `r`.
+    pub fn verify(&self, msg: &[u8], sig: &Signature) -> bool {
+        let Some((r, s)) = decode(sig) else {
+            return false;
+        };
+
+        let e = challenge(&sig.0[..32], &self.bytes, msg);
+        let big_r = ProjectivePoint::vartime_multiscalar_mul(
+            &[s, -e],
+            &[ProjectivePoint::generator(), self.point],
+        );
+
+        match big_r.to_affine() {
+            Some((x, y)) => !y.is_odd() && x == r,
+            None => false,
+        }
+    }
+}
+
+/// Verify signatures on `messages[i]` by `keys[i]` at once, by checking a
+/// random linear combination of the verification equations with a single
+/// multi-scalar multiplication. Accepts exactly when every signature
+/// verifies, except with negligible probability.
+pub fn verify_batch(messages: &[&[u8]], signatures: &[Signature], keys: &[VerifyingKey]) -> bool {
+    assert_eq!(messages.len(), signatures.len());
+    assert_eq!(messages.len(), keys.len());
+
+    let mut g_coeff = Scalar::ZERO;
+    let mut scalars = vec![];
+    let mut points = vec![];
+
+    for ((msg, sig), key) in messages.iter().zip(signatures).zip(keys) {
+        let Some((_, s)) = decode(sig) else {
+            return false;
+        };
+        let Some(r) = lift_x(&sig.0[..32]) else {
+            return false;
+        };
+
+        let e = challenge(&sig.0[..32], &key.bytes, msg);
+
+        let mut a = [0u8; 16];
+        rng::fill(&mut a);
+        let a = Scalar::from_le_bytes(&a);
+
+        g_coeff = g_coeff - a * s;
+        scalars.extend([a, a * e]);
+        points.extend([r, key.point]);
+    }
+
+    scalars.push(g_coeff);
+    points.push(ProjectivePoint::generator());
+
+    ProjectivePoint::vartime_multiscalar_mul(&scalars, &points).is_identity()
+}
+
+/// Split `r || s`, rejecting `r >= p` and `s >= n`.
+fn decode(sig: &Signature) -> Option<(FieldElement, Scalar)> {
+    let r = FieldElement::from_canonical_be_bytes(&sig.0[..32])?;
+    let s = Scalar::from_canonical_be_bytes(&sig.0[32..])?;
+
+    Some((r, s))
+}
+
+/// The point with x-coordinate `x` and even y, if there is one.
+fn lift_x(x: &[u8]) -> Option<ProjectivePoint> {
+    ProjectivePoint::from_sec1(&[&[2], x].concat())
+}
+
+/// `e = hash_challenge(r || P || m) mod n`
+pub(crate) fn challenge(r: &[u8], key: &[u8; 32], msg: &[u8]) -> Scalar {
+    Scalar::from_be_bytes(&tagged_hash(&CHALLENGE, &[r, key, msg]))
+}
+
+/// The tagged hash of the concatenation of `parts`, resumed from the
+/// midstate of its tag.
+fn tagged_hash(midstate: &([u32; 8], u64), parts: &[&[u8]]) -> [u8; 32] {
+    let mut sha = Sha256::from_midstate(midstate.0, midstate.1);
+
+    for part in parts {
+        sha.update(part);
+    }
+
+    sha.result()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{unhex, unhex_array};
+
+    /// test-vectors.csv of BIP-340.
+    const VECTORS: &str = "\
+index,secret key,public key,aux_rand,message,signature,verification result,comment
+0,0000000000000000000000000000000000000000000000000000000000000003,F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9,0000000000000000000000000000000000000000000000000000000000000000,0000000000000000000000000000000000000000000000000000000000000000,E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0,TRUE,
+1,B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,0000000000000000000000000000000000000000000000000000000000000001,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A,TRUE,
+2,C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9,DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8,C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906,7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C,5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1BAB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7,TRUE,
+3,0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710,25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF,7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3,TRUE,test fails if msg is reduced modulo p or n
+4,,D69C3509BB99E412E68B0FE8544E72837DFA30746D8BE2AA65975F29D22DC7B9,,4DF3C3F68FCC83B27E9D42C90431A72499F17875C81A599B566C9889B9696703,00000000000000000000003B78CE563F89A0ED9414F5AA28AD0D96D6795F9C6376AFB1548AF603B3EB45C9F8207DEE1060CB71C04E80F593060B07D28308D7F4,TRUE,
+5,,EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,public key not on the curve
+6,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A14602975563CC27944640AC607CD107AE10923D9EF7A73C643E166BE5EBEAFA34B1AC553E2,FALSE,has_even_y(R) is false
+7,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,1FA62E331EDBC21C394792D2AB1100A7B432B013DF3F6FF4F99FCB33E0E1515F28890B3EDB6E7189B630448B515CE4F8622A954CFE545735AAEA5134FCCDB2BD,FALSE,negated message
+8,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769961764B3AA9B2FFCB6EF947B6887A226E8D7C93E00C5ED0C1834FF0D0C2E6DA6,FALSE,negated s value
+9,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,0000000000000000000000000000000000000000000000000000000000000000123DDA8328AF9C23A94C1FEECFD123BA4FB73476F0D594DCB65C6425BD186051,FALSE,sG - eP is infinite. Test fails in single verification if has_even_y(inf) is defined as true and x(inf) as 0
+10,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,00000000000000000000000000000000000000000000000000000000000000017615FBAF5AE28864013C099742DEADB4DBA87F11AC6754F93780D5A1837CF197,FALSE,sG - eP is infinite. Test fails in single verification if has_even_y(inf) is defined as true and x(inf) as 1
+11,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,4A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1D69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,sig[0:32] is not an X coordinate on the curve
+12,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,sig[0:32] is equal to field size
+13,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141,FALSE,sig[32:64] is equal to curve order
+14,,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,public key is not a valid X coordinate because it exceeds the field size
+15,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,,71535DB165ECD9FBBC046E5FFAEA61186BB6AD436732FCCC25291A55895464CF6069CE26BF03466228F19A3A62DB8A649F2D560FAC652827D1AF0574E427AB63,TRUE,message of size 0
+16,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,11,08A20A0AFEF64124649232E0693C583AB1B9934AE63B4C3511F3AE1134C6A303EA3173BFEA6683BD101FA5AA5DBC1996FE7CACFC5A577D33EC14564CEC2BACBF,TRUE,message of size 1
+17,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,0102030405060708090A0B0C0D0E0F1011,5130F39A4059B43BC7CAC09A19ECE52B5D8699D1A71E3C52DA9AFDB6B50AC370C4A482B77BF960F8681540E25B6771ECE1E5A37FD80E5A51897C5566A97EA5A5,TRUE,message of size 17
+18,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,99999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999,403B12B0D8555A344175EA7EC746566303321E5DBFA8BE6F091635163ECA79A8585ED3E3170807E7C03B720FC54C7B23897FCBA0E9D0B4A06894CFD249F22367,TRUE,message of size 100
+";
+
+    #[test]
+    fn vectors() {
+        for line in VECTORS.lines().skip(1) {
+            let [index, sk, pk, aux_rand, msg, sig, result, _] =
+                line.split(',').collect::<Vec<_>>().try_into().unwrap();
+            let (msg, sig) = (unhex(msg), Signature(unhex_array(sig)));
+
+            if !sk.is_empty() {
+                let key = SigningKey::from_bytes(&unhex_array(sk)).unwrap();
+
+                assert_eq!(
+                    key.verifying_key().to_bytes(),
+                    unhex_array(pk),
+                    "row {index}"
+                );
+                assert_eq!(
+                    key.sign_with_aux_rand(&msg, &unhex_array(aux_rand)),
+                    sig,
+                    "row {index}"
+                );
+            }
+
+            let verified = VerifyingKey::from_bytes(&unhex_array(pk))
+                .is_some_and(|key| key.verify(&msg, &sig));
+
+            assert_eq!(verified, result == "TRUE", "row {index}");
+        }
+    }
+
+    /// The valid rows of [`VECTORS`] pass as one batch, and fail together
+    /// once any of their signatures is tampered with.
+    #[test]
+    fn batch() {
+        let rows = VECTORS
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').collect::<Vec<_>>())
+            .filter(|row| row[6] == "TRUE")
+            .collect::<Vec<_>>();
+
+        let messages = rows.iter().map(|row| unhex(row[4])).collect::<Vec<_>>();
+        let messages = messages.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let mut signatures = rows
+            .iter()
+            .map(|row| Signature(unhex_array(row[5])))
+            .collect::<Vec<_>>();
+        let keys = rows
+            .iter()
+            .map(|row| VerifyingKey::from_bytes(&unhex_array(row[2])).unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(verify_batch(&messages, &signatures, &keys));
+
+        for i in [0, signatures.len() - 1] {
+            signatures[i].0[63] ^= 1;
+            assert!(!verify_batch(&messages, &signatures, &keys));
+            signatures[i].0[63] ^= 1;
+        }
+
+        signatures.swap(1, 2);
+        assert!(!verify_batch(&messages, &signatures, &keys));
+    }
+}
@@ -0,0 +1,446 @@
+//! # Curve25519
+//! Arithmetic in GF(2^255 - 19) and X25519 key agreement (RFC 7748).
+//!
+//! Everything that touches secret data runs in constant time: there are no
+//! branches or table lookups on secret values, only masked selects.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::rng;
+
+const MASK51: u64 = (1 << 51) - 1;
+
+/// An element of GF(2^255 - 19), as five 51-bit limbs, least significant
+/// first. Limbs may exceed 51 bits between reductions.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldElement(pub [u64; 5]);
+
+impl FieldElement {
+    pub const ZERO: FieldElement = FieldElement([0, 0, 0, 0, 0]);
+    pub const ONE: FieldElement = FieldElement([1, 0, 0, 0, 0]);
+    /// A square root of -1.
+    pub const SQRT_M1: FieldElement = FieldElement([
+        0x61b274a0ea0b0,
+        0xd5a5fc8f189d,
+        0x7ef5e9cbd0c60,
+        0x78595a6804c9e,
+        0x2b8324804fc1d,
+    ]);
+
+    /// Little-endian bytes; the top bit is ignored, non-canonical values
+    /// (>= p) are accepted and reduced.
+    pub fn from_bytes(bytes: &[u8; 32]) -> FieldElement {
+        let load = |i: usize| u64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+
+        FieldElement([
+            load(0) & MASK51,
+            (load(6) >> 3) & MASK51,
+            (load(12) >> 6) & MASK51,
+            (load(19) >> 1) & MASK51,
+            (load(24) >> 12) & MASK51,
+        ])
+    }
+
+    /// The canonical little-endian encoding.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut l = reduce(self.0);
+
+        // l < 2p here, so subtracting p once is enough: q is 1 iff l >= p.
+        let mut q = (l[0] + 19) >> 51;
+        q = (l[1] + q) >> 51;
+        q = (l[2] + q) >> 51;
+        q = (l[3] + q) >> 51;
+        q = (l[4] + q) >> 51;
+
+        l[0] += 19 * q;
+
+        l[1] += l[0] >> 51;
+        l[0] &= MASK51;
+        l[2] += l[1] >> 51;
+        l[1] &= MASK51;
+        l[3] += l[2] >> 51;
+        l[2] &= MASK51;
+        l[4] += l[3] >> 51;
+        l[3] &= MASK51;
+        l[4] &= MASK51;
+
+        let words = [
+            l[0] | l[1] << 51,
+            l[1] >> 13 | l[2] << 38,
+            l[2] >> 26 | l[3] << 25,
+            l[3] >> 39 | l[4] << 12,
+        ];
+
+        let mut bytes = [0u8; 32];
+
+        for (xs, w) in bytes.chunks_exact_mut(8).zip(words) {
+            xs.copy_from_slice(&w.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    pub fn square(&self) -> FieldElement {
+        *self * *self
+    }
+
+    /// `self^(2^k)`
+    pub fn pow2k(&self, k: usize) -> FieldElement {
+        (0..k).fold(*self, |acc, _| acc.square())
+    }
+
+    /// `self^e` for a public exponent `e` given as little-endian bytes.
+    pub fn pow(&self, e: &[u8; 32]) -> FieldElement {
+        let mut res = FieldElement::ONE;
+
+        for i in (0..256).rev() {
+            res = res.square();
+
+            if (e[i / 8] >> (i % 8)) & 1 == 1 {
+                res = res * *self;
+            }
+        }
+
+        res
+    }
+
+    /// `self^(p - 2)`, which is `1 / self` for non-zero `self` and 0 for 0.
+    pub fn invert(&self) -> FieldElement {
+        self.pow(&P_MINUS_2)
+    }
+
+    /// `SQRT_RATIO_M1` of RFC 9496: `(true, sqrt(u / v))` if `u / v` is
+    /// square, otherwise `(false, sqrt(SQRT_M1 * u / v))`. The root returned
+    /// is always non-negative, and `v = 0` gives `(u == 0, 0)`.
+    pub fn sqrt_ratio_i(u: &FieldElement, v: &FieldElement) -> (bool, FieldElement) {
+        let v3 = v.square() * *v;
+        let v7 = v3.square() * *v;
+        let mut r = (*u * v3) * (*u * v7).pow(&P_MINUS_5_DIV_8);
+        let check = *v * r.square();
+
+        let correct_sign = check.ct_eq(u);
+        let flipped_sign = check.ct_eq(&-*u);
+        let flipped_sign_i = check.ct_eq(&(-*u * FieldElement::SQRT_M1));
+
+        r = FieldElement::select(
+            &r,
+            &(r * FieldElement::SQRT_M1),
+            flipped_sign | flipped_sign_i,
+        );
+        r = FieldElement::select(&r, &-r, r.is_negative());
+
+        (correct_sign | flipped_sign, r)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.ct_eq(&FieldElement::ZERO)
+    }
+
+    /// Whether the canonical encoding is odd, the "sign" of RFC 8032.
+    pub fn is_negative(&self) -> bool {
+        self.to_bytes()[0] & 1 == 1
+    }
+
+    pub fn ct_eq(&self, other: &FieldElement) -> bool {
+        let diff = self
+            .to_bytes()
+            .iter()
+            .zip(other.to_bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b));
+
+        diff == 0
+    }
+
+    /// `b` if `choice` else `a`, without branching on `choice`.
+    pub fn select(a: &FieldElement, b: &FieldElement, choice: bool) -> FieldElement {
+        let mask = (choice as u64).wrapping_neg();
+
+        FieldElement(std::array::from_fn(|i| a.0[i] ^ (mask & (a.0[i] ^ b.0[i]))))
+    }
+
+    /// Swap `a` and `b` if `choice`, without branching on `choice`.
+    pub fn swap(a: &mut FieldElement, b: &mut FieldElement, choice: bool) {
+        let mask = (choice as u64).wrapping_neg();
+
+        for i in 0..5 {
+            let t = mask & (a.0[i] ^ b.0[i]);
+            a.0[i] ^= t;
+            b.0[i] ^= t;
+        }
+    }
+}
+
+impl Add for FieldElement {
+    type Output = FieldElement;
+
+    fn add(self, rhs: FieldElement) -> FieldElement {
+        FieldElement(reduce(std::array::from_fn(|i| self.0[i] + rhs.0[i])))
+    }
+}
+
+impl Sub for FieldElement {
+    type Output = FieldElement;
+
+    fn sub(self, rhs: FieldElement) -> FieldElement {
+        // Add 16p first so that no limb underflows.
+        let rhs = reduce(rhs.0);
+
+        FieldElement(reduce([
+            (self.0[0] + 0x7FFFFFFFFFFED0) - rhs[0],
+            (self.0[1] + 0x7FFFFFFFFFFFF0) - rhs[1],
+            (self.0[2] + 0x7FFFFFFFFFFFF0) - rhs[2],
+            (self.0[3] + 0x7FFFFFFFFFFFF0) - rhs[3],
+            (self.0[4] + 0x7FFFFFFFFFFFF0) - rhs[4],
+        ]))
+    }
+}
+
+impl Neg for FieldElement {
+    type Output = FieldElement;
+
+    fn neg(self) -> FieldElement {
+        FieldElement::ZERO - self
+    }
+}
+
+impl Mul for FieldElement {
+    type Output = FieldElement;
+
+    fn mul(self, rhs: FieldElement) -> FieldElement {
+        let m = |x: u64, y: u64| x as u128 * y as u128;
+
+        let a = reduce(self.0);
+        let b = reduce(rhs.0);
+        let b19: [u64; 5] = std::array::from_fn(|i| 19 * b[i]);
+
+        let c = [
+            m(a[0], b[0]) + m(a[4], b19[1]) + m(a[3], b19[2]) + m(a[2], b19[3]) + m(a[1], b19[4]),
+            m(a[1], b[0]) + m(a[0], b[1]) + m(a[4], b19[2]) + m(a[3], b19[3]) + m(a[2], b19[4]),
+            m(a[2], b[0]) + m(a[1], b[1]) + m(a[0], b[2]) + m(a[4], b19[3]) + m(a[3], b19[4]),
+            m(a[3], b[0]) + m(a[2], b[1]) + m(a[1], b[2]) + m(a[0], b[3]) + m(a[4], b19[4]),
+            m(a[4], b[0]) + m(a[3], b[1]) + m(a[2], b[2]) + m(a[1], b[3]) + m(a[0], b[4]),
+        ];
+
+        let mut l = [0u64; 5];
+        let mut carry = 0u128;
+
+        for i in 0..5 {
+            let t = c[i] + carry;
+            l[i] = t as u64 & MASK51;
+            carry = t >> 51;
+        }
+
+        l[0] += 19 * carry as u64;
+
+        FieldElement(reduce(l))
+    }
+}
+
+/// Carry every limb into the next one, bringing all limbs below 2^52.
+fn reduce(mut l: [u64; 5]) -> [u64; 5] {
+    let c = l.map(|x| x >> 51);
+
+    for x in &mut l {
+        *x &= MASK51;
+    }
+
+    l[0] += 19 * c[4];
+    l[1] += c[0];
+    l[2] += c[1];
+    l[3] += c[2];
+    l[4] += c[3];
+
+    l
+}
+
+/// `p - 2`, little-endian
+const P_MINUS_2: [u8; 32] = [
+    0xeb, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f,
+];
+
+/// `(p - 5) / 8`, little-endian
+const P_MINUS_5_DIV_8: [u8; 32] = [
+    0xfd, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x0f,
+];
+
+// X25519
+
+/// The u-coordinate of the base point, 9.
+pub const BASEPOINT: [u8; 32] = [
+    9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// The X25519 function of RFC 7748: scalar multiplication of the point with
+/// u-coordinate `u` by the clamped scalar `k`.
+pub fn x25519(k: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let mut k = *k;
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+
+    ladder(&k, &FieldElement::from_bytes(u)).to_bytes()
+}
+
+/// The Montgomery ladder on `u` with the 255-bit scalar `k`, returning the
+/// affine u-coordinate of `k * u`.
+fn ladder(k: &[u8; 32], u: &FieldElement) -> FieldElement {
+    let a24 = FieldElement([121665, 0, 0, 0, 0]);
+
+    let x_1 = *u;
+    let (mut x_2, mut z_2) = (FieldElement::ONE, FieldElement::ZERO);
+    let (mut x_3, mut z_3) = (*u, FieldElement::ONE);
+    let mut swap = false;
+
+    for t in (0..255).rev() {
+        let k_t = (k[t / 8] >> (t % 8)) & 1 == 1;
+
+        swap ^= k_t;
+        FieldElement::swap(&mut x_2, &mut x_3, swap);
+        FieldElement::swap(&mut z_2, &mut z_3, swap);
+        swap = k_t;
+
+        let a = x_2 + z_2;
+        let aa = a.square();
+        let b = x_2 - z_2;
+        let bb = b.square();
+        let e = aa - bb;
+        let c = x_3 + z_3;
+        let d = x_3 - z_3;
+        let da = d * a;
+        let cb = c * b;
+
+        x_3 = (da + cb).square();
+        z_3 = x_1 * (da - cb).square();
+        x_2 = aa * bb;
+        z_2 = e * (aa + a24 * e);
+    }
+
+    FieldElement::swap(&mut x_2, &mut x_3, swap);
+    FieldElement::swap(&mut z_2, &mut z_3, swap);
+
+    x_2 * z_2.invert()
+}
+
+/// An X25519 private key.
+#[derive(Clone)]
+pub struct SecretKey([u8; 32]);
+
+/// An X25519 public key, the u-coordinate of `k * 9`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(pub [u8; 32]);
+
+impl SecretKey {
+    /// A fresh key from the operating system's CSPRNG.
+    pub fn generate() -> SecretKey {
+        SecretKey(rng::bytes())
+    }
+
+    pub fn from_bytes(bytes: [u8; 32]) -> SecretKey {
+        SecretKey(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(x25519(&self.0, &BASEPOINT))
+    }
+
+    /// The shared secret with `peer`, or `None` if it is all zero, i.e. the
+    /// peer sent a point of small order.
+    pub fn diffie_hellman(&self, peer: &PublicKey) -> Option<[u8; 32]> {
+        let shared = x25519(&self.0, &peer.0);
+
+        (shared.iter().fold(0, |acc, x| acc | x) != 0).then_some(shared)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex_array;
+
+    /// Section 5.2 of RFC 7748.
+    #[test]
+    fn x25519_vectors() {
+        for (k, u, expected) in [
+            (
+                "a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4",
+                "e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c",
+                "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552",
+            ),
+            (
+                "4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d",
+                "e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493",
+                "95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957",
+            ),
+        ] {
+            assert_eq!(
+                x25519(&unhex_array(k), &unhex_array(u)),
+                unhex_array(expected)
+            );
+        }
+    }
+
+    /// `k = x25519(k, u), u = k` from `k = u = 9`, after `n` iterations.
+    fn iterate(n: usize) -> [u8; 32] {
+        let (mut k, mut u) = (BASEPOINT, BASEPOINT);
+
+        for _ in 0..n {
+            (k, u) = (x25519(&k, &u), k);
+        }
+
+        k
+    }
+
+    #[test]
+    fn x25519_iterated() {
+        assert_eq!(
+            iterate(1),
+            unhex_array("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
+        );
+        assert_eq!(
+            iterate(1000),
+            unhex_array("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51")
+        );
+    }
+
+    #[test]
+    #[ignore = "takes about a minute"]
+    fn x25519_iterated_million() {
+        assert_eq!(
+            iterate(1_000_000),
+            unhex_array("7c3911e0ab2586fd864497297e575e6f3bc601c0883c30df5f4dd2d24f665424")
+        );
+    }
+
+    /// Section 6.1 of RFC 7748.
+    #[test]
+    fn diffie_hellman() {
+        let alice = SecretKey::from_bytes(unhex_array(
+            "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+        ));
+        let bob = SecretKey::from_bytes(unhex_array(
+            "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+        ));
+
+        assert_eq!(
+            alice.public_key().0,
+            unhex_array("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+        );
+        assert_eq!(
+            bob.public_key().0,
+            unhex_array("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+        );
+
+        let shared =
+            unhex_array("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(alice.diffie_hellman(&bob.public_key()), Some(shared));
+        assert_eq!(bob.diffie_hellman(&alice.public_key()), Some(shared));
+
+        assert_eq!(alice.diffie_hellman(&PublicKey([0; 32])), None);
+    }
+}
@@ -0,0 +1,247 @@
+//! # Curve448
+//! Arithmetic in GF(2^448 - 2^224 - 1) and X448 key agreement (RFC 7748).
+//!
+//! The field is a [`Residue`] modulo the Goldilocks prime, so everything on
+//! secret data is constant time in the same way as in [`super::modular`].
+
+use super::modular::{Modulus, Residue};
+use crate::rng;
+
+/// `p = 2^448 - 2^224 - 1`
+#[derive(Debug, Clone, Copy)]
+pub struct Goldilocks;
+
+impl Modulus<7> for Goldilocks {
+    const P: [u64; 7] = [
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xfffffffeffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ];
+}
+
+/// An element of GF(2^448 - 2^224 - 1).
+pub type FieldElement = Residue<Goldilocks, 7>;
+
+// X448
+
+/// The u-coordinate of the base point, 5.
+pub const BASEPOINT: [u8; 56] = {
+    let mut u = [0; 56];
+    u[0] = 5;
+    u
+};
+
+/// The X448 function of RFC 7748: scalar multiplication of the point with
+/// u-coordinate `u` by the clamped scalar `k`.
+pub fn x448(k: &[u8; 56], u: &[u8; 56]) -> [u8; 56] {
+    let mut k = *k;
+    k[0] &= 252;
+    k[55] |= 128;
+
+    ladder(&k, &FieldElement::from_le_bytes(u))
+        .to_le_bytes()
+        .try_into()
+        .unwrap()
+}
+
+/// The Montgomery ladder on `u` with the 448-bit scalar `k`, returning the
+/// affine u-coordinate of `k * u`.
+fn ladder(k: &[u8; 56], u: &FieldElement) -> FieldElement {
+    let a24 = FieldElement::from_u64(39081);
+
+    let x_1 = *u;
+    let (mut x_2, mut z_2) = (FieldElement::ONE, FieldElement::ZERO);
+    let (mut x_3, mut z_3) = (*u, FieldElement::ONE);
+    let mut swap = false;
+
+    for t in (0..448).rev() {
+        let k_t = (k[t / 8] >> (t % 8)) & 1 == 1;
+
+        swap ^= k_t;
+        (x_2, x_3) = (
+            FieldElement::select(&x_2, &x_3, swap),
+            FieldElement::select(&x_3, &x_2, swap),
+        );
+        (z_2, z_3) = (
+            FieldElement::select(&z_2, &z_3, swap),
+            FieldElement::select(&z_3, &z_2, swap),
+        );
+        swap = k_t;
+
+        let a = x_2 + z_2;
+        let aa = a.square();
+        let b = x_2 - z_2;
+        let bb = b.square();
+        let e = aa - bb;
+        let c = x_3 + z_3;
+        let d = x_3 - z_3;
+        let da = d * a;
+        let cb = c * b;
+
+        x_3 = (da + cb).square();
+        z_3 = x_1 * (da - cb).square();
+        x_2 = aa * bb;
+        z_2 = e * (aa + a24 * e);
+    }
+
+    let x_2 = FieldElement::select(&x_2, &x_3, swap);
+    let z_2 = FieldElement::select(&z_2, &z_3, swap);
+
+    x_2 * z_2.invert()
+}
+
+/// An X448 private key.
+#[derive(Clone)]
+pub struct SecretKey([u8; 56]);
+
+/// An X448 public key, the u-coordinate of `k * 5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(pub [u8; 56]);
+
+impl SecretKey {
+    /// A fresh key from the operating system's CSPRNG.
+    pub fn generate() -> SecretKey {
+        SecretKey(rng::bytes())
+    }
+
+    pub fn from_bytes(bytes: [u8; 56]) -> SecretKey {
+        SecretKey(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 56] {
+        self.0
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(x448(&self.0, &BASEPOINT))
+    }
+
+    /// The shared secret with `peer`, or `None` if it is all zero, i.e. the
+    /// peer sent a point of small order.
+    pub fn diffie_hellman(&self, peer: &PublicKey) -> Option<[u8; 56]> {
+        let shared = x448(&self.0, &peer.0);
+
+        (shared.iter().fold(0, |acc, x| acc | x) != 0).then_some(shared)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex_array;
+
+    /// Section 5.2 of RFC 7748.
+    #[test]
+    fn x448_vectors() {
+        for (k, u, expected) in [
+            (
+                concat!(
+                    "3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121",
+                    "700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3",
+                ),
+                concat!(
+                    "06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9",
+                    "814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086",
+                ),
+                concat!(
+                    "ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239f",
+                    "e14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f",
+                ),
+            ),
+            (
+                concat!(
+                    "203d494428b8399352665ddca42f9de8fef600908e0d461cb021f8c5",
+                    "38345dd77c3e4806e25f46d3315c44e0a5b4371282dd2c8d5be3095f",
+                ),
+                concat!(
+                    "0fbcc2f993cd56d3305b0b7d9e55d4c1a8fb5dbb52f8e9a1e9b6201b",
+                    "165d015894e56c4d3570bee52fe205e28a78b91cdfbde71ce8d157db",
+                ),
+                concat!(
+                    "884a02576239ff7a2f2f63b2db6a9ff37047ac13568e1e30fe63c4a7",
+                    "ad1b3ee3a5700df34321d62077e63633c575c1c954514e99da7c179d",
+                ),
+            ),
+        ] {
+            assert_eq!(
+                x448(&unhex_array(k), &unhex_array(u)),
+                unhex_array(expected)
+            );
+        }
+    }
+
+    /// `k = x448(k, u), u = k` from `k = u = 5`, after `n` iterations.
+    fn iterate(n: usize) -> [u8; 56] {
+        let (mut k, mut u) = (BASEPOINT, BASEPOINT);
+
+        for _ in 0..n {
+            (k, u) = (x448(&k, &u), k);
+        }
+
+        k
+    }
+
+    #[test]
+    fn x448_iterated() {
+        assert_eq!(
+            iterate(1),
+            unhex_array(concat!(
+                "3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a",
+                "4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113",
+            ))
+        );
+    }
+
+    #[test]
+    #[ignore = "slow in debug builds"]
+    fn x448_iterated_thousand() {
+        assert_eq!(
+            iterate(1000),
+            unhex_array(concat!(
+                "aa3b4749d55b9daf1e5b00288826c467274ce3ebbdd5c17b975e09d4",
+                "af6c67cf10d087202db88286e2b79fceea3ec353ef54faa26e219f38",
+            ))
+        );
+    }
+
+    /// Section 6.2 of RFC 7748.
+    #[test]
+    fn diffie_hellman() {
+        let alice = SecretKey::from_bytes(unhex_array(concat!(
+            "9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28d",
+            "d9c9baf574a9419744897391006382a6f127ab1d9ac2d8c0a598726b",
+        )));
+        let bob = SecretKey::from_bytes(unhex_array(concat!(
+            "1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d",
+            "6927c120bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d",
+        )));
+
+        assert_eq!(
+            alice.public_key().0,
+            unhex_array(concat!(
+                "9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c",
+                "22c5d9bbc836647241d953d40c5b12da88120d53177f80e532c41fa0",
+            ))
+        );
+        assert_eq!(
+            bob.public_key().0,
+            unhex_array(concat!(
+                "3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b430",
+                "27d8b972fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609",
+            ))
+        );
+
+        let shared = unhex_array(concat!(
+            "07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282b",
+            "b60c0b56fd2464c335543936521c24403085d59a449a5037514a879d",
+        ));
+        assert_eq!(alice.diffie_hellman(&bob.public_key()), Some(shared));
+        assert_eq!(bob.diffie_hellman(&alice.public_key()), Some(shared));
+
+        assert_eq!(alice.diffie_hellman(&PublicKey([0; 56])), None);
+    }
+}
@@ -0,0 +1,507 @@
+//! # ECDSA (FIPS 186-5)
+//! Signatures over the curves of [`super::weierstrass`], each paired with a
+//! hash through [`EcdsaCurve`].
+//!
+//! Signatures are the fixed-width `r || s` of IEEE P1363, as used by JOSE.
+//! Both `s` and `n - s` verify; curves with [`EcdsaCurve::LOW_S`] only ever
+//! produce the lower one. A recovery id next to the signature tells which
+//! of the up to four candidate keys signed, as Ethereum uses.
+//!
+//! Nonces are derived as in RFC 6979, from the key and the digest with HMAC
+//! over the hash that made the digest, so signing needs no randomness.
+//! Messages are hashed with the curve's [`EcdsaCurve::Hash`]; precomputed
+//! digests name their own [`Hash`].
+//! Hedged signing feeds fresh random bytes in as the additional input `k'`
+//! of section 3.6, which keeps the nonces safe if either the randomness or
+//! the determinism fails, e.g. under fault attacks.
+
+use super::{
+    modular::Modulus,
+    weierstrass::{random_scalar, Curve, ProjectivePoint, Scalar},
+};
+use crate::{
+    rng,
+    sha::{
+        hmac::{hmac_sha256, hmac_sha384, hmac_sha512},
+        sha256::Sha256,
+        sha384::Sha384,
+        sha512::Sha512,
+    },
+};
+
+/// A hash for ECDSA, with the HMAC over it that RFC 6979 derives nonces
+/// from.
+pub trait Hash {
+    /// The length of the digest in bytes.
+    const OUTPUT_LEN: usize;
+
+    fn digest(msg: &[u8]) -> Vec<u8>;
+
+    fn hmac(key: &[u8], msg: &[u8]) -> Vec<u8>;
+}
+
+impl Hash for Sha256 {
+    const OUTPUT_LEN: usize = 32;
+
+    fn digest(msg: &[u8]) -> Vec<u8> {
+        Sha256::new(msg).result().to_vec()
+    }
+
+    fn hmac(key: &[u8], msg: &[u8]) -> Vec<u8> {
+        hmac_sha256(key, msg).to_vec()
+    }
+}
+
+impl Hash for Sha384 {
+    const OUTPUT_LEN: usize = 48;
+
+    fn digest(msg: &[u8]) -> Vec<u8> {
+        Sha384::new(msg).result().to_vec()
+    }
+
+    fn hmac(key: &[u8], msg: &[u8]) -> Vec<u8> {
+        hmac_sha384(key, msg).to_vec()
+    }
+}
+
+impl Hash for Sha512 {
+    const OUTPUT_LEN: usize = 64;
+
+    fn digest(msg: &[u8]) -> Vec<u8> {
+        Sha512::new(msg).result().to_vec()
+    }
+
+    fn hmac(key: &[u8], msg: &[u8]) -> Vec<u8> {
+        hmac_sha512(key, msg).to_vec()
+    }
+}
+
+/// A curve with the hash ECDSA uses on it.
+pub trait EcdsaCurve<const N: usize>: Curve<N> {
+    /// Whether signing normalizes `s` to at most `n / 2`.
+    const LOW_S: bool = false;
+
+    /// The hash of messages passed to [`SigningKey::sign`] and
+    /// [`VerifyingKey::verify`].
+    type Hash: Hash;
+}
+
+/// An ECDSA private key `d`.
+#[derive(Clone)]
+pub struct SigningKey<C: EcdsaCurve<N>, const N: usize> {
+    d: Scalar<C, N>,
+    verifying_key: VerifyingKey<C, N>,
+}
+
+/// An ECDSA public key `Q = [d] G`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyingKey<C: EcdsaCurve<N>, const N: usize> {
+    point: ProjectivePoint<C, N>,
+}
+
+/// An ECDSA signature `(r, s)`, both in `1..n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature<C: EcdsaCurve<N>, const N: usize> {
+    r: Scalar<C, N>,
+    s: Scalar<C, N>,
+}
+
+impl<C: EcdsaCurve<N>, const N: usize> SigningKey<C, N> {
+    /// A fresh key from the operating system's CSPRNG.
+    pub fn generate() -> Self {
+        SigningKey::from_scalar(random_scalar::<C, N>())
+    }
+
+    /// `None` unless `bytes` is a big-endian integer in `1..n`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let d = Scalar::<C, N>::from_canonical_be_bytes(bytes)?;
+
+        (!d.is_zero()).then(|| SigningKey::from_scalar(d))
+    }
+
+    fn from_scalar(d: Scalar<C, N>) -> Self {
+        SigningKey {
+            d,
+            verifying_key: VerifyingKey {
+                point: ProjectivePoint::mul_base(&d),
+            },
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.d.to_be_bytes()
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey<C, N> {
+        self.verifying_key
+    }
+
+    /// Sign the curve's hash of `msg`, deterministically.
+    pub fn sign(&self, msg: &[u8]) -> Signature<C, N> {
+        self.sign_digest::<C::Hash>(&C::Hash::digest(msg))
+    }
+
+    /// Sign the curve's hash of `msg`, with `8 N` random bytes as extra
+    /// input to the nonce derivation.
+    pub fn sign_hedged(&self, msg: &[u8]) -> Signature<C, N> {
+        let mut extra = vec![0u8; 8 * N];
+        rng::fill(&mut extra);
+
+        self.sign_digest_with_entropy::<C::Hash>(&C::Hash::digest(msg), &extra)
+    }
+
+    /// Sign a precomputed digest made with `H`, deterministically, with
+    /// nonces from HMAC over `H` as in RFC 6979.
+    pub fn sign_digest<H: Hash>(&self, digest: &[u8]) -> Signature<C, N> {
+        self.sign_digest_with_entropy::<H>(digest, &[])
+    }
+
+    /// Sign a precomputed digest made with `H`, with the additional input
+    /// `extra` to the RFC 6979 nonce derivation.
+    pub fn sign_digest_with_entropy<H: Hash>(
+        &self,
+        digest: &[u8],
+        extra: &[u8],
+    ) -> Signature<C, N> {
+        self.sign_with_recovery_id::<H>(digest, extra).0
+    }
+
+    /// Sign the curve's hash of `msg`, deterministically, and return the
+    /// recovery id for [`VerifyingKey::recover`].
+    pub fn sign_recoverable(&self, msg: &[u8]) -> (Signature<C, N>, u8) {
+        self.sign_with_recovery_id::<C::Hash>(&C::Hash::digest(msg), &[])
+    }
+
+    /// Like [`SigningKey::sign_digest`], with the recovery id for
+    /// [`VerifyingKey::recover_from_digest`].
+    pub fn sign_digest_recoverable<H: Hash>(&self, digest: &[u8]) -> (Signature<C, N>, u8) {
+        self.sign_with_recovery_id::<H>(digest, &[])
+    }
+
+    fn sign_with_recovery_id<H: Hash>(&self, digest: &[u8], extra: &[u8]) -> (Signature<C, N>, u8) {
+        let e = bits2int::<C, N>(digest);
+        let mut nonces = Rfc6979::new::<C, H, N>(&self.d, digest, extra);
+
+        loop {
+            if let Some(sig) = self.sign_with_nonce(&e, &nonces.next::<C, N>()) {
+                return sig;
+            }
+        }
+    }
+
+    /// `r = x([k] G) mod n` and `s = (e + r d) / k`, or `None` if either is
+    /// zero and another nonce is needed. The recovery id has the parity of
+    /// `y(R)` in bit 0, and whether `x(R) >= n` in bit 1.
+    fn sign_with_nonce(&self, e: &Scalar<C, N>, k: &Scalar<C, N>) -> Option<(Signature<C, N>, u8)> {
+        let (x, y) = ProjectivePoint::<C, N>::mul_base(k).to_affine()?;
+
+        let r = Scalar::<C, N>::from_be_bytes(&x.to_be_bytes());
+        let s = (*e + r * self.d) * k.invert();
+
+        if r.is_zero() || s.is_zero() {
+            return None;
+        }
+
+        let overflow = Scalar::<C, N>::from_canonical_be_bytes(&x.to_be_bytes()).is_none();
+        let recovery_id = y.is_odd() as u8 | (overflow as u8) << 1;
+        let sig = Signature { r, s };
+
+        // R and -R give the same r; negating s swaps which one verifies.
+        if C::LOW_S && !sig.is_low_s() {
+            return Some((sig.normalize_s(), recovery_id ^ 1));
+        }
+
+        Some((sig, recovery_id))
+    }
+}
+
+impl<C: EcdsaCurve<N>, const N: usize> VerifyingKey<C, N> {
+    /// `None` unless `bytes` is a SEC1 encoding of a point other than the
+    /// identity.
+    pub fn from_sec1(bytes: &[u8]) -> Option<Self> {
+        ProjectivePoint::from_sec1(bytes).map(|point| VerifyingKey { point })
+    }
+
+    pub fn to_sec1(&self, compressed: bool) -> Vec<u8> {
+        self.point.to_sec1(compressed)
+    }
+
+    pub fn verify(&self, msg: &[u8], sig: &Signature<C, N>) -> bool {
+        self.verify_digest(&C::Hash::digest(msg), sig)
+    }
+
+    /// Check `x([e / s] G + [r / s] Q) mod n = r`.
+    pub fn verify_digest(&self, digest: &[u8], sig: &Signature<C, N>) -> bool {
+        let e = bits2int::<C, N>(digest);
+        let w = sig.s.invert();
+
+        let point = ProjectivePoint::mul_base(&(e * w)) + self.point.mul(&(sig.r * w));
+
+        let Some((x, _)) = point.to_affine() else {
+            return false;
+        };
+
+        Scalar::<C, N>::from_be_bytes(&x.to_be_bytes()) == sig.r
+    }
+
+    /// The key that made `sig` on the curve's hash of `msg`, given the
+    /// recovery id from signing.
+    pub fn recover(msg: &[u8], sig: &Signature<C, N>, recovery_id: u8) -> Option<Self> {
+        VerifyingKey::recover_from_digest(&C::Hash::digest(msg), sig, recovery_id)
+    }
+
+    /// `Q = (s R - e G) / r`, where `R` is the point with `x = r` (plus `n`
+    /// if bit 1 of `recovery_id` is set) and the parity of `y` in bit 0.
+    pub fn recover_from_digest(
+        digest: &[u8],
+        sig: &Signature<C, N>,
+        recovery_id: u8,
+    ) -> Option<Self> {
+        if recovery_id > 3 {
+            return None;
+        }
+
+        let mut x = sig.r.to_be_bytes();
+
+        if recovery_id & 2 == 2 {
+            let n = <C::Order as Modulus<N>>::P
+                .iter()
+                .rev()
+                .flat_map(|limb| limb.to_be_bytes())
+                .collect::<Vec<_>>();
+
+            let mut carry = 0;
+            for (x_i, n_i) in x.iter_mut().zip(n).rev() {
+                let sum = *x_i as u16 + n_i as u16 + carry;
+                *x_i = sum as u8;
+                carry = sum >> 8;
+            }
+
+            if carry == 1 {
+                return None;
+            }
+        }
+
+        let mut encoding = vec![2 | (recovery_id & 1)];
+        encoding.extend(x);
+        let big_r = ProjectivePoint::<C, N>::from_sec1(&encoding)?;
+
+        let e = bits2int::<C, N>(digest);
+        let r_inv = sig.r.invert();
+        let point = big_r.mul(&(sig.s * r_inv)) - ProjectivePoint::mul_base(&(e * r_inv));
+
+        (!point.is_identity()).then_some(VerifyingKey { point })
+    }
+}
+
+impl<C: EcdsaCurve<N>, const N: usize> Signature<C, N> {
+    /// `None` unless `bytes` is `r || s`, both big-endian in `1..n`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 16 * N {
+            return None;
+        }
+
+        let r = Scalar::<C, N>::from_canonical_be_bytes(&bytes[..8 * N])?;
+        let s = Scalar::<C, N>::from_canonical_be_bytes(&bytes[8 * N..])?;
+
+        (!r.is_zero() && !s.is_zero()).then_some(Signature { r, s })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.r.to_be_bytes(), self.s.to_be_bytes()].concat()
+    }
+
+    /// Whether `s <= n / 2`, i.e. `s <= n - s`.
+    pub fn is_low_s(&self) -> bool {
+        self.s.to_be_bytes() <= (-self.s).to_be_bytes()
+    }
+
+    /// The equally valid signature `(r, n - s)` if `s` is high.
+    pub fn normalize_s(&self) -> Self {
+        Signature {
+            r: self.r,
+            s: Scalar::<C, N>::select(&self.s, &-self.s, !self.is_low_s()),
+        }
+    }
+}
+
+/// The leftmost `64 N` bits of `digest` as an integer, reduced modulo `n`.
+fn bits2int<C: Curve<N>, const N: usize>(digest: &[u8]) -> Scalar<C, N> {
+    Scalar::<C, N>::from_be_bytes(&digest[..digest.len().min(8 * N)])
+}
+
+/// The HMAC-DRBG of RFC 6979, section 3.2, producing candidate nonces.
+struct Rfc6979 {
+    hmac: fn(&[u8], &[u8]) -> Vec<u8>,
+    k: Vec<u8>,
+    v: Vec<u8>,
+    fresh: bool,
+}
+
+impl Rfc6979 {
+    /// Seed with `int2octets(x) || bits2octets(h1) || extra`, using HMAC
+    /// over the hash `H` that made `h1`.
+    fn new<C: Curve<N>, H: Hash, const N: usize>(
+        x: &Scalar<C, N>,
+        digest: &[u8],
+        extra: &[u8],
+    ) -> Self {
+        let seed = [
+            &x.to_be_bytes()[..],
+            &bits2int::<C, N>(digest).to_be_bytes(),
+            extra,
+        ]
+        .concat();
+
+        let mut drbg = Rfc6979 {
+            hmac: H::hmac,
+            k: vec![0; H::OUTPUT_LEN],
+            v: vec![1; H::OUTPUT_LEN],
+            fresh: true,
+        };
+
+        for tag in [0, 1] {
+            drbg.k = (drbg.hmac)(&drbg.k, &[&drbg.v[..], &[tag], &seed].concat());
+            drbg.v = (drbg.hmac)(&drbg.k, &drbg.v);
+        }
+
+        drbg
+    }
+
+    /// The next candidate `k` in `1..n`; every call after the first starts
+    /// by stepping past the previous one, as when it gave `r = 0` or `s = 0`.
+    fn next<C: Curve<N>, const N: usize>(&mut self) -> Scalar<C, N> {
+        if !self.fresh {
+            self.reseed();
+        }
+        self.fresh = false;
+
+        loop {
+            let mut t = vec![];
+
+            while t.len() < 8 * N {
+                self.v = (self.hmac)(&self.k, &self.v);
+                t.extend_from_slice(&self.v);
+            }
+
+            if let Some(k) = Scalar::<C, N>::from_canonical_be_bytes(&t[..8 * N]) {
+                if !k.is_zero() {
+                    return k;
+                }
+            }
+
+            self.reseed();
+        }
+    }
+
+    fn reseed(&mut self) {
+        self.k = (self.hmac)(&self.k, &[&self.v[..], &[0]].concat());
+        self.v = (self.hmac)(&self.k, &self.v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Hash;
+    use crate::{
+        ecc::p256::{Signature, SigningKey},
+        sha::{sha256::Sha256, sha384::Sha384, sha512::Sha512},
+        test_util::unhex,
+    };
+
+    fn key() -> SigningKey {
+        SigningKey::from_bytes(&unhex(
+            "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+        ))
+        .unwrap()
+    }
+
+    /// RFC 6979, appendix A.2.5, with SHA-256 and the message "sample".
+    #[test]
+    fn rfc6979() {
+        let key = key();
+        let sig = key.sign(b"sample");
+
+        assert_eq!(
+            sig.to_bytes(),
+            unhex(concat!(
+                "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716",
+                "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8",
+            ))
+        );
+        assert!(key.verifying_key().verify(b"sample", &sig));
+    }
+
+    /// RFC 6979, appendix A.2.5: digests of other hashes get nonces from
+    /// HMAC over the same hash.
+    #[test]
+    fn rfc6979_other_hashes() {
+        fn check<H: Hash>(msg: &[u8], expected: &str) {
+            let key = key();
+            let digest = H::digest(msg);
+            let sig = key.sign_digest::<H>(&digest);
+
+            assert_eq!(sig.to_bytes(), unhex(expected));
+            assert!(key.verifying_key().verify_digest(&digest, &sig));
+            assert_eq!(Signature::from_bytes(&sig.to_bytes()), Some(sig));
+        }
+
+        check::<Sha384>(
+            b"sample",
+            concat!(
+                "0eafea039b20e9b42309fb1d89e213057cbf973dc0cfc8f129edddc800ef7719",
+                "4861f0491e6998b9455193e34e7b0d284ddd7149a74b95b9261f13abde940954",
+            ),
+        );
+        check::<Sha384>(
+            b"test",
+            concat!(
+                "83910e8b48bb0c74244ebdf7f07a1c5413d61472bd941ef3920e623fbccebeb6",
+                "8ddbec54cf8cd5874883841d712142a56a8d0f218f5003cb0296b6b509619f2c",
+            ),
+        );
+        check::<Sha512>(
+            b"sample",
+            concat!(
+                "8496a60b5e9b47c825488827e0495b0e3fa109ec4568fd3f8d1097678eb97f00",
+                "2362ab1adbe2b8adf9cb9edab740ea6049c028114f2460f96554f61fae3302fe",
+            ),
+        );
+        check::<Sha512>(
+            b"test",
+            concat!(
+                "461d93f31b6540894788fd206c07cfa0cc35f46fa3c91816fff1040ad1581a04",
+                "39af9f15de0db8d97e72719c74820d304ce5226e32dedae67519e840d1194e55",
+            ),
+        );
+    }
+
+    /// Extra input changes the nonce but not the validity. The fixed case
+    /// was computed with an independent implementation of RFC 6979,
+    /// section 3.6.
+    #[test]
+    fn hedged() {
+        let key = key();
+        let deterministic = key.sign(b"sample");
+
+        let sig = key.sign_hedged(b"sample");
+        assert_ne!(sig, deterministic);
+        assert_ne!(sig, key.sign_hedged(b"sample"));
+        assert!(key.verifying_key().verify(b"sample", &sig));
+
+        let digest = Sha256::digest(b"sample");
+        let extra = (0..32).collect::<Vec<u8>>();
+        assert_eq!(
+            key.sign_digest_with_entropy::<Sha256>(&digest, &extra)
+                .to_bytes(),
+            unhex(concat!(
+                "25404cfdb1228f680881e195dae0665f43f988c40cbc4e23927810d7c4635d74",
+                "8f076e7b9ea4bde92fb16b5cf25d0d3656db01a6e19c885b53cb8754f1b819c3",
+            ))
+        );
+        assert_eq!(
+            key.sign_digest_with_entropy::<Sha256>(&digest, &[]),
+            deterministic
+        );
+    }
+}
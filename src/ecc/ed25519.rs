@@ -0,0 +1,521 @@
+//! # Ed25519 (RFC 8032)
+//! Signatures over edwards25519 with SHA-512.
+//!
+//! By default verification is strict about encodings: the point encodings of
+//! the key and of `R` must be canonical and `S` must be below `l`. Other
+//! implementations draw these lines elsewhere, so [`Ruleset`] selects which
+//! edge cases to accept when verifiers have to agree with each other.
+//!
+//! Besides plain Ed25519 this covers the Ed25519ctx and Ed25519ph variants,
+//! which prefix every hash with `dom2(phflag, context)`, and batch
+//! verification.
+
+use super::edwards25519::{EdwardsPoint, Scalar};
+use crate::{rng, sha::sha512::Sha512};
+
+/// An Ed25519 private key, derived from a 32 byte seed.
+#[derive(Clone)]
+pub struct SigningKey {
+    seed: [u8; 32],
+    s: Scalar,
+    prefix: [u8; 32],
+    verifying_key: VerifyingKey,
+}
+
+/// An Ed25519 public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyingKey {
+    bytes: [u8; 32],
+    point: EdwardsPoint,
+}
+
+/// An Ed25519 signature `R || S`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature(pub [u8; 64]);
+
+/// The verification rules for the edge cases RFC 8032 leaves open. `S` must
+/// be below `l` under every ruleset.
+///
+/// | Ruleset        | Encodings of `A`, `R` | Small-order `A`, `R` | Equation      |
+/// |----------------|-----------------------|----------------------|---------------|
+/// | `Cofactorless` | canonical             | accepted             | cofactorless  |
+/// | `Cofactored`   | canonical             | accepted             | cofactored    |
+/// | `Strict`       | canonical             | rejected             | cofactorless  |
+/// | `Zip215`       | any                   | accepted             | cofactored    |
+///
+/// The cofactorless equation is `[S] B = R + [k] A`, the cofactored one
+/// `[8][S] B = [8] R + [8][k] A`. They differ only when `R` or `A` has a
+/// torsion component.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Ruleset {
+    /// What [`VerifyingKey::verify`] does.
+    #[default]
+    Cofactorless,
+    /// The equation RFC 8032 recommends, which agrees with batch
+    /// verification.
+    Cofactored,
+    /// Also reject keys and `R` of small order, as RFC 8032 does not.
+    Strict,
+    /// Zcash's ZIP-215: every encoding that decodes to a point once `y` is
+    /// reduced modulo `p` is valid, and the hash uses the bytes as given.
+    /// Any two ZIP-215 verifiers agree on every input, and verification is
+    /// compatible with batching.
+    Zip215,
+}
+
+impl Ruleset {
+    fn decompress(self, bytes: &[u8; 32]) -> Option<EdwardsPoint> {
+        match self {
+            Ruleset::Zip215 => EdwardsPoint::decompress_non_canonical(bytes),
+            _ => EdwardsPoint::decompress(bytes),
+        }
+    }
+
+    fn check(self, s: &Scalar, r: &EdwardsPoint, k: &Scalar, a: &EdwardsPoint) -> bool {
+        let diff = EdwardsPoint::mul_base(s) - *r - a.mul(k);
+
+        match self {
+            Ruleset::Cofactorless => diff.is_identity(),
+            Ruleset::Strict => !r.is_small_order() && !a.is_small_order() && diff.is_identity(),
+            Ruleset::Cofactored | Ruleset::Zip215 => diff.is_small_order(),
+        }
+    }
+}
+
+impl SigningKey {
+    /// A fresh key from the operating system's CSPRNG.
+    pub fn generate() -> SigningKey {
+        SigningKey::from_seed(&rng::bytes())
+    }
+
+    pub fn from_seed(seed: &[u8; 32]) -> SigningKey {
+        let h = Sha512::new(seed).result();
+
+        let mut s = [0u8; 32];
+        s.copy_from_slice(&h[..32]);
+        s[0] &= 248;
+        s[31] &= 127;
+        s[31] |= 64;
+
+        let s = Scalar::from_le_bytes(&s);
+        let point = EdwardsPoint::mul_base(&s);
+
+        SigningKey {
+            seed: *seed,
+            s,
+            prefix: h[32..].try_into().unwrap(),
+            verifying_key: VerifyingKey {
+                bytes: point.compress(),
+                point,
+            },
+        }
+    }
+
+    pub fn to_seed(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+
+    pub fn sign(&self, msg: &[u8]) -> Signature {
+        self.sign_with_dom(&[], msg)
+    }
+
+    /// Ed25519ctx. Panics unless `context` is 1 to 255 bytes long.
+    pub fn sign_with_context(&self, msg: &[u8], context: &[u8]) -> Signature {
+        assert!(!context.is_empty(), "Ed25519ctx needs a non-empty context");
+
+        self.sign_with_dom(&dom2(false, context), msg)
+    }
+
+    /// Ed25519ph, signing the SHA-512 hash of everything fed to
+    /// `prehashed`. Panics if `context` is longer than 255 bytes.
+    pub fn sign_prehashed(&self, mut prehashed: Sha512, context: &[u8]) -> Signature {
+        self.sign_with_dom(&dom2(true, context), &prehashed.result())
+    }
+
+    fn sign_with_dom(&self, dom: &[u8], msg: &[u8]) -> Signature {
+        let r = hash(&[dom, &self.prefix, msg]);
+        let r_bytes = EdwardsPoint::mul_base(&r).compress();
+
+        let k = hash(&[dom, &r_bytes, &self.verifying_key.bytes, msg]);
+        let s = r + k * self.s;
+
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&r_bytes);
+        sig[32..].copy_from_slice(&s.to_le_bytes());
+
+        Signature(sig)
+    }
+}
+
+impl VerifyingKey {
+    /// `None` unless `bytes` is the canonical encoding of a curve point.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<VerifyingKey> {
+        Some(VerifyingKey {
+            bytes: *bytes,
+            point: EdwardsPoint::decompress(bytes)?,
+        })
+    }
+
+    /// Decode `bytes` as `rules` allows. The bytes are kept as given, to be
+    /// hashed into the challenge.
+    pub fn from_bytes_with_rules(bytes: &[u8; 32], rules: Ruleset) -> Option<VerifyingKey> {
+        Some(VerifyingKey {
+            bytes: *bytes,
+            point: rules.decompress(bytes)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    /// Check `[S] B = R + [k] A`.
+    pub fn verify(&self, msg: &[u8], sig: &Signature) -> bool {
+        self.verify_with_rules(msg, sig, Ruleset::Cofactorless)
+    }
+
+    /// Plain Ed25519 under `rules`. Non-canonical keys have to be decoded
+    /// with [`VerifyingKey::from_bytes_with_rules`] first.
+    pub fn verify_with_rules(&self, msg: &[u8], sig: &Signature, rules: Ruleset) -> bool {
+        self.verify_with_dom(&[], msg, sig, rules)
+    }
+
+    /// Ed25519ctx. An empty or over-long `context` never verifies.
+    pub fn verify_with_context(&self, msg: &[u8], context: &[u8], sig: &Signature) -> bool {
+        (1..=255).contains(&context.len())
+            && self.verify_with_dom(&dom2(false, context), msg, sig, Ruleset::Cofactorless)
+    }
+
+    /// Ed25519ph, over the SHA-512 hash of everything fed to `prehashed`.
+    pub fn verify_prehashed(&self, mut prehashed: Sha512, context: &[u8], sig: &Signature) -> bool {
+        context.len() <= 255
+            && self.verify_with_dom(
+                &dom2(true, context),
+                &prehashed.result(),
+                sig,
+                Ruleset::Cofactorless,
+            )
+    }
+
+    fn verify_with_dom(&self, dom: &[u8], msg: &[u8], sig: &Signature, rules: Ruleset) -> bool {
+        let Some((r, s)) = decode(sig, rules) else {
+            return false;
+        };
+
+        let k = hash(&[dom, &sig.0[..32], &self.bytes, msg]);
+
+        rules.check(&s, &r, &k, &self.point)
+    }
+}
+
+/// Verify plain Ed25519 signatures on `messages[i]` by `keys[i]` at once,
+/// by checking a random linear combination of the verification equations
+/// with a single multi-scalar multiplication.
+///
+/// The combined equation is multiplied by the cofactor, so this agrees with
+/// [`Ruleset::Cofactored`] rather than [`VerifyingKey::verify`].
+pub fn verify_batch(messages: &[&[u8]], signatures: &[Signature], keys: &[VerifyingKey]) -> bool {
+    assert_eq!(messages.len(), signatures.len());
+    assert_eq!(messages.len(), keys.len());
+
+    let mut b_coeff = Scalar::ZERO;
+    let mut scalars = vec![];
+    let mut points = vec![];
+
+    for ((msg, sig), key) in messages.iter().zip(signatures).zip(keys) {
+        let Some((r, s)) = decode(sig, Ruleset::Cofactored) else {
+            return false;
+        };
+
+        let k = hash(&[&sig.0[..32], &key.bytes, msg]);
+
+        let mut z = [0u8; 16];
+        rng::fill(&mut z);
+        let z = Scalar::from_le_bytes(&z);
+
+        b_coeff = b_coeff - z * s;
+        scalars.extend([z, z * k]);
+        points.extend([r, key.point]);
+    }
+
+    scalars.push(b_coeff);
+    points.push(EdwardsPoint::BASEPOINT);
+
+    EdwardsPoint::vartime_multiscalar_mul(&scalars, &points)
+        .mul_by_cofactor()
+        .is_identity()
+}
+
+/// Split `R || S`, rejecting `S >= l` and the encodings of `R` that `rules`
+/// does not allow.
+fn decode(sig: &Signature, rules: Ruleset) -> Option<(EdwardsPoint, Scalar)> {
+    let r = rules.decompress(&sig.0[..32].try_into().unwrap())?;
+    let s = Scalar::from_canonical_le_bytes(&sig.0[32..])?;
+
+    Some((r, s))
+}
+
+/// `dom2(phflag, context)` of RFC 8032
+fn dom2(phflag: bool, context: &[u8]) -> Vec<u8> {
+    assert!(context.len() <= 255, "context longer than 255 bytes");
+
+    let mut dom = b"SigEd25519 no Ed25519 collisions".to_vec();
+    dom.extend([phflag as u8, context.len() as u8]);
+    dom.extend_from_slice(context);
+
+    dom
+}
+
+/// SHA-512 of the concatenation of `parts`, modulo `l`.
+fn hash(parts: &[&[u8]]) -> Scalar {
+    let mut sha = Sha512::new(&[]);
+
+    for part in parts {
+        sha.update(part);
+    }
+
+    Scalar::from_le_bytes(&sha.result())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{unhex, unhex_array};
+
+    /// RFC 8032, section 7.1, TEST 1: the empty message.
+    #[test]
+    fn rfc8032_test1() {
+        let key = SigningKey::from_seed(&unhex_array(
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        ));
+        let sig = key.sign(&[]);
+
+        assert_eq!(
+            key.verifying_key().to_bytes().to_vec(),
+            unhex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+        );
+        assert_eq!(
+            sig.0.to_vec(),
+            unhex(concat!(
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155",
+                "5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ))
+        );
+        assert!(key.verifying_key().verify(&[], &sig));
+        assert!(!key.verifying_key().verify(&[0], &sig));
+    }
+
+    /// RFC 8032, section 7.2, the Ed25519ctx vector with context "foo".
+    #[test]
+    fn rfc8032_ctx() {
+        let key = SigningKey::from_seed(&unhex_array(
+            "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+        ));
+        let msg = unhex("f726936d19c800494e3fdaff20b276a8");
+        let sig = key.sign_with_context(&msg, b"foo");
+
+        assert_eq!(
+            key.verifying_key().to_bytes().to_vec(),
+            unhex("dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292")
+        );
+        assert_eq!(
+            sig.0.to_vec(),
+            unhex(concat!(
+                "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a",
+                "8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d",
+            ))
+        );
+        assert!(key.verifying_key().verify_with_context(&msg, b"foo", &sig));
+        assert!(!key.verifying_key().verify_with_context(&msg, b"bar", &sig));
+        assert!(!key.verifying_key().verify(&msg, &sig));
+    }
+
+    /// RFC 8032, section 7.3, the Ed25519ph vector for "abc".
+    #[test]
+    fn rfc8032_ph() {
+        let key = SigningKey::from_seed(&unhex_array(
+            "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+        ));
+        let sig = key.sign_prehashed(Sha512::new(b"abc"), &[]);
+
+        assert_eq!(
+            key.verifying_key().to_bytes().to_vec(),
+            unhex("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf")
+        );
+        assert_eq!(
+            sig.0.to_vec(),
+            unhex(concat!(
+                "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae41",
+                "31f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406",
+            ))
+        );
+        assert!(key
+            .verifying_key()
+            .verify_prehashed(Sha512::new(b"abc"), &[], &sig));
+        assert!(!key
+            .verifying_key()
+            .verify_prehashed(Sha512::new(b"abd"), &[], &sig));
+    }
+
+    #[test]
+    fn batch() {
+        let keys = (0..4)
+            .map(|i| SigningKey::from_seed(&[i; 32]))
+            .collect::<Vec<_>>();
+        let messages: [&[u8]; 4] = [b"", b"a", b"batch", &[0xff; 100]];
+        let mut signatures = keys
+            .iter()
+            .zip(messages)
+            .map(|(key, msg)| key.sign(msg))
+            .collect::<Vec<_>>();
+        let keys = keys
+            .iter()
+            .map(SigningKey::verifying_key)
+            .collect::<Vec<_>>();
+
+        assert!(verify_batch(&messages, &signatures, &keys));
+        assert!(!verify_batch(&messages[..3], &signatures[1..], &keys[..3]));
+
+        signatures[2].0[40] ^= 1;
+        assert!(!verify_batch(&messages, &signatures, &keys));
+    }
+
+    /// The test vectors of "Taming the many EdDSAs" (Chalkias, Garillot,
+    /// Nikolaenko), as message, key, signature and whether each of
+    /// `Cofactorless`, `Cofactored`, `Strict` and `Zip215` accepts them.
+    const TAMING: [(&str, &str, &str, [bool; 4]); 12] = [
+        (
+            "8c93255d71dcab10e8f379c26200f3c7bd5f09d9bc3068d3ef4edeb4853022b6",
+            "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac03fa",
+            concat!(
+                "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+            ),
+            [true, true, false, true],
+        ),
+        (
+            "9bd9f44f4dcc75bd531b56b2cd280b0bb38fc1cd6d1230e14861d861de092e79",
+            "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac03fa",
+            concat!(
+                "f7badec5b8abeaf699583992219b7b223f1df3fbbea919844e3f7c554a43dd43",
+                "a5bb704786be79fc476f91d3f3f89b03984d8068dcf1bb7dfc6637b45450ac04",
+            ),
+            [true, true, false, true],
+        ),
+        (
+            "aebf3f2601a0c8c5d39cc7d8911642f740b78168218da8471772b35f9d35b9ab",
+            "f7badec5b8abeaf699583992219b7b223f1df3fbbea919844e3f7c554a43dd43",
+            concat!(
+                "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac03fa",
+                "8c4bd45aecaca5b24fb97bc10ac27ac8751a7dfe1baff8b953ec9f5833ca260e",
+            ),
+            [true, true, false, true],
+        ),
+        (
+            "9bd9f44f4dcc75bd531b56b2cd280b0bb38fc1cd6d1230e14861d861de092e79",
+            "cdb267ce40c5cd45306fa5d2f29731459387dbf9eb933b7bd5aed9a765b88d4d",
+            concat!(
+                "9046a64750444938de19f227bb80485e92b83fdb4b6506c160484c016cc1852f",
+                "87909e14428a7a1d62e9f22f3d3ad7802db02eb2e688b6c52fcd6648a98bd009",
+            ),
+            [true, true, true, true],
+        ),
+        (
+            "e47d62c63f830dc7a6851a0b1f33ae4bb2f507fb6cffec4011eaccd55b53f56c",
+            "cdb267ce40c5cd45306fa5d2f29731459387dbf9eb933b7bd5aed9a765b88d4d",
+            concat!(
+                "160a1cb0dc9c0258cd0a7d23e94d8fa878bcb1925f2c64246b2dee1796bed512",
+                "5ec6bc982a269b723e0668e540911a9a6a58921d6925e434ab10aa7940551a09",
+            ),
+            [false, true, false, true],
+        ),
+        (
+            "e47d62c63f830dc7a6851a0b1f33ae4bb2f507fb6cffec4011eaccd55b53f56c",
+            "cdb267ce40c5cd45306fa5d2f29731459387dbf9eb933b7bd5aed9a765b88d4d",
+            concat!(
+                "21122a84e0b5fca4052f5b1235c80a537878b38f3142356b2c2384ebad4668b7",
+                "e40bc836dac0f71076f9abe3a53f9c03c1ceeeddb658d0030494ace586687405",
+            ),
+            [false, true, false, true],
+        ),
+        (
+            "85e241a07d148b41e47d62c63f830dc7a6851a0b1f33ae4bb2f507fb6cffec40",
+            "442aad9f089ad9e14647b1ef9099a1ff4798d78589e66f28eca69c11f582a623",
+            concat!(
+                "e96f66be976d82e60150baecff9906684aebb1ef181f67a7189ac78ea23b6c0e",
+                "547f7690a0e2ddcd04d87dbc3490dc19b3b3052f7ff0538cb68afb369ba3a514",
+            ),
+            [false, false, false, false],
+        ),
+        (
+            "85e241a07d148b41e47d62c63f830dc7a6851a0b1f33ae4bb2f507fb6cffec40",
+            "442aad9f089ad9e14647b1ef9099a1ff4798d78589e66f28eca69c11f582a623",
+            concat!(
+                "8ce5b96c8f26d0ab6c47958c9e68b937104cd36e13c33566acd2fe8d38aa1942",
+                "7e71f98a473474f2f13f06f97c20d58cc3f54b8bd0d272f42b695dd7e89a8c22",
+            ),
+            [false, false, false, false],
+        ),
+        (
+            "9bedc267423725d473888631ebf45988bad3db83851ee85c85e241a07d148b41",
+            "f7badec5b8abeaf699583992219b7b223f1df3fbbea919844e3f7c554a43dd43",
+            concat!(
+                "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+                "03be9678ac102edcd92b0210bb34d7428d12ffc5df5f37e359941266a4e35f0f",
+            ),
+            [true, true, false, true],
+        ),
+        (
+            "9bedc267423725d473888631ebf45988bad3db83851ee85c85e241a07d148b41",
+            "f7badec5b8abeaf699583992219b7b223f1df3fbbea919844e3f7c554a43dd43",
+            concat!(
+                "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "ca8c5b64cd208982aa38d4936621a4775aa233aa0505711d8fdcfdaa943d4908",
+            ),
+            [false, false, false, true],
+        ),
+        (
+            "e96b7021eb39c1a163b6da4e3093dcd3f21387da4cc4572be588fafae23c155b",
+            "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            concat!(
+                "a9d55260f765261eb9b84e106f665e00b867287a761990d7135963ee0a7d59dc",
+                "a5bb704786be79fc476f91d3f3f89b03984d8068dcf1bb7dfc6637b45450ac04",
+            ),
+            [false, false, false, true],
+        ),
+        (
+            "39a591f5321bbe07fd5a23dc2f39d025d74526615746727ceefd6e82ae65c06f",
+            "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            concat!(
+                "a9d55260f765261eb9b84e106f665e00b867287a761990d7135963ee0a7d59dc",
+                "a5bb704786be79fc476f91d3f3f89b03984d8068dcf1bb7dfc6637b45450ac04",
+            ),
+            [false, false, false, true],
+        ),
+    ];
+
+    #[test]
+    fn taming() {
+        let rulesets = [
+            Ruleset::Cofactorless,
+            Ruleset::Cofactored,
+            Ruleset::Strict,
+            Ruleset::Zip215,
+        ];
+
+        for (i, (msg, key, sig, expected)) in TAMING.iter().enumerate() {
+            let (msg, key, sig) = (unhex(msg), unhex_array(key), Signature(unhex_array(sig)));
+
+            for (rules, expected) in rulesets.iter().zip(expected) {
+                let accepted = VerifyingKey::from_bytes_with_rules(&key, *rules)
+                    .is_some_and(|key| key.verify_with_rules(&msg, &sig, *rules));
+
+                assert_eq!(accepted, *expected, "case {i} under {rules:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! # Ed448 (RFC 8032)
+//! Signatures over edwards448 with SHAKE256.
+//!
+//! Every hash is prefixed with `dom4(phflag, context)`, so plain Ed448 is
+//! Ed448 with an empty context. Verification is strict about encodings like
+//! [`super::ed25519`], and checks the cofactorless equation.
+
+use super::edwards448::{EdwardsPoint, Scalar};
+use crate::{rng, sha::sha3::shake256};
+
+/// An Ed448 private key, derived from a 57 byte seed.
+#[derive(Clone)]
+pub struct SigningKey {
+    seed: [u8; 57],
+    s: Scalar,
+    prefix: [u8; 57],
+    verifying_key: VerifyingKey,
+}
+
+/// An Ed448 public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyingKey {
+    bytes: [u8; 57],
+    point: EdwardsPoint,
+}
+
+/// An Ed448 signature `R || S`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature(pub [u8; 114]);
+
+impl SigningKey {
+    /// A fresh key from the operating system's CSPRNG.
+    pub fn generate() -> SigningKey {
+        SigningKey::from_seed(&rng::bytes())
+    }
+
+    pub fn from_seed(seed: &[u8; 57]) -> SigningKey {
+        let h = shake256(seed, 114);
+
+        let mut s = [0u8; 57];
+        s.copy_from_slice(&h[..57]);
+        s[0] &= 252;
+        s[55] |= 128;
+        s[56] = 0;
+
+        let s = Scalar::from_le_bytes(&s);
+        let point = EdwardsPoint::mul_base(&s);
+
+        SigningKey {
+            seed: *seed,
+            s,
+            prefix: h[57..].try_into().unwrap(),
+            verifying_key: VerifyingKey {
+                bytes: point.compress(),
+                point,
+            },
+        }
+    }
+
+    pub fn to_seed(&self) -> [u8; 57] {
+        self.seed
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+
+    pub fn sign(&self, msg: &[u8]) -> Signature {
+        self.sign_with_context(msg, &[])
+    }
+
+    /// Panics if `context` is longer than 255 bytes.
+    pub fn sign_with_context(&self, msg: &[u8], context: &[u8]) -> Signature {
+        self.sign_with_dom(&dom4(false, context), msg)
+    }
+
+    /// Ed448ph, signing `digest = SHAKE256(M, 64)`. Panics if `context` is
+    /// longer than 255 bytes.
+    pub fn sign_prehashed(&self, digest: &[u8; 64], context: &[u8]) -> Signature {
+        self.sign_with_dom(&dom4(true, context), digest)
+    }
+
+    fn sign_with_dom(&self, dom: &[u8], msg: &[u8]) -> Signature {
+        let r = hash(&[dom, &self.prefix, msg]);
+        let r_bytes = EdwardsPoint::mul_base(&r).compress();
+
+        let k = hash(&[dom, &r_bytes, &self.verifying_key.bytes, msg]);
+        let s = r + k * self.s;
+
+        let mut sig = [0u8; 114];
+        sig[..57].copy_from_slice(&r_bytes);
+        sig[57..113].copy_from_slice(&s.to_le_bytes());
+
+        Signature(sig)
+    }
+}
+
+impl VerifyingKey {
+    /// `None` unless `bytes` is the canonical encoding of a curve point.
+    pub fn from_bytes(bytes: &[u8; 57]) -> Option<VerifyingKey> {
+        Some(VerifyingKey {
+            bytes: *bytes,
+            point: EdwardsPoint::decompress(bytes)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 57] {
+        self.bytes
+    }
+
+    /// Check `[S] B = R + [k] A`.
+    pub fn verify(&self, msg: &[u8], sig: &Signature) -> bool {
+        self.verify_with_context(msg, &[], sig)
+    }
+
+    /// An over-long `context` never verifies.
+    pub fn verify_with_context(&self, msg: &[u8], context: &[u8], sig: &Signature) -> bool {
+        context.len() <= 255 && self.verify_with_dom(&dom4(false, context), msg, sig)
+    }
+
+    /// Ed448ph, over `digest = SHAKE256(M, 64)`.
+    pub fn verify_prehashed(&self, digest: &[u8; 64], context: &[u8], sig: &Signature) -> bool {
+        context.len() <= 255 && self.verify_with_dom(&dom4(true, context), digest, sig)
+    }
+
+    fn verify_with_dom(&self, dom: &[u8], msg: &[u8], sig: &Signature) -> bool {
+        let Some(r) = EdwardsPoint::decompress(&sig.0[..57].try_into().unwrap()) else {
+            return false;
+        };
+        if sig.0[113] != 0 {
+            return false;
+        }
+        let Some(s) = Scalar::from_canonical_le_bytes(&sig.0[57..113]) else {
+            return false;
+        };
+
+        let k = hash(&[dom, &sig.0[..57], &self.bytes, msg]);
+
+        EdwardsPoint::mul_base(&s) == r + self.point.mul(&k)
+    }
+}
+
+/// `dom4(phflag, context)` of RFC 8032
+fn dom4(phflag: bool, context: &[u8]) -> Vec<u8> {
+    assert!(context.len() <= 255, "context longer than 255 bytes");
+
+    let mut dom = b"SigEd448".to_vec();
+    dom.extend([phflag as u8, context.len() as u8]);
+    dom.extend_from_slice(context);
+
+    dom
+}
+
+/// 114 bytes of SHAKE256 of the concatenation of `parts`, modulo `l`.
+fn hash(parts: &[&[u8]]) -> Scalar {
+    Scalar::from_le_bytes(&shake256(&parts.concat(), 114))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex_array;
+
+    /// Check the key and signature of an RFC 8032 vector, and that the
+    /// signature verifies with `verify`.
+    fn check(
+        seed: &str,
+        public: &str,
+        sig: &str,
+        sign: impl Fn(&SigningKey) -> Signature,
+        verify: impl Fn(&VerifyingKey, &Signature) -> bool,
+    ) {
+        let key = SigningKey::from_seed(&unhex_array(seed));
+        let sig = Signature(unhex_array(sig));
+
+        assert_eq!(key.verifying_key().to_bytes(), unhex_array(public));
+        assert_eq!(sign(&key).0, sig.0);
+        assert!(verify(&key.verifying_key(), &sig));
+    }
+
+    /// RFC 8032, section 7.4, "Blank".
+    #[test]
+    fn blank() {
+        check(
+            concat!(
+                "6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960e",
+                "f6e348a3528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b",
+            ),
+            concat!(
+                "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d",
+                "80e96778edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180",
+            ),
+            concat!(
+                "533a37f6bbe457251f023c0d88f976ae2dfb504a843e34d2074fd823",
+                "d41a591f2b233f034f628281f2fd7a22ddd47d7828c59bd0a21bfd39",
+                "80ff0d2028d4b18a9df63e006c5d1c2d345b925d8dc00b4104852db9",
+                "9ac5c7cdda8530a113a0f4dbb61149f05a7363268c71d95808ff2e65",
+                "2600",
+            ),
+            |key| key.sign(&[]),
+            |key, sig| key.verify(&[], sig) && !key.verify(&[0], sig),
+        );
+    }
+
+    /// RFC 8032, section 7.4, "1 octet (with context)".
+    #[test]
+    fn one_octet_with_context() {
+        check(
+            concat!(
+                "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00ac",
+                "da2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e",
+            ),
+            concat!(
+                "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c",
+                "6798c0866aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+            ),
+            concat!(
+                "d4f8f6131770dd46f40867d6fd5d5055de43541f8c5e35abbcd001b3",
+                "2a89f7d2151f7647f11d8ca2ae279fb842d607217fce6e042f6815ea",
+                "000c85741de5c8da1144a6a1aba7f96de42505d7a7298524fda538fc",
+                "cbbb754f578c1cad10d54d0d5428407e85dcbc98a49155c13764e66c",
+                "3c00",
+            ),
+            |key| key.sign_with_context(&[0x03], b"foo"),
+            |key, sig| key.verify_with_context(&[0x03], b"foo", sig) && !key.verify(&[0x03], sig),
+        );
+    }
+
+    /// RFC 8032, section 7.5, Ed448ph of "abc".
+    #[test]
+    fn prehashed() {
+        let digest = shake256(b"abc", 64).try_into().unwrap();
+
+        check(
+            concat!(
+                "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b9",
+                "6dca3d42ef7822e0d5104127dc05d6dbefde69e3ab2cec7c867c6e2c49",
+            ),
+            concat!(
+                "259b71c19f83ef77a7abd26524cbdb3161b590a48f7d17de3ee0ba9c",
+                "52beb743c09428a131d6b1b57303d90d8132c276d5ed3d5d01c0f53880",
+            ),
+            concat!(
+                "822f6901f7480f3d5f562c592994d9693602875614483256505600bb",
+                "c281ae381f54d6bce2ea911574932f52a4e6cadd78769375ec3ffd1b",
+                "801a0d9b3f4030cd433964b6457ea39476511214f97469b57dd32dbc",
+                "560a9a94d00bff07620464a3ad203df7dc7ce360c3cd3696d9d9fab9",
+                "0f00",
+            ),
+            |key| key.sign_prehashed(&digest, &[]),
+            |key, sig| key.verify_prehashed(&digest, &[], sig) && !key.verify(b"abc", sig),
+        );
+    }
+}
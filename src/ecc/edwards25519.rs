@@ -0,0 +1,311 @@
+//! # Edwards25519
+//! The twisted Edwards curve `-x^2 + y^2 = 1 + d x^2 y^2` over GF(2^255 - 19),
+//! birationally equivalent to Curve25519, and its scalars modulo the prime
+//! order `l` of the base point. The full group has order `8 l`.
+
+use std::ops::{Add, Neg, Sub};
+
+use super::{
+    curve25519::FieldElement,
+    group::{self, Group},
+    modular::{Modulus, Residue},
+};
+
+/// `l = 2^252 + 27742317777372353535851937790883648493`
+#[derive(Debug, Clone, Copy)]
+pub struct GroupOrder;
+
+impl Modulus<4> for GroupOrder {
+    const P: [u64; 4] = [
+        0x5812631a5cf5d3ed,
+        0x14def9dea2f79cd6,
+        0x0000000000000000,
+        0x1000000000000000,
+    ];
+}
+
+/// An integer modulo `l`.
+pub type Scalar = Residue<GroupOrder, 4>;
+
+/// `d = -121665 / 121666`
+pub const D: FieldElement = FieldElement([
+    0x34dca135978a3,
+    0x1a8283b156ebd,
+    0x5e7a26001c029,
+    0x739c663a03cbb,
+    0x52036cee2b6ff,
+]);
+
+/// `2 d`
+const D2: FieldElement = FieldElement([
+    0x69b9426b2f159,
+    0x35050762add7a,
+    0x3cf44c0038052,
+    0x6738cc7407977,
+    0x2406d9dc56dff,
+]);
+
+/// A point in extended coordinates `(X : Y : Z : T)`, with `x = X / Z`,
+/// `y = Y / Z` and `x y = T / Z`.
+#[derive(Debug, Clone, Copy)]
+pub struct EdwardsPoint {
+    pub(crate) x: FieldElement,
+    pub(crate) y: FieldElement,
+    pub(crate) z: FieldElement,
+    pub(crate) t: FieldElement,
+}
+
+impl EdwardsPoint {
+    pub const IDENTITY: EdwardsPoint = EdwardsPoint {
+        x: FieldElement::ZERO,
+        y: FieldElement::ONE,
+        z: FieldElement::ONE,
+        t: FieldElement::ZERO,
+    };
+
+    /// The base point `B`, with `y = 4 / 5` and positive `x`.
+    pub const BASEPOINT: EdwardsPoint = EdwardsPoint {
+        x: FieldElement([
+            0x62d608f25d51a,
+            0x412a4b4f6592a,
+            0x75b7171a4b31d,
+            0x1ff60527118fe,
+            0x216936d3cd6e5,
+        ]),
+        y: FieldElement([
+            0x6666666666658,
+            0x4cccccccccccc,
+            0x1999999999999,
+            0x3333333333333,
+            0x6666666666666,
+        ]),
+        z: FieldElement::ONE,
+        t: FieldElement([
+            0x68ab3a5b7dda3,
+            0x00eea2a5eadbb,
+            0x2af8df483c27e,
+            0x332b375274732,
+            0x67875f0fd78b7,
+        ]),
+    };
+
+    /// The affine point `(x, y)`, which must be on the curve.
+    pub fn from_affine(x: FieldElement, y: FieldElement) -> EdwardsPoint {
+        EdwardsPoint {
+            x,
+            y,
+            z: FieldElement::ONE,
+            t: x * y,
+        }
+    }
+
+    /// The RFC 8032 encoding: `y` little-endian, with the sign of `x` in the
+    /// top bit.
+    pub fn compress(&self) -> [u8; 32] {
+        let z_inv = self.z.invert();
+        let x = self.x * z_inv;
+        let y = self.y * z_inv;
+
+        let mut bytes = y.to_bytes();
+        bytes[31] |= (x.is_negative() as u8) << 7;
+
+        bytes
+    }
+
+    /// Decode an RFC 8032 encoding, rejecting non-canonical ones: `y >= p`,
+    /// and `x = 0` with the sign bit set.
+    pub fn decompress(bytes: &[u8; 32]) -> Option<EdwardsPoint> {
+        let mut y_bytes = *bytes;
+        y_bytes[31] &= 0x7f;
+
+        let y = FieldElement::from_bytes(&y_bytes);
+
+        if y.to_bytes() != y_bytes {
+            return None;
+        }
+
+        let point = EdwardsPoint::decompress_y(&y, bytes[31] >> 7 == 1)?;
+
+        if point.x.is_zero() && bytes[31] >> 7 == 1 {
+            return None;
+        }
+
+        Some(point)
+    }
+
+    /// Decode like [`decompress`](EdwardsPoint::decompress), but also accept
+    /// the non-canonical encodings as ZIP-215 does: `y` is taken modulo `p`
+    /// and the sign bit of `x = 0` is ignored.
+    pub fn decompress_non_canonical(bytes: &[u8; 32]) -> Option<EdwardsPoint> {
+        let mut y_bytes = *bytes;
+        y_bytes[31] &= 0x7f;
+
+        EdwardsPoint::decompress_y(&FieldElement::from_bytes(&y_bytes), bytes[31] >> 7 == 1)
+    }
+
+    /// Recover the point with coordinate `y` and sign `sign`; for `x = 0` the
+    /// sign is ignored.
+    pub(crate) fn decompress_y(y: &FieldElement, sign: bool) -> Option<EdwardsPoint> {
+        let yy = y.square();
+        let u = yy - FieldElement::ONE;
+        let v = D * yy + FieldElement::ONE;
+
+        let (is_square, x) = FieldElement::sqrt_ratio_i(&u, &v);
+
+        if !is_square {
+            return None;
+        }
+
+        let x = FieldElement::select(&x, &-x, sign);
+
+        Some(EdwardsPoint::from_affine(x, *y))
+    }
+
+    pub fn double(&self) -> EdwardsPoint {
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = self.z.square() + self.z.square();
+        let h = a + b;
+        let e = h - (self.x + self.y).square();
+        let g = a - b;
+        let f = c + g;
+
+        EdwardsPoint {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+        }
+    }
+
+    /// `[s] self`, in constant time.
+    pub fn mul(&self, s: &Scalar) -> EdwardsPoint {
+        self.mul_bits(&s.to_le_bytes(), 253)
+    }
+
+    /// `[s] B`, in constant time.
+    pub fn mul_base(s: &Scalar) -> EdwardsPoint {
+        EdwardsPoint::BASEPOINT.mul(s)
+    }
+
+    /// `[k] self` for the low `bits` bits of the little-endian integer `k`,
+    /// in constant time with respect to `k`.
+    pub fn mul_bits(&self, k: &[u8], bits: usize) -> EdwardsPoint {
+        let mut acc = EdwardsPoint::IDENTITY;
+
+        for i in (0..bits).rev() {
+            acc = acc.double();
+
+            let sum = acc + *self;
+            acc = EdwardsPoint::select(&acc, &sum, (k[i / 8] >> (i % 8)) & 1 == 1);
+        }
+
+        acc
+    }
+
+    /// `sum [s_i] P_i`, in variable time: only for public inputs.
+    pub fn vartime_multiscalar_mul(scalars: &[Scalar], points: &[EdwardsPoint]) -> EdwardsPoint {
+        group::vartime_multiscalar_mul(scalars, points)
+    }
+
+    /// `[8] self`
+    pub fn mul_by_cofactor(&self) -> EdwardsPoint {
+        self.double().double().double()
+    }
+
+    /// Whether `[8] self` is the identity, i.e. `self` lies in the torsion
+    /// subgroup.
+    pub fn is_small_order(&self) -> bool {
+        self.mul_by_cofactor().is_identity()
+    }
+
+    /// Whether `[l] self` is the identity, i.e. `self` has no torsion
+    /// component.
+    pub fn is_torsion_free(&self) -> bool {
+        let l = GroupOrder::P
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        self.mul_bits(&l, 253).is_identity()
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.ct_eq(&EdwardsPoint::IDENTITY)
+    }
+
+    pub fn ct_eq(&self, other: &EdwardsPoint) -> bool {
+        (self.x * other.z).ct_eq(&(other.x * self.z))
+            & (self.y * other.z).ct_eq(&(other.y * self.z))
+    }
+
+    /// `b` if `choice` else `a`, without branching on `choice`.
+    pub fn select(a: &EdwardsPoint, b: &EdwardsPoint, choice: bool) -> EdwardsPoint {
+        EdwardsPoint {
+            x: FieldElement::select(&a.x, &b.x, choice),
+            y: FieldElement::select(&a.y, &b.y, choice),
+            z: FieldElement::select(&a.z, &b.z, choice),
+            t: FieldElement::select(&a.t, &b.t, choice),
+        }
+    }
+}
+
+impl Group for EdwardsPoint {
+    const IDENTITY: EdwardsPoint = EdwardsPoint::IDENTITY;
+
+    fn double(&self) -> EdwardsPoint {
+        EdwardsPoint::double(self)
+    }
+}
+
+impl PartialEq for EdwardsPoint {
+    fn eq(&self, other: &EdwardsPoint) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl Eq for EdwardsPoint {}
+
+impl Add for EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    /// The complete addition law for `a = -1` ("add-2008-hwcd-3").
+    fn add(self, rhs: EdwardsPoint) -> EdwardsPoint {
+        let a = (self.y - self.x) * (rhs.y - rhs.x);
+        let b = (self.y + self.x) * (rhs.y + rhs.x);
+        let c = self.t * D2 * rhs.t;
+        let d = (self.z + self.z) * rhs.z;
+        let e = b - a;
+        let f = d - c;
+        let g = d + c;
+        let h = b + a;
+
+        EdwardsPoint {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+        }
+    }
+}
+
+impl Neg for EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn neg(self) -> EdwardsPoint {
+        EdwardsPoint {
+            x: -self.x,
+            y: self.y,
+            z: self.z,
+            t: -self.t,
+        }
+    }
+}
+
+impl Sub for EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn sub(self, rhs: EdwardsPoint) -> EdwardsPoint {
+        self + -rhs
+    }
+}
@@ -0,0 +1,238 @@
+//! # Edwards448
+//! The untwisted Edwards curve `x^2 + y^2 = 1 + d x^2 y^2` with
+//! `d = -39081` over GF(2^448 - 2^224 - 1), 4-isogenous to Curve448, and its
+//! scalars modulo the prime order `l` of the base point. The full group has
+//! order `4 l`.
+
+use std::ops::{Add, Neg, Sub};
+
+use super::{
+    curve448::FieldElement,
+    modular::{Modulus, Residue},
+};
+
+/// `l = 2^446 - 13818066809895115352007386748515426880336692474882178609894547503885`
+#[derive(Debug, Clone, Copy)]
+pub struct GroupOrder;
+
+impl Modulus<7> for GroupOrder {
+    const P: [u64; 7] = [
+        0x2378c292ab5844f3,
+        0x216cc2728dc58f55,
+        0xc44edb49aed63690,
+        0xffffffff7cca23e9,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x3fffffffffffffff,
+    ];
+}
+
+/// An integer modulo `l`.
+pub type Scalar = Residue<GroupOrder, 7>;
+
+/// `-d = 39081`
+const MINUS_D: u64 = 39081;
+
+/// A point in projective coordinates `(X : Y : Z)`, with `x = X / Z` and
+/// `y = Y / Z`.
+#[derive(Debug, Clone, Copy)]
+pub struct EdwardsPoint {
+    x: FieldElement,
+    y: FieldElement,
+    z: FieldElement,
+}
+
+impl EdwardsPoint {
+    pub const IDENTITY: EdwardsPoint = EdwardsPoint {
+        x: FieldElement::ZERO,
+        y: FieldElement::ONE,
+        z: FieldElement::ONE,
+    };
+
+    /// The base point `B` of RFC 8032, section 5.2.
+    pub fn basepoint() -> EdwardsPoint {
+        EdwardsPoint::from_affine(
+            FieldElement::from_limbs([
+                0x2626a82bc70cc05e,
+                0x433b80e18b00938e,
+                0x12ae1af72ab66511,
+                0xea6de324a3d3a464,
+                0x9e146570470f1767,
+                0x221d15a622bf36da,
+                0x4f1970c66bed0ded,
+            ]),
+            FieldElement::from_limbs([
+                0x9808795bf230fa14,
+                0xfdbd132c4ed7c8ad,
+                0x3ad3ff1ce67c39c4,
+                0x87789c1e05a0c2d7,
+                0x4bea73736ca39840,
+                0x8876203756c9c762,
+                0x693f46716eb6bc24,
+            ]),
+        )
+    }
+
+    /// The affine point `(x, y)`, which must be on the curve.
+    pub fn from_affine(x: FieldElement, y: FieldElement) -> EdwardsPoint {
+        EdwardsPoint {
+            x,
+            y,
+            z: FieldElement::ONE,
+        }
+    }
+
+    /// The RFC 8032 encoding: `y` as 57 little-endian bytes, with the sign
+    /// of `x` in the top bit.
+    pub fn compress(&self) -> [u8; 57] {
+        let z_inv = self.z.invert();
+        let x = self.x * z_inv;
+        let y = self.y * z_inv;
+
+        let mut bytes = [0u8; 57];
+        bytes[..56].copy_from_slice(&y.to_le_bytes());
+        bytes[56] = (x.is_odd() as u8) << 7;
+
+        bytes
+    }
+
+    /// Decode an RFC 8032 encoding, rejecting non-canonical ones: `y >= p`,
+    /// set bits besides the sign in the last byte, and `x = 0` with the sign
+    /// bit set.
+    pub fn decompress(bytes: &[u8; 57]) -> Option<EdwardsPoint> {
+        if bytes[56] & 0x7f != 0 {
+            return None;
+        }
+
+        let y = FieldElement::from_canonical_le_bytes(&bytes[..56])?;
+        let sign = bytes[56] >> 7 == 1;
+
+        // x^2 = (y^2 - 1) / (d y^2 - 1)
+        let yy = y.square();
+        let u = yy - FieldElement::ONE;
+        let v = -(FieldElement::from_u64(MINUS_D) * yy) - FieldElement::ONE;
+
+        let x = (u * v.invert()).sqrt()?;
+
+        if x.is_zero() && sign {
+            return None;
+        }
+
+        let x = FieldElement::select(&x, &-x, x.is_odd() != sign);
+
+        Some(EdwardsPoint::from_affine(x, y))
+    }
+
+    pub fn double(&self) -> EdwardsPoint {
+        let b = (self.x + self.y).square();
+        let c = self.x.square();
+        let d = self.y.square();
+        let e = c + d;
+        let h = self.z.square();
+        let j = e - (h + h);
+
+        EdwardsPoint {
+            x: (b - e) * j,
+            y: e * (c - d),
+            z: e * j,
+        }
+    }
+
+    /// `[s] self`, in constant time.
+    pub fn mul(&self, s: &Scalar) -> EdwardsPoint {
+        self.mul_bits(&s.to_le_bytes(), 446)
+    }
+
+    /// `[s] B`, in constant time.
+    pub fn mul_base(s: &Scalar) -> EdwardsPoint {
+        EdwardsPoint::basepoint().mul(s)
+    }
+
+    /// `[k] self` for the low `bits` bits of the little-endian integer `k`,
+    /// in constant time with respect to `k`.
+    pub fn mul_bits(&self, k: &[u8], bits: usize) -> EdwardsPoint {
+        let mut acc = EdwardsPoint::IDENTITY;
+
+        for i in (0..bits).rev() {
+            acc = acc.double();
+
+            let sum = acc + *self;
+            acc = EdwardsPoint::select(&acc, &sum, (k[i / 8] >> (i % 8)) & 1 == 1);
+        }
+
+        acc
+    }
+
+    /// `[4] self`
+    pub fn mul_by_cofactor(&self) -> EdwardsPoint {
+        self.double().double()
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.ct_eq(&EdwardsPoint::IDENTITY)
+    }
+
+    pub fn ct_eq(&self, other: &EdwardsPoint) -> bool {
+        (self.x * other.z).ct_eq(&(other.x * self.z))
+            & (self.y * other.z).ct_eq(&(other.y * self.z))
+    }
+
+    /// `b` if `choice` else `a`, without branching on `choice`.
+    pub fn select(a: &EdwardsPoint, b: &EdwardsPoint, choice: bool) -> EdwardsPoint {
+        EdwardsPoint {
+            x: FieldElement::select(&a.x, &b.x, choice),
+            y: FieldElement::select(&a.y, &b.y, choice),
+            z: FieldElement::select(&a.z, &b.z, choice),
+        }
+    }
+}
+
+impl PartialEq for EdwardsPoint {
+    fn eq(&self, other: &EdwardsPoint) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl Eq for EdwardsPoint {}
+
+impl Add for EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    /// The complete addition law of RFC 8032, section 5.2.4.
+    fn add(self, rhs: EdwardsPoint) -> EdwardsPoint {
+        let a = self.z * rhs.z;
+        let b = a.square();
+        let c = self.x * rhs.x;
+        let d = self.y * rhs.y;
+        let e = -(FieldElement::from_u64(MINUS_D) * c * d);
+        let f = b - e;
+        let g = b + e;
+        let h = (self.x + self.y) * (rhs.x + rhs.y);
+
+        EdwardsPoint {
+            x: a * f * (h - c - d),
+            y: a * g * (d - c),
+            z: f * g,
+        }
+    }
+}
+
+impl Neg for EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn neg(self) -> EdwardsPoint {
+        EdwardsPoint {
+            x: -self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+}
+
+impl Sub for EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn sub(self, rhs: EdwardsPoint) -> EdwardsPoint {
+        self + -rhs
+    }
+}
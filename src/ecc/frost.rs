@@ -0,0 +1,720 @@
+//! # FROST (RFC 9591)
+//! Two-round threshold Schnorr signatures: any `t` of `n` holders of shares
+//! of a secret key sign together, and the result is a plain Schnorr
+//! signature under the group key. The [`Ristretto255`] and [`Ed25519`]
+//! ciphersuites both hash with SHA-512, and the latter's signatures verify
+//! as ordinary Ed25519 signatures.
+//!
+//! Keys come from a trusted dealer, who splits the secret with Shamir's
+//! scheme and publishes Feldman commitments to the polynomial so that every
+//! participant can check its share.
+//!
+//! A signing round goes:
+//! 1. [`commit`] by every signer, who sends the [`SigningCommitments`] to the
+//!    coordinator and keeps the [`SigningNonces`];
+//! 2. [`sign`] by every signer over the message and the list of all
+//!    commitments;
+//! 3. [`aggregate`] of the signature shares by the coordinator, after
+//!    [`verify_signature_share`] on each if the result does not verify.
+//!
+//! Like [`super::musig2`], signing consumes the nonces so that they can't
+//! be used twice.
+
+use std::fmt::Debug;
+
+use super::{
+    edwards25519::{self, EdwardsPoint},
+    group::Group,
+    modular::{Modulus, Residue},
+    ristretto255::RistrettoPoint,
+};
+use crate::{rng, sha::sha512::Sha512, sss::polynomial};
+
+/// A prime-order group with the hash functions `H1` to `H5` of RFC 9591.
+pub trait Ciphersuite: Copy + Debug + Eq {
+    type Element: Group + Debug + Eq;
+    /// The order of the group, a modulus of 256 bits at most.
+    type Order: Modulus<4>;
+
+    /// The `contextString` prefixed to the hashes.
+    const CONTEXT: &'static [u8];
+
+    fn mul(element: &Self::Element, s: &Scalar<Self>) -> Self::Element;
+    fn mul_base(s: &Scalar<Self>) -> Self::Element;
+    fn serialize(element: &Self::Element) -> Vec<u8>;
+
+    /// `None` unless `bytes` canonically encodes an element of the
+    /// prime-order group other than the identity.
+    fn deserialize(bytes: &[u8]) -> Option<Self::Element>;
+
+    /// 32 bytes, little-endian unless the suite says otherwise.
+    fn serialize_scalar(s: &Scalar<Self>) -> Vec<u8> {
+        s.to_le_bytes()
+    }
+
+    fn deserialize_scalar(bytes: &[u8]) -> Option<Scalar<Self>> {
+        Scalar::<Self>::from_canonical_le_bytes(bytes)
+    }
+
+    /// `H4` and `H5`: `H(contextString || label || msg)`, with SHA-512
+    /// unless the suite says otherwise.
+    fn hash(label: &[u8], msg: &[u8]) -> Vec<u8> {
+        let mut sha = Sha512::new(Self::CONTEXT);
+        sha.update(label);
+        sha.update(msg);
+
+        sha.result().to_vec()
+    }
+
+    /// `H1` and `H3`: [`Ciphersuite::hash`] reduced to a scalar.
+    fn hash_to_scalar(label: &[u8], msg: &[u8]) -> Scalar<Self> {
+        Scalar::<Self>::from_le_bytes(&Self::hash(label, msg))
+    }
+
+    /// `H2`, the challenge.
+    fn h2(msg: &[u8]) -> Scalar<Self> {
+        Self::hash_to_scalar(b"chal", msg)
+    }
+
+    /// The multiple of `element` compared when verifying the final
+    /// signature.
+    fn clear_cofactor(element: &Self::Element) -> Self::Element {
+        *element
+    }
+}
+
+/// An integer modulo the order of the group of `C`.
+pub type Scalar<C> = Residue<<C as Ciphersuite>::Order, 4>;
+
+/// FROST(ristretto255, SHA-512)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ristretto255;
+
+/// FROST(Ed25519, SHA-512)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ed25519;
+
+impl Ciphersuite for Ristretto255 {
+    type Element = RistrettoPoint;
+    type Order = edwards25519::GroupOrder;
+
+    const CONTEXT: &'static [u8] = b"FROST-RISTRETTO255-SHA512-v1";
+
+    fn mul(element: &RistrettoPoint, s: &Scalar<Self>) -> RistrettoPoint {
+        element.mul(s)
+    }
+
+    fn mul_base(s: &Scalar<Self>) -> RistrettoPoint {
+        RistrettoPoint::mul_base(s)
+    }
+
+    fn serialize(element: &RistrettoPoint) -> Vec<u8> {
+        element.compress().to_vec()
+    }
+
+    fn deserialize(bytes: &[u8]) -> Option<RistrettoPoint> {
+        RistrettoPoint::decompress(bytes.try_into().ok()?).filter(|p| !p.is_identity())
+    }
+}
+
+impl Ciphersuite for Ed25519 {
+    type Element = EdwardsPoint;
+    type Order = edwards25519::GroupOrder;
+
+    const CONTEXT: &'static [u8] = b"FROST-ED25519-SHA512-v1";
+
+    fn mul(element: &EdwardsPoint, s: &Scalar<Self>) -> EdwardsPoint {
+        element.mul(s)
+    }
+
+    fn mul_base(s: &Scalar<Self>) -> EdwardsPoint {
+        EdwardsPoint::mul_base(s)
+    }
+
+    fn serialize(element: &EdwardsPoint) -> Vec<u8> {
+        element.compress().to_vec()
+    }
+
+    fn deserialize(bytes: &[u8]) -> Option<EdwardsPoint> {
+        EdwardsPoint::decompress(bytes.try_into().ok()?)
+            .filter(|p| !p.is_identity() && p.is_torsion_free())
+    }
+
+    /// The Ed25519 challenge `SHA-512(R || A || M)`, without the context.
+    fn h2(msg: &[u8]) -> Scalar<Self> {
+        Scalar::<Self>::from_le_bytes(&Sha512::new(msg).result())
+    }
+
+    fn clear_cofactor(element: &EdwardsPoint) -> EdwardsPoint {
+        element.mul_by_cofactor()
+    }
+}
+
+/// A participant's share `f(i)` of the group secret `f(0)`.
+#[derive(Clone)]
+pub struct SecretShare<C: Ciphersuite> {
+    /// The non-zero identifier `i`.
+    pub identifier: Scalar<C>,
+    pub signing_share: Scalar<C>,
+}
+
+/// A signer's hiding and binding nonces for one signature. Deliberately
+/// neither `Clone` nor `Copy`.
+pub struct SigningNonces<C: Ciphersuite> {
+    hiding: Scalar<C>,
+    binding: Scalar<C>,
+}
+
+/// The commitments `[d] B` and `[e] B` to a signer's nonces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SigningCommitments<C: Ciphersuite> {
+    pub identifier: Scalar<C>,
+    pub hiding: C::Element,
+    pub binding: C::Element,
+}
+
+// Key generation
+
+/// Split `secret` into `max_participants` shares, any `min_participants` of
+/// which can sign, with identifiers `1..=max_participants`. Returns the
+/// shares, the group public key and the VSS commitment to the polynomial.
+///
+/// Panics unless `2 <= min_participants <= max_participants`.
+pub fn trusted_dealer_keygen<C: Ciphersuite>(
+    secret: &Scalar<C>,
+    max_participants: u16,
+    min_participants: u16,
+) -> (Vec<SecretShare<C>>, C::Element, Vec<C::Element>) {
+    assert!(
+        2 <= min_participants && min_participants <= max_participants,
+        "invalid threshold"
+    );
+
+    let mut coefficients = vec![*secret];
+    coefficients.extend((1..min_participants).map(|_| random_nonzero_scalar::<C>()));
+
+    trusted_dealer_keygen_with(&coefficients, max_participants)
+}
+
+/// [`trusted_dealer_keygen`] with the given polynomial, constant term first.
+fn trusted_dealer_keygen_with<C: Ciphersuite>(
+    coefficients: &[Scalar<C>],
+    max_participants: u16,
+) -> (Vec<SecretShare<C>>, C::Element, Vec<C::Element>) {
+    let shares = (1..=max_participants as u64)
+        .map(|i| {
+            let identifier = Scalar::<C>::from_u64(i);
+
+            SecretShare {
+                identifier,
+                signing_share: polynomial::evaluate(coefficients, &identifier),
+            }
+        })
+        .collect();
+
+    let commitment = coefficients.iter().map(C::mul_base).collect::<Vec<_>>();
+
+    (shares, commitment[0], commitment)
+}
+
+/// Check `share` against the dealer's VSS commitment.
+pub fn vss_verify<C: Ciphersuite>(share: &SecretShare<C>, commitment: &[C::Element]) -> bool {
+    C::mul_base(&share.signing_share)
+        == polynomial::evaluate_commitment(commitment, &share.identifier)
+}
+
+/// The group public key and the public keys of participants
+/// `1..=max_participants`, from the VSS commitment.
+pub fn derive_group_info<C: Ciphersuite>(
+    max_participants: u16,
+    commitment: &[C::Element],
+) -> (C::Element, Vec<C::Element>) {
+    let keys = (1..=max_participants as u64)
+        .map(|i| polynomial::evaluate_commitment(commitment, &Scalar::<C>::from_u64(i)))
+        .collect();
+
+    (commitment[0], keys)
+}
+
+// Signing
+
+/// Round one: fresh nonces, each derived from 32 random bytes and the
+/// signer's share so that a weak CSPRNG alone does not expose them.
+pub fn commit<C: Ciphersuite>(share: &SecretShare<C>) -> (SigningNonces<C>, SigningCommitments<C>) {
+    commit_with(share, &rng::bytes(), &rng::bytes())
+}
+
+/// [`commit`] with the given random bytes for the hiding and binding
+/// nonces.
+fn commit_with<C: Ciphersuite>(
+    share: &SecretShare<C>,
+    hiding_random: &[u8; 32],
+    binding_random: &[u8; 32],
+) -> (SigningNonces<C>, SigningCommitments<C>) {
+    let nonces = SigningNonces {
+        hiding: nonce_generate_with::<C>(hiding_random, &share.signing_share),
+        binding: nonce_generate_with::<C>(binding_random, &share.signing_share),
+    };
+
+    let commitments = SigningCommitments {
+        identifier: share.identifier,
+        hiding: C::mul_base(&nonces.hiding),
+        binding: C::mul_base(&nonces.binding),
+    };
+
+    (nonces, commitments)
+}
+
+/// Round two: the signature share `d + e rho + lambda s c`, consuming
+/// `nonces`. `None` if `share` is not among `commitments` or an identifier
+/// appears twice.
+pub fn sign<C: Ciphersuite>(
+    share: &SecretShare<C>,
+    nonces: SigningNonces<C>,
+    group_public_key: &C::Element,
+    msg: &[u8],
+    commitments: &[SigningCommitments<C>],
+) -> Option<Scalar<C>> {
+    let binding_factor =
+        binding_factor_for::<C>(share.identifier, group_public_key, msg, commitments)?;
+    let group_commitment = compute_group_commitment::<C>(group_public_key, msg, commitments);
+
+    let lambda = interpolating_value::<C>(commitments, &share.identifier)?;
+    let challenge = compute_challenge::<C>(&group_commitment, group_public_key, msg);
+
+    Some(nonces.hiding + nonces.binding * binding_factor + lambda * share.signing_share * challenge)
+}
+
+/// The signature `R || z` from the shares of all signers in `commitments`.
+/// If it does not [`verify`], [`verify_signature_share`] tells who cheated.
+pub fn aggregate<C: Ciphersuite>(
+    group_public_key: &C::Element,
+    msg: &[u8],
+    commitments: &[SigningCommitments<C>],
+    signature_shares: &[Scalar<C>],
+) -> Vec<u8> {
+    let group_commitment = compute_group_commitment::<C>(group_public_key, msg, commitments);
+    let z = signature_shares
+        .iter()
+        .fold(Scalar::<C>::ZERO, |acc, z_i| acc + *z_i);
+
+    [C::serialize(&group_commitment), C::serialize_scalar(&z)].concat()
+}
+
+/// Check the signature share of the signer `identifier`, whose public key
+/// is `public_key`, against its commitments.
+pub fn verify_signature_share<C: Ciphersuite>(
+    identifier: &Scalar<C>,
+    public_key: &C::Element,
+    signature_share: &Scalar<C>,
+    group_public_key: &C::Element,
+    msg: &[u8],
+    commitments: &[SigningCommitments<C>],
+) -> bool {
+    let Some(own) = commitments.iter().find(|c| c.identifier == *identifier) else {
+        return false;
+    };
+    let Some(binding_factor) =
+        binding_factor_for::<C>(*identifier, group_public_key, msg, commitments)
+    else {
+        return false;
+    };
+    let Some(lambda) = interpolating_value::<C>(commitments, identifier) else {
+        return false;
+    };
+
+    let group_commitment = compute_group_commitment::<C>(group_public_key, msg, commitments);
+    let challenge = compute_challenge::<C>(&group_commitment, group_public_key, msg);
+
+    let commitment_share = own.hiding + C::mul(&own.binding, &binding_factor);
+
+    C::mul_base(signature_share) == commitment_share + C::mul(public_key, &(challenge * lambda))
+}
+
+/// Check a final signature `R || z` under the group key, `[z] B = R + [c] PK`
+/// up to [`Ciphersuite::clear_cofactor`].
+pub fn verify<C: Ciphersuite>(group_public_key: &C::Element, msg: &[u8], sig: &[u8]) -> bool {
+    // Scalars take 32 bytes in every suite.
+    let Some((r, z)) = sig.split_at_checked(sig.len().wrapping_sub(32)) else {
+        return false;
+    };
+    let Some(r) = C::deserialize(r) else {
+        return false;
+    };
+    let Some(z) = C::deserialize_scalar(z) else {
+        return false;
+    };
+
+    let challenge = compute_challenge::<C>(&r, group_public_key, msg);
+
+    C::clear_cofactor(&C::mul_base(&z))
+        == C::clear_cofactor(&(r + C::mul(group_public_key, &challenge)))
+}
+
+// Helpers
+
+/// `H3(random || SerializeScalar(secret))`, where `random` must be 32 fresh
+/// random bytes.
+fn nonce_generate_with<C: Ciphersuite>(random: &[u8; 32], secret: &Scalar<C>) -> Scalar<C> {
+    C::hash_to_scalar(
+        b"nonce",
+        &[&random[..], &C::serialize_scalar(secret)].concat(),
+    )
+}
+
+/// The binding factors `rho_i = H1(PK || H4(msg) || H5(commitments) || i)`
+/// of all signers, paired with their identifiers.
+fn compute_binding_factors<C: Ciphersuite>(
+    group_public_key: &C::Element,
+    msg: &[u8],
+    commitments: &[SigningCommitments<C>],
+) -> Vec<(Scalar<C>, Scalar<C>)> {
+    let mut sorted = commitments.to_vec();
+    sorted.sort_by_key(|c| c.identifier.to_be_bytes());
+
+    let encoded = sorted
+        .iter()
+        .flat_map(|c| {
+            [
+                C::serialize_scalar(&c.identifier),
+                C::serialize(&c.hiding),
+                C::serialize(&c.binding),
+            ]
+            .concat()
+        })
+        .collect::<Vec<_>>();
+
+    let prefix = [
+        &C::serialize(group_public_key)[..],
+        &C::hash(b"msg", msg),
+        &C::hash(b"com", &encoded),
+    ]
+    .concat();
+
+    sorted
+        .iter()
+        .map(|c| {
+            let rho_input = [&prefix[..], &C::serialize_scalar(&c.identifier)].concat();
+
+            (c.identifier, C::hash_to_scalar(b"rho", &rho_input))
+        })
+        .collect()
+}
+
+fn binding_factor_for<C: Ciphersuite>(
+    identifier: Scalar<C>,
+    group_public_key: &C::Element,
+    msg: &[u8],
+    commitments: &[SigningCommitments<C>],
+) -> Option<Scalar<C>> {
+    compute_binding_factors::<C>(group_public_key, msg, commitments)
+        .into_iter()
+        .find(|(i, _)| *i == identifier)
+        .map(|(_, rho)| rho)
+}
+
+/// `R = sum D_i + [rho_i] E_i`
+fn compute_group_commitment<C: Ciphersuite>(
+    group_public_key: &C::Element,
+    msg: &[u8],
+    commitments: &[SigningCommitments<C>],
+) -> C::Element {
+    let binding_factors = compute_binding_factors::<C>(group_public_key, msg, commitments);
+
+    commitments.iter().fold(C::Element::IDENTITY, |acc, c| {
+        let (_, rho) = binding_factors
+            .iter()
+            .find(|(i, _)| *i == c.identifier)
+            .unwrap();
+
+        acc + c.hiding + C::mul(&c.binding, rho)
+    })
+}
+
+/// `H2(R || PK || msg)`
+fn compute_challenge<C: Ciphersuite>(
+    group_commitment: &C::Element,
+    group_public_key: &C::Element,
+    msg: &[u8],
+) -> Scalar<C> {
+    C::h2(
+        &[
+            &C::serialize(group_commitment)[..],
+            &C::serialize(group_public_key),
+            msg,
+        ]
+        .concat(),
+    )
+}
+
+/// The Lagrange coefficient of `x_i` at 0 over the identifiers of
+/// `commitments`, or `None` if `x_i` is not one of them or one repeats.
+fn interpolating_value<C: Ciphersuite>(
+    commitments: &[SigningCommitments<C>],
+    x_i: &Scalar<C>,
+) -> Option<Scalar<C>> {
+    let identifiers = commitments.iter().map(|c| c.identifier).collect::<Vec<_>>();
+
+    polynomial::lagrange_coefficient(&identifiers, x_i)
+}
+
+fn random_nonzero_scalar<C: Ciphersuite>() -> Scalar<C> {
+    loop {
+        let s = Scalar::<C>::from_le_bytes(&rng::bytes::<64>());
+
+        if !s.is_zero() {
+            return s;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{unhex, unhex_array};
+
+    fn scalar<C: Ciphersuite>(hex: &str) -> Scalar<C> {
+        C::deserialize_scalar(&unhex(hex)).unwrap()
+    }
+
+    /// The inputs and outputs of a signing round by participants 1 and 3 of
+    /// three, as in RFC 9591, appendix E.
+    struct Vector {
+        secret: &'static str,
+        coefficient: &'static str,
+        group_public_key: &'static str,
+        shares: [&'static str; 3],
+        /// The hiding and binding nonce randomness of each signer.
+        randomness: [[&'static str; 2]; 2],
+        /// The hiding and binding nonces and their commitments.
+        nonces: [[&'static str; 4]; 2],
+        binding_factors: [&'static str; 2],
+        signature_shares: [&'static str; 2],
+        signature: &'static str,
+    }
+
+    fn check_vector<C: Ciphersuite>(vector: &Vector) {
+        let msg = b"test";
+        let (shares, group_public_key, commitment) = trusted_dealer_keygen_with(
+            &[scalar::<C>(vector.secret), scalar::<C>(vector.coefficient)],
+            3,
+        );
+
+        assert_eq!(
+            C::serialize(&group_public_key),
+            unhex(vector.group_public_key)
+        );
+        for (share, expected) in shares.iter().zip(vector.shares) {
+            assert_eq!(share.signing_share, scalar::<C>(expected));
+            assert!(vss_verify::<C>(share, &commitment));
+        }
+
+        let signers = [&shares[0], &shares[2]];
+        let (nonces, commitments): (Vec<_>, Vec<_>) = signers
+            .iter()
+            .zip(vector.randomness)
+            .map(|(share, [hiding, binding])| {
+                commit_with(share, &unhex_array(hiding), &unhex_array(binding))
+            })
+            .unzip();
+
+        for ((nonces, commitments), expected) in nonces.iter().zip(&commitments).zip(vector.nonces)
+        {
+            assert_eq!(nonces.hiding, scalar::<C>(expected[0]));
+            assert_eq!(nonces.binding, scalar::<C>(expected[1]));
+            assert_eq!(C::serialize(&commitments.hiding), unhex(expected[2]));
+            assert_eq!(C::serialize(&commitments.binding), unhex(expected[3]));
+        }
+
+        let binding_factors = compute_binding_factors(&group_public_key, msg, &commitments);
+        for ((identifier, rho), (share, expected)) in binding_factors
+            .iter()
+            .zip(signers.iter().zip(vector.binding_factors))
+        {
+            assert_eq!(*identifier, share.identifier);
+            assert_eq!(*rho, scalar::<C>(expected));
+        }
+
+        let signature_shares = signers
+            .iter()
+            .zip(nonces)
+            .map(|(share, nonces)| {
+                sign(share, nonces, &group_public_key, msg, &commitments).unwrap()
+            })
+            .collect::<Vec<_>>();
+        for (z, expected) in signature_shares.iter().zip(vector.signature_shares) {
+            assert_eq!(*z, scalar::<C>(expected));
+        }
+
+        let sig = aggregate(&group_public_key, msg, &commitments, &signature_shares);
+        assert_eq!(sig, unhex(vector.signature));
+        assert!(verify::<C>(&group_public_key, msg, &sig));
+        assert!(!verify::<C>(&group_public_key, b"tesu", &sig));
+    }
+
+    /// RFC 9591, appendix E.1: FROST(Ed25519, SHA-512).
+    #[test]
+    fn ed25519_vectors() {
+        check_vector::<Ed25519>(&Vector {
+            secret: "7b1c33d3f5291d85de664833beb1ad469f7fb6025a0ec78b3a790c6e13a98304",
+            coefficient: "178199860edd8c62f5212ee91eff1295d0d670ab4ed4506866bae57e7030b204",
+            group_public_key: "15d21ccd7ee42959562fc8aa63224c8851fb3ec85a3faf66040d380fb9738673",
+            shares: [
+                "929dcc590407aae7d388761cddb0c0db6f5627aea8e217f4a033f2ec83d93509",
+                "a91e66e012e4364ac9aaa405fcafd370402d9859f7b6685c07eed76bf409e80d",
+                "d3cb090a075eb154e82fdb4b3cb507f110040905468bb9c46da8bdea643a9a02",
+            ],
+            randomness: [
+                [
+                    "0fd2e39e111cdc266f6c0f4d0fd45c947761f1f5d3cb583dfcb9bbaf8d4c9fec",
+                    "69cd85f631d5f7f2721ed5e40519b1366f340a87c2f6856363dbdcda348a7501",
+                ],
+                [
+                    "86d64a260059e495d0fb4fcc17ea3da7452391baa494d4b00321098ed2a0062f",
+                    "13e6b25afb2eba51716a9a7d44130c0dbae0004a9ef8d7b5550c8a0e07c61775",
+                ],
+            ],
+            nonces: [
+                [
+                    "812d6104142944d5a55924de6d49940956206909f2acaeedecda2b726e630407",
+                    "b1110165fc2334149750b28dd813a39244f315cff14d4e89e6142f262ed83301",
+                    "b5aa8ab305882a6fc69cbee9327e5a45e54c08af61ae77cb8207be3d2ce13de3",
+                    "67e98ab55aa310c3120418e5050c9cf76cf387cb20ac9e4b6fdb6f82a469f932",
+                ],
+                [
+                    "c256de65476204095ebdc01bd11dc10e57b36bc96284595b8215222374f99c0e",
+                    "243d71944d929063bc51205714ae3c2218bd3451d0214dfb5aeec2a90c35180d",
+                    "cfbdb165bd8aad6eb79deb8d287bcc0ab6658ae57fdcc98ed12c0669e90aec91",
+                    "7487bc41a6e712eea2f2af24681b58b1cf1da278ea11fe4e8b78398965f13552",
+                ],
+            ],
+            binding_factors: [
+                "f2cb9d7dd9beff688da6fcc83fa89046b3479417f47f55600b106760eb3b5603",
+                "b087686bf35a13f3dc78e780a34b0fe8a77fef1b9938c563f5573d71d8d7890f",
+            ],
+            signature_shares: [
+                "001719ab5a53ee1a12095cd088fd149702c0720ce5fd2f29dbecf24b7281b603",
+                "bd86125de990acc5e1f13781d8e32c03a9bbd4c53539bbc106058bfd14326007",
+            ],
+            signature: concat!(
+                "36282629c383bb820a88b71cae937d41f2f2adfcc3d02e55507e2fb9e2dd3cbe",
+                "bd9d2b0844e49ae0f3fa935161e1419aab7b47d21a37ebeae1f17d4987b3160b",
+            ),
+        });
+    }
+
+    /// RFC 9591, appendix E.3: FROST(ristretto255, SHA-512).
+    #[test]
+    fn ristretto255_vectors() {
+        check_vector::<Ristretto255>(&Vector {
+            secret: "1b25a55e463cfd15cf14a5d3acc3d15053f08da49c8afcf3ab265f2ebc4f970b",
+            coefficient: "410f8b744b19325891d73736923525a4f596c805d060dfb9c98009d34e3fec02",
+            group_public_key: "e2a62f39eede11269e3bd5a7d97554f5ca384f9f6d3dd9c3c0d05083c7254f57",
+            shares: [
+                "5c3430d391552f6e60ecdc093ff9f6f4488756aa6cebdbad75a768010b8f830e",
+                "b06fc5eac20b4f6e1b271d9df2343d843e1e1fb03c4cbb673f2872d459ce6f01",
+                "f17e505f0e2581c6acfe54d3846a622834b5e7b50cad9a2109a97ba7a80d5c04",
+            ],
+            randomness: [
+                [
+                    "f595a133b4d95c6e1f79887220c8b275ce6277e7f68a6640e1e7140f9be2fb5c",
+                    "34dd1001360e3513cb37bebfabe7be4a32c5bb91ba19fbd4360d039111f0fbdc",
+                ],
+                [
+                    "daa0cf42a32617786d390e0c7edfbf2efbd428037069357b5173ae61d6dd5d5e",
+                    "b4387e72b2e4108ce4168931cc2c7fcce5f345a5297368952c18b5fc8473f050",
+                ],
+            ],
+            nonces: [
+                [
+                    "214f2cabb86ed71427ea7ad4283b0fae26b6746c801ce824b83ceb2b99278c03",
+                    "c9b8f5e16770d15603f744f8694c44e335e8faef00dad182b8d7a34a62552f0c",
+                    "965def4d0958398391fc06d8c2d72932608b1e6255226de4fb8d972dac15fd57",
+                    "ec5170920660820007ae9e1d363936659ef622f99879898db86e5bf1d5bf2a14",
+                ],
+                [
+                    "3f7927872b0f9051dd98dd73eb2b91494173bbe0feb65a3e7e58d3e2318fa40f",
+                    "ffd79445fb8030f0a3ddd3861aa4b42b618759282bfe24f1f9304c7009728305",
+                    "480e06e3de182bf83489c45d7441879932fd7b434a26af41455756264fbd5d6e",
+                    "3064746dfd3c1862ef58fc68c706da287dd925066865ceacc816b3a28c7b363b",
+                ],
+            ],
+            binding_factors: [
+                "8967fd70fa06a58e5912603317fa94c77626395a695a0e4e4efc4476662eba0c",
+                "f2c1bb7c33a10511158c2f1766a4a5fadf9f86f2a92692ed333128277cc31006",
+            ],
+            signature_shares: [
+                "9285f875923ce7e0c491a592e9ea1865ec1b823ead4854b48c8a46287749ee09",
+                "7cb211fe0e3d59d25db6e36b3fb32344794139602a7b24f1ae0dc4e26ad7b908",
+            ],
+            signature: concat!(
+                "fc45655fbc66bbffad654ea4ce5fdae253a49a64ace25d9adb62010dd9fb2555",
+                "2164141787162e5b4cab915b4aa45d94655dbb9ed7c378a53b980a0be220a802",
+            ),
+        });
+    }
+
+    #[test]
+    fn bad_signature_share() {
+        let msg = b"message";
+        let (shares, group_public_key, commitment) =
+            trusted_dealer_keygen::<Ristretto255>(&random_nonzero_scalar::<Ristretto255>(), 3, 2);
+        let (_, public_keys) = derive_group_info::<Ristretto255>(3, &commitment);
+
+        let (nonces_1, commitments_1) = commit(&shares[0]);
+        let (nonces_2, commitments_2) = commit(&shares[1]);
+        let commitments = [commitments_1, commitments_2];
+
+        let z_1 = sign(&shares[0], nonces_1, &group_public_key, msg, &commitments).unwrap();
+        let z_2 = sign(&shares[1], nonces_2, &group_public_key, msg, &commitments).unwrap();
+
+        let check = |i: usize, public_key: &RistrettoPoint, z: &Scalar<Ristretto255>| {
+            verify_signature_share(
+                &shares[i].identifier,
+                public_key,
+                z,
+                &group_public_key,
+                msg,
+                &commitments,
+            )
+        };
+
+        assert!(check(0, &public_keys[0], &z_1));
+        assert!(check(1, &public_keys[1], &z_2));
+
+        // A tampered share, the share of another signer, the wrong public
+        // key, and a signer without commitments.
+        assert!(!check(
+            0,
+            &public_keys[0],
+            &(z_1 + Scalar::<Ristretto255>::ONE)
+        ));
+        assert!(!check(0, &public_keys[0], &z_2));
+        assert!(!check(0, &public_keys[1], &z_1));
+        assert!(!check(2, &public_keys[2], &z_1));
+
+        let sig = aggregate(&group_public_key, msg, &commitments, &[z_1, z_1]);
+        assert!(!verify::<Ristretto255>(&group_public_key, msg, &sig));
+    }
+
+    #[test]
+    fn bad_vss_share() {
+        let (shares, _, commitment) =
+            trusted_dealer_keygen::<Ed25519>(&random_nonzero_scalar::<Ed25519>(), 5, 3);
+        let (_, _, other_commitment) =
+            trusted_dealer_keygen::<Ed25519>(&random_nonzero_scalar::<Ed25519>(), 5, 3);
+
+        for share in &shares {
+            assert!(vss_verify::<Ed25519>(share, &commitment));
+            assert!(!vss_verify::<Ed25519>(share, &other_commitment));
+
+            let mut tampered = share.clone();
+            tampered.signing_share = tampered.signing_share + Scalar::<Ed25519>::ONE;
+            assert!(!vss_verify::<Ed25519>(&tampered, &commitment));
+
+            let mut moved = share.clone();
+            moved.identifier = moved.identifier + Scalar::<Ed25519>::ONE;
+            assert!(!vss_verify::<Ed25519>(&moved, &commitment));
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! # Groups
+//! Algorithms shared by the point types of different curves.
+
+use std::ops::Add;
+
+use super::modular::{Modulus, Residue};
+
+/// A group of curve points, written additively.
+pub trait Group: Copy + Add<Output = Self> {
+    const IDENTITY: Self;
+
+    fn double(&self) -> Self;
+}
+
+/// `sum [s_i] P_i` with 4-bit fixed windows, sharing the doublings between
+/// all terms. Variable time: only for public inputs.
+pub fn vartime_multiscalar_mul<G: Group, M: Modulus<N>, const N: usize>(
+    scalars: &[Residue<M, N>],
+    points: &[G],
+) -> G {
+    assert_eq!(scalars.len(), points.len());
+
+    let tables = points
+        .iter()
+        .map(|p| {
+            let mut table = [G::IDENTITY; 16];
+
+            for j in 1..16 {
+                table[j] = table[j - 1] + *p;
+            }
+
+            table
+        })
+        .collect::<Vec<_>>();
+
+    let digits = scalars.iter().map(|s| s.to_le_bytes()).collect::<Vec<_>>();
+
+    let mut acc = G::IDENTITY;
+
+    for w in (0..16 * N).rev() {
+        acc = acc.double().double().double().double();
+
+        for (table, s) in tables.iter().zip(&digits) {
+            let digit = (s[w / 2] >> (4 * (w % 2))) & 0xf;
+
+            if digit != 0 {
+                acc = acc + table[digit as usize];
+            }
+        }
+    }
+
+    acc
+}
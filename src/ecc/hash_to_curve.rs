@@ -0,0 +1,589 @@
+//! # Hash-to-curve for Curve25519 (RFC 9380)
+//! The `edwards25519_XMD:SHA-512_ELL2_*` and `curve25519_XMD:SHA-512_ELL2_*`
+//! suites: `expand_message_xmd` with SHA-512, the Elligator 2 map to
+//! Curve25519, and the rational map to edwards25519.
+//!
+//! Elligator 2 can also be run backwards. About half of all points have a
+//! representative, a field element that maps to them; encoded with random
+//! padding it is indistinguishable from 32 uniform bytes, which is what
+//! [`representative`] and [`from_representative`] are for. For X25519 keys,
+//! [`generate_representable`] makes key pairs whose public keys all have
+//! one, and [`x25519_representative`] and [`x25519_from_representative`]
+//! convert between u-coordinates and representatives.
+
+use super::{
+    curve25519::{FieldElement, PublicKey, SecretKey},
+    edwards25519::{EdwardsPoint, Scalar},
+    modular::{Modulus, Residue},
+};
+use crate::{rng, sha::sha512::Sha512};
+
+/// `p = 2^255 - 19`, to reduce the wide outputs of `hash_to_field`.
+#[derive(Debug, Clone, Copy)]
+struct FieldPrime;
+
+impl Modulus<4> for FieldPrime {
+    const P: [u64; 4] = [
+        0xffffffffffffffed,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+    ];
+}
+
+/// `J`, the Montgomery coefficient of Curve25519.
+const J: FieldElement = FieldElement([486662, 0, 0, 0, 0]);
+
+/// `sqrt(-486664)` with `sgn0 = 0`, for the map to edwards25519.
+const SQRT_M486664: FieldElement = FieldElement([
+    0x604aaff457e06,
+    0x2296fa350598d,
+    0x7f13dfb16874f,
+    0x35de93d846e01,
+    0x0f26edf460a00,
+]);
+
+/// A point of order 8 on edwards25519, generating the torsion subgroup.
+const LOW_ORDER: EdwardsPoint = EdwardsPoint {
+    x: FieldElement([
+        0x14646c545d14a,
+        0x6027cbc471bd4,
+        0x3792aed7064f1,
+        0x5147499cc991c,
+        0x1fd5b9a006394,
+    ]),
+    y: FieldElement([
+        0x04d3d706a17c7,
+        0x1aec1679749fb,
+        0x14c80a83d9c40,
+        0x3a763661c967d,
+        0x7a03ac9277fdc,
+    ]),
+    z: FieldElement::ONE,
+    t: FieldElement([
+        0x5253ede7e8781,
+        0x699bf50b2c5a1,
+        0x3e2bc2aea64f5,
+        0x5f598193b68c9,
+        0x6ce244c360a26,
+    ]),
+};
+
+/// `len` pseudorandom bytes from `msg` and the domain separation tag `dst`
+/// (RFC 9380, section 5.3.1).
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    expand_message_xmd_with(|x| Sha512::new(x).result().to_vec(), 128, 64, msg, dst, len)
+}
+
+/// [`expand_message_xmd`] over any hash with `block_size` byte input blocks
+/// and `output_size` byte digests, e.g. SHA-256 with 64 and 32.
+pub fn expand_message_xmd_with<H>(
+    hash: H,
+    block_size: usize,
+    output_size: usize,
+    msg: &[u8],
+    dst: &[u8],
+    len: usize,
+) -> Vec<u8>
+where
+    H: Fn(&[u8]) -> Vec<u8>,
+{
+    let ell = len.div_ceil(output_size);
+    assert!(
+        ell <= 255 && len <= 0xffff,
+        "expand_message_xmd output too long"
+    );
+
+    let mut dst_prime = if dst.len() > 255 {
+        hash(&[b"H2C-OVERSIZE-DST-", dst].concat())
+    } else {
+        dst.to_vec()
+    };
+    dst_prime.push(dst_prime.len() as u8);
+
+    let b_0 = hash(
+        &[
+            &vec![0u8; block_size][..],
+            msg,
+            &(len as u16).to_be_bytes(),
+            &[0],
+            &dst_prime,
+        ]
+        .concat(),
+    );
+
+    let mut out = Vec::with_capacity(ell * output_size);
+    let mut b_i = vec![0u8; output_size];
+
+    for i in 1..=ell {
+        let mut input = b_0.iter().zip(&b_i).map(|(x, y)| x ^ y).collect::<Vec<_>>();
+        input.push(i as u8);
+        input.extend_from_slice(&dst_prime);
+
+        b_i = hash(&input);
+        out.extend_from_slice(&b_i);
+    }
+
+    out.truncate(len);
+    out
+}
+
+/// `count` field elements from `msg`, each reduced from 48 bytes
+/// (RFC 9380, section 5.2).
+pub fn hash_to_field(msg: &[u8], dst: &[u8], count: usize) -> Vec<FieldElement> {
+    expand_message_xmd(msg, dst, 48 * count)
+        .chunks(48)
+        .map(|chunk| {
+            let x = Residue::<FieldPrime, 4>::from_be_bytes(chunk);
+            FieldElement::from_bytes(&x.to_le_bytes().try_into().unwrap())
+        })
+        .collect()
+}
+
+/// The Elligator 2 map to Curve25519 with `Z = 2` (RFC 9380, section 6.7.1),
+/// returning the Montgomery point `(s, t)`. Constant time.
+pub fn map_to_curve25519(u: &FieldElement) -> (FieldElement, FieldElement) {
+    // 1 + 2 u^2 is never zero, as -1/2 is not a square.
+    let x1 = -J * (FieldElement::ONE + (u.square() + u.square())).invert();
+    let x2 = -x1 - J;
+
+    let (gx1_square, y1) = FieldElement::sqrt_ratio_i(&g(&x1), &FieldElement::ONE);
+    let (_, y2) = FieldElement::sqrt_ratio_i(&g(&x2), &FieldElement::ONE);
+
+    // The roots are non-negative; the first branch takes the negative one.
+    let x = FieldElement::select(&x2, &x1, gx1_square);
+    let y = FieldElement::select(&y2, &-y1, gx1_square);
+
+    (x, y)
+}
+
+/// [`map_to_curve25519`] followed by the rational map to edwards25519
+/// (RFC 9380, appendix D.1).
+pub fn map_to_edwards25519(u: &FieldElement) -> EdwardsPoint {
+    let (s, t) = map_to_curve25519(u);
+
+    montgomery_to_edwards(&s, &t)
+}
+
+/// `edwards25519_XMD:SHA-512_ELL2_RO_`: a random oracle to edwards25519,
+/// landing in the prime-order subgroup.
+pub fn hash_to_edwards25519(msg: &[u8], dst: &[u8]) -> EdwardsPoint {
+    let u = hash_to_field(msg, dst, 2);
+
+    (map_to_edwards25519(&u[0]) + map_to_edwards25519(&u[1])).mul_by_cofactor()
+}
+
+/// `edwards25519_XMD:SHA-512_ELL2_NU_`: cheaper than
+/// [`hash_to_edwards25519`], but the output is not uniformly distributed.
+pub fn encode_to_edwards25519(msg: &[u8], dst: &[u8]) -> EdwardsPoint {
+    let u = hash_to_field(msg, dst, 1);
+
+    map_to_edwards25519(&u[0]).mul_by_cofactor()
+}
+
+/// `curve25519_XMD:SHA-512_ELL2_RO_`, as the little-endian `u`-coordinate
+/// X25519 uses.
+pub fn hash_to_curve25519(msg: &[u8], dst: &[u8]) -> [u8; 32] {
+    edwards_to_montgomery(&hash_to_edwards25519(msg, dst))
+        .0
+        .to_bytes()
+}
+
+/// `curve25519_XMD:SHA-512_ELL2_NU_`, as the little-endian `u`-coordinate.
+pub fn encode_to_curve25519(msg: &[u8], dst: &[u8]) -> [u8; 32] {
+    edwards_to_montgomery(&encode_to_edwards25519(msg, dst))
+        .0
+        .to_bytes()
+}
+
+/// A random 32 byte encoding `r` with `map_to_edwards25519(r) = point`, or
+/// `None` if the point has no representative.
+///
+/// The sign of `r` and the unused top bit are random, so that encodings
+/// of random points are uniform bytes. For keys this also needs random
+/// points: a public key from a clamped secret always lies in the prime-order
+/// subgroup, which the representative would reveal; see
+/// [`generate_representable`].
+pub fn representative(point: &EdwardsPoint) -> Option<[u8; 32]> {
+    let (u, v) = edwards_to_montgomery(point);
+
+    representative_of(&u, v.is_negative())
+}
+
+/// The point a 32 byte representative maps to; every string is valid.
+pub fn from_representative(bytes: &[u8; 32]) -> EdwardsPoint {
+    map_to_edwards25519(&FieldElement::from_bytes(bytes))
+}
+
+/// A representative of the X25519 public key `public`, or `None` if it has
+/// none. Either point with this u-coordinate will do, so one is picked at
+/// random.
+pub fn x25519_representative(public: &PublicKey) -> Option<[u8; 32]> {
+    let [tweak] = rng::bytes::<1>();
+
+    representative_of(&FieldElement::from_bytes(&public.0), tweak & 1 == 1)
+}
+
+/// The X25519 public key a 32 byte representative maps to.
+pub fn x25519_from_representative(bytes: &[u8; 32]) -> PublicKey {
+    PublicKey(
+        map_to_curve25519(&FieldElement::from_bytes(bytes))
+            .0
+            .to_bytes(),
+    )
+}
+
+/// A fresh X25519 key pair whose public key has a representative, and that
+/// representative.
+///
+/// The public key is `[k] B + T` for the clamped secret `k` and a random `T`
+/// of order dividing 8, which makes it uniform over the whole curve instead
+/// of the prime-order subgroup. X25519 clamps scalars to multiples of 8, so
+/// `T` drops out of every shared secret. About half of all points have a
+/// representative; keys without one are discarded and drawn again.
+///
+/// The public key differs from [`SecretKey::public_key`], which leaves out
+/// `T`: publish only the representative.
+pub fn generate_representable() -> (SecretKey, PublicKey, [u8; 32]) {
+    loop {
+        let secret = SecretKey::generate();
+
+        let mut k = secret.to_bytes();
+        k[0] &= 248;
+        k[31] &= 127;
+        k[31] |= 64;
+
+        let [tweak] = rng::bytes::<1>();
+        let point =
+            EdwardsPoint::mul_base(&Scalar::from_le_bytes(&k)) + LOW_ORDER.mul_bits(&[tweak], 3);
+
+        if let Some(r) = representative(&point) {
+            let public = PublicKey(edwards_to_montgomery(&point).0.to_bytes());
+
+            return (secret, public, r);
+        }
+    }
+}
+
+/// The representative of the Montgomery point with u-coordinate `u` and a
+/// `v` of sign `negative`.
+fn representative_of(u: &FieldElement, negative: bool) -> Option<[u8; 32]> {
+    let u_plus_j = *u + J;
+
+    // The first branch of the map gives v with sgn0 = 1, and
+    // u = -J / (1 + 2 r^2); the second u = -J - x1.
+    let num = FieldElement::select(u, &u_plus_j, negative);
+    let den = FieldElement::select(&u_plus_j, u, negative);
+
+    if den.is_zero() {
+        return None;
+    }
+
+    let (is_square, r) = FieldElement::sqrt_ratio_i(&-num, &(den + den));
+
+    if !is_square {
+        return None;
+    }
+
+    let [tweak] = rng::bytes::<1>();
+    let r = FieldElement::select(&r, &-r, tweak & 1 == 1);
+
+    let mut bytes = r.to_bytes();
+    bytes[31] |= tweak & 0x80;
+
+    Some(bytes)
+}
+
+/// `x^3 + J x^2 + x`
+fn g(x: &FieldElement) -> FieldElement {
+    (x.square() + J * *x + FieldElement::ONE) * *x
+}
+
+/// `(x, y) = (sqrt(-486664) s / t, (s - 1) / (s + 1))`, with the exceptional
+/// points `t = 0` or `s = -1` going to the identity.
+fn montgomery_to_edwards(s: &FieldElement, t: &FieldElement) -> EdwardsPoint {
+    let num_x = SQRT_M486664 * *s;
+    let num_y = *s - FieldElement::ONE;
+    let den_y = *s + FieldElement::ONE;
+
+    let exceptional = t.is_zero() | den_y.is_zero();
+    let inv = (*t * den_y).invert();
+
+    let x = FieldElement::select(&(num_x * den_y * inv), &FieldElement::ZERO, exceptional);
+    let y = FieldElement::select(&(num_y * *t * inv), &FieldElement::ONE, exceptional);
+
+    EdwardsPoint::from_affine(x, y)
+}
+
+/// `(u, v) = ((1 + y) / (1 - y), sqrt(-486664) u / x)`, the inverse of
+/// [`montgomery_to_edwards`]. The identity goes to `(0, 0)`.
+fn edwards_to_montgomery(point: &EdwardsPoint) -> (FieldElement, FieldElement) {
+    let z_inv = point.z.invert();
+    let x = point.x * z_inv;
+    let y = point.y * z_inv;
+
+    let one_minus_y = FieldElement::ONE - y;
+    let inv = (one_minus_y * x).invert();
+
+    let u = (FieldElement::ONE + y) * x * inv;
+    let v = SQRT_M486664 * u * one_minus_y * inv;
+
+    (u, v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        sha::sha256::Sha256,
+        test_util::{unhex, unhex_array},
+    };
+
+    /// The messages of the RFC 9380 test vectors.
+    fn messages() -> [Vec<u8>; 5] {
+        [
+            b"".to_vec(),
+            b"abc".to_vec(),
+            b"abcdef0123456789".to_vec(),
+            [&b"q128_"[..], &[b'q'; 128]].concat(),
+            [&b"a512_"[..], &[b'a'; 512]].concat(),
+        ]
+    }
+
+    /// A field element from the big-endian hex of the RFC.
+    fn fe(hex: &str) -> FieldElement {
+        let mut bytes = unhex(hex);
+        bytes.reverse();
+
+        FieldElement::from_bytes(&bytes.try_into().unwrap())
+    }
+
+    /// RFC 9380, appendix J.5.1: `edwards25519_XMD:SHA-512_ELL2_RO_`.
+    const EDWARDS25519_RO: [(&str, &str); 5] = [
+        (
+            "3c3da6925a3c3c268448dcabb47ccde5439559d9599646a8260e47b1e4822fc6",
+            "09a6c8561a0b22bef63124c588ce4c62ea83a3c899763af26d795302e115dc21",
+        ),
+        (
+            "608040b42285cc0d72cbb3985c6b04c935370c7361f4b7fbdb1ae7f8c1a8ecad",
+            "1a8395b88338f22e435bbd301183e7f20a5f9de643f11882fb237f88268a5531",
+        ),
+        (
+            "6d7fabf47a2dc03fe7d47f7dddd21082c5fb8f86743cd020f3fb147d57161472",
+            "53060a3d140e7fbcda641ed3cf42c88a75411e648a1add71217f70ea8ec561a6",
+        ),
+        (
+            "5fb0b92acedd16f3bcb0ef83f5c7b7a9466b5f1e0d8d217421878ea3686f8524",
+            "2eca15e355fcfa39d2982f67ddb0eea138e2994f5956ed37b7f72eea5e89d2f7",
+        ),
+        (
+            "0efcfde5898a839b00997fbe40d2ebe950bc81181afbd5cd6b9618aa336c1e8c",
+            "6dc2fc04f266c5c27f236a80b14f92ccd051ef1ff027f26a07f8c0f327d8f995",
+        ),
+    ];
+
+    /// RFC 9380, appendix J.4.1: `curve25519_XMD:SHA-512_ELL2_RO_`.
+    const CURVE25519_RO: [(&str, &str); 5] = [
+        (
+            "2de3780abb67e861289f5749d16d3e217ffa722192d16bbd9d1bfb9d112b98c0",
+            "3b5dc2a498941a1033d176567d457845637554a2fe7a3507d21abd1c1bd6e878",
+        ),
+        (
+            "2b4419f1f2d48f5872de692b0aca72cc7b0a60915dd70bde432e826b6abc526d",
+            "1b8235f255a268f0a6fa8763e97eb3d22d149343d495da1160eff9703f2d07dd",
+        ),
+        (
+            "68ca1ea5a6acf4e9956daa101709b1eee6c1bb0df1de3b90d4602382a104c036",
+            "2a375b656207123d10766e68b938b1812a4a6625ff83cb8d5e86f58a4be08353",
+        ),
+        (
+            "096e9c8bae6c06b554c1ee69383bb0e82267e064236b3a30608d4ed20b73ac5a",
+            "1eb5a62612cafb32b16c3329794645b5b948d9f8ffe501d4e26b073fef6de355",
+        ),
+        (
+            "1bc61845a138e912f047b5e70ba9606ba2a447a4dade024c8ef3dd42b7bbc5fe",
+            "623d05e47b70e25f7f1d51dda6d7c23c9a18ce015fe3548df596ea9e38c69bf1",
+        ),
+    ];
+
+    /// RFC 9380, appendix K.3: `expand_message_xmd` with SHA-512, for 32 and
+    /// 128 bytes of output.
+    const EXPAND_XMD: [(&str, &str); 5] = [
+        (
+            "6b9a7312411d92f921c6f68ca0b6380730a1a4d982c507211a90964c394179ba",
+            concat!(
+                "41b037d1734a5f8df225dd8c7de38f851efdb45c372887be655212d07251b921",
+                "b052b62eaed99b46f72f2ef4cc96bfaf254ebbbec091e1a3b9e4fb5e5b619d2e",
+                "0c5414800a1d882b62bb5cd1778f098b8eb6cb399d5d9d18f5d5842cf5d13d7e",
+                "b00a7cff859b605da678b318bd0e65ebff70bec88c753b159a805d2c89c55961",
+            ),
+        ),
+        (
+            "0da749f12fbe5483eb066a5f595055679b976e93abe9be6f0f6318bce7aca8dc",
+            concat!(
+                "7f1dddd13c08b543f2e2037b14cefb255b44c83cc397c1786d975653e36a6b11",
+                "bdd7732d8b38adb4a0edc26a0cef4bb45217135456e58fbca1703cd6032cb134",
+                "7ee720b87972d63fbf232587043ed2901bce7f22610c0419751c065922b48843",
+                "1851041310ad659e4b23520e1772ab29dcdeb2002222a363f0c2b1c972b3efe1",
+            ),
+        ),
+        (
+            "087e45a86e2939ee8b91100af1583c4938e0f5fc6c9db4b107b83346bc967f58",
+            concat!(
+                "3f721f208e6199fe903545abc26c837ce59ac6fa45733f1baaf0222f8b7acb04",
+                "24814fcb5eecf6c1d38f06e9d0a6ccfbf85ae612ab8735dfdf9ce84c372a77c8",
+                "f9e1c1e952c3a61b7567dd0693016af51d2745822663d0c2367e3f4f0bed827f",
+                "eecc2aaf98c949b5ed0d35c3f1023d64ad1407924288d366ea159f46287e61ac",
+            ),
+        ),
+        (
+            "7336234ee9983902440f6bc35b348352013becd88938d2afec44311caf8356b3",
+            concat!(
+                "b799b045a58c8d2b4334cf54b78260b45eec544f9f2fb5bd12fb603eaee70db7",
+                "317bf807c406e26373922b7b8920fa29142703dd52bdf280084fb7ef69da78af",
+                "df80b3586395b433dc66cde048a258e476a561e9deba7060af40adf30c64249c",
+                "a7ddea79806ee5beb9a1422949471d267b21bc88e688e4014087a0b592b695ed",
+            ),
+        ),
+        (
+            "57b5f7e766d5be68a6bfe1768e3c2b7f1228b3e4b3134956dd73a59b954c66f4",
+            concat!(
+                "05b0bfef265dcee87654372777b7c44177e2ae4c13a27f103340d9cd11c86cb2",
+                "426ffcad5bd964080c2aee97f03be1ca18e30a1f14e27bc11ebbd650f305269c",
+                "c9fb1db08bf90bfc79b42a952b46daf810359e7bc36452684784a64952c343c5",
+                "2e5124cd1f71d474d5197fefc571a92929c9084ffe1112cf5eea5192ebff330b",
+            ),
+        ),
+    ];
+
+    #[test]
+    fn expand_message() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA512-256";
+
+        for (msg, (short, long)) in messages().iter().zip(EXPAND_XMD) {
+            assert_eq!(expand_message_xmd(msg, dst, 0x20), unhex(short));
+            assert_eq!(expand_message_xmd(msg, dst, 0x80), unhex(long));
+        }
+    }
+
+    /// RFC 9380, appendix K.1: `expand_message_xmd` with SHA-256.
+    #[test]
+    fn expand_message_sha256() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let sha256 = |x: &[u8]| Sha256::new(x).result().to_vec();
+
+        for (msg, expected) in [
+            (
+                &b""[..],
+                "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+            ),
+            (
+                b"abc",
+                "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+            ),
+            (
+                b"abcdef0123456789",
+                "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+            ),
+        ] {
+            assert_eq!(
+                expand_message_xmd_with(sha256, 64, 32, msg, dst, 0x20),
+                unhex(expected)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "too long")]
+    fn expand_message_too_long() {
+        // 255 blocks of SHA-256 are 8160 bytes.
+        let sha256 = |x: &[u8]| Sha256::new(x).result().to_vec();
+        expand_message_xmd_with(sha256, 64, 32, b"", b"DST", 8161);
+    }
+
+    #[test]
+    fn edwards25519_ro() {
+        let dst = b"QUUX-V01-CS02-with-edwards25519_XMD:SHA-512_ELL2_RO_";
+
+        for (msg, (x, y)) in messages().iter().zip(EDWARDS25519_RO) {
+            assert_eq!(
+                hash_to_edwards25519(msg, dst),
+                EdwardsPoint::from_affine(fe(x), fe(y))
+            );
+        }
+    }
+
+    #[test]
+    fn curve25519_ro() {
+        let dst = b"QUUX-V01-CS02-with-curve25519_XMD:SHA-512_ELL2_RO_";
+
+        for (msg, (x, y)) in messages().iter().zip(CURVE25519_RO) {
+            let (u, v) = edwards_to_montgomery(&hash_to_edwards25519(msg, dst));
+
+            assert!(u.ct_eq(&fe(x)) && v.ct_eq(&fe(y)));
+            assert_eq!(hash_to_curve25519(msg, dst), fe(x).to_bytes());
+        }
+    }
+
+    #[test]
+    fn representative_round_trip() {
+        let mut found = 0;
+
+        for i in 1..=32u8 {
+            let point = EdwardsPoint::mul_base(&Scalar::from_le_bytes(&[i; 32]));
+
+            if let Some(r) = representative(&point) {
+                assert_eq!(from_representative(&r), point);
+                found += 1;
+            }
+        }
+
+        assert!(found > 0);
+    }
+
+    #[test]
+    fn low_order() {
+        assert_eq!(
+            LOW_ORDER.compress(),
+            unhex_array("c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a")
+        );
+        assert_ne!(LOW_ORDER.mul_bits(&[4], 3), EdwardsPoint::IDENTITY);
+        assert_eq!(LOW_ORDER.mul_bits(&[8], 4), EdwardsPoint::IDENTITY);
+    }
+
+    #[test]
+    fn representable_keys() {
+        let mut torsion = false;
+
+        for _ in 0..16 {
+            let (secret, public, r) = generate_representable();
+            assert_eq!(x25519_from_representative(&r), public);
+
+            let peer = SecretKey::generate();
+            let shared = secret.diffie_hellman(&peer.public_key());
+            assert!(shared.is_some());
+            assert_eq!(shared, peer.diffie_hellman(&public));
+
+            torsion |= public != secret.public_key();
+        }
+
+        // Each key has a 1/8 chance of no low-order component.
+        assert!(torsion);
+    }
+
+    #[test]
+    fn x25519_representative_round_trip() {
+        let mut found = 0;
+
+        for i in 1..=32u8 {
+            let public = SecretKey::from_bytes([i; 32]).public_key();
+
+            if let Some(r) = x25519_representative(&public) {
+                assert_eq!(x25519_from_representative(&r), public);
+                found += 1;
+            }
+        }
+
+        assert!(found > 0);
+    }
+}
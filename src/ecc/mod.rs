@@ -1 +1,18 @@
-mod curve25519;
+pub mod bip340;
+pub mod curve25519;
+pub mod curve448;
+pub mod ecdsa;
+pub mod ed25519;
+pub mod ed448;
+pub mod edwards25519;
+pub mod edwards448;
+pub mod frost;
+pub mod group;
+pub mod hash_to_curve;
+pub mod modular;
+pub mod musig2;
+pub mod p256;
+pub mod p384;
+pub mod ristretto255;
+pub mod secp256k1;
+pub mod weierstrass;
@@ -0,0 +1,414 @@
+//! # Modular arithmetic
+//! Constant-time arithmetic modulo a fixed odd modulus of `N` 64-bit limbs,
+//! with elements kept in Montgomery form. Used for scalars modulo group
+//! orders and for the fields of curves without a dedicated implementation.
+//!
+//! Secret-dependent work never branches: additions, subtractions and the
+//! final Montgomery reduction subtract the modulus under a mask. Only
+//! [`Residue::pow`] depends on its exponent, which must therefore be public.
+
+use std::{
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// A fixed odd modulus `P`, as little-endian 64-bit limbs.
+pub trait Modulus<const N: usize>: Copy + Debug {
+    const P: [u64; N];
+
+    /// `-P^-1 mod 2^64`
+    const INV: u64 = neg_inv(Self::P[0]);
+    /// `R mod P`, with `R = 2^(64 N)`
+    const R1: [u64; N] = pow2_mod(64 * N, &Self::P);
+    /// `R^2 mod P`
+    const R2: [u64; N] = pow2_mod(128 * N, &Self::P);
+}
+
+/// An integer modulo `M::P`, stored as `x * R mod P`.
+#[derive(Clone, Copy)]
+pub struct Residue<M: Modulus<N>, const N: usize> {
+    limbs: [u64; N],
+    _m: PhantomData<M>,
+}
+
+impl<M: Modulus<N>, const N: usize> Debug for Residue<M, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Residue(0x")?;
+
+        for limb in self.to_limbs().iter().rev() {
+            write!(f, "{limb:016x}")?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+impl<M: Modulus<N>, const N: usize> Residue<M, N> {
+    pub const ZERO: Self = Residue {
+        limbs: [0; N],
+        _m: PhantomData,
+    };
+    pub const ONE: Self = Residue {
+        limbs: M::R1,
+        _m: PhantomData,
+    };
+
+    /// Any `N` limb integer, reduced modulo `P`.
+    pub fn from_limbs(limbs: [u64; N]) -> Self {
+        Residue {
+            limbs: mont_mul::<M, N>(&limbs, &M::R2),
+            _m: PhantomData,
+        }
+    }
+
+    pub fn from_u64(x: u64) -> Self {
+        let mut limbs = [0; N];
+        limbs[0] = x;
+
+        Self::from_limbs(limbs)
+    }
+
+    /// A little-endian integer of any length, reduced modulo `P`.
+    pub fn from_le_bytes(bytes: &[u8]) -> Self {
+        let r = Residue::<M, N> {
+            limbs: M::R2,
+            _m: PhantomData,
+        };
+
+        // Horner's rule in base R, most significant chunk first.
+        bytes.chunks(8 * N).rev().fold(Self::ZERO, |acc, chunk| {
+            acc * r + Self::from_limbs(limbs(chunk))
+        })
+    }
+
+    /// A big-endian integer of any length, reduced modulo `P`.
+    pub fn from_be_bytes(bytes: &[u8]) -> Self {
+        let mut le = bytes.to_vec();
+        le.reverse();
+
+        Self::from_le_bytes(&le)
+    }
+
+    /// A little-endian integer that must be below `P`.
+    pub fn from_canonical_le_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() > 8 * N {
+            return None;
+        }
+
+        let x = limbs(bytes);
+        let (_, borrow) = sub_limbs(&x, &M::P);
+
+        (borrow == 1).then(|| Self::from_limbs(x))
+    }
+
+    /// A big-endian integer that must be below `P`.
+    pub fn from_canonical_be_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut le = bytes.to_vec();
+        le.reverse();
+
+        Self::from_canonical_le_bytes(&le)
+    }
+
+    /// The canonical value, as little-endian limbs.
+    pub fn to_limbs(&self) -> [u64; N] {
+        let mut one = [0; N];
+        one[0] = 1;
+
+        mont_mul::<M, N>(&self.limbs, &one)
+    }
+
+    /// The canonical value, as `8 N` little-endian bytes.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        self.to_limbs()
+            .iter()
+            .flat_map(|limb| limb.to_le_bytes())
+            .collect()
+    }
+
+    /// The canonical value, as `8 N` big-endian bytes.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.to_le_bytes();
+        bytes.reverse();
+
+        bytes
+    }
+
+    pub fn square(&self) -> Self {
+        *self * *self
+    }
+
+    /// `self^e` for a public exponent `e`.
+    pub fn pow(&self, e: &[u64; N]) -> Self {
+        let mut res = Self::ONE;
+
+        for i in (0..64 * N).rev() {
+            res = res.square();
+
+            if (e[i / 64] >> (i % 64)) & 1 == 1 {
+                res = res * *self;
+            }
+        }
+
+        res
+    }
+
+    /// `self^(P - 2)`, which is `1 / self` for prime `P` and non-zero `self`,
+    /// and 0 for 0.
+    pub fn invert(&self) -> Self {
+        let mut two = [0; N];
+        two[0] = 2;
+
+        self.pow(&sub_limbs(&M::P, &two).0)
+    }
+
+    /// A square root, if there is one. Only for primes `P = 3 mod 4`, where
+    /// it is `self^((P + 1) / 4)`.
+    pub fn sqrt(&self) -> Option<Self> {
+        assert_eq!(M::P[0] & 3, 3, "sqrt needs P = 3 mod 4");
+
+        // (P + 1) / 4 = (P >> 2) + 1, as the two low bits of P are set.
+        let e: [u64; N] =
+            std::array::from_fn(|i| M::P[i] >> 2 | M::P.get(i + 1).map_or(0, |x| x << 62));
+
+        let mut one = [0; N];
+        one[0] = 1;
+        let (e, _) = add_limbs(&e, &one);
+
+        let root = self.pow(&e);
+
+        root.square().ct_eq(self).then_some(root)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.iter().fold(0, |acc, x| acc | x) == 0
+    }
+
+    /// Whether the canonical value is odd.
+    pub fn is_odd(&self) -> bool {
+        self.to_limbs()[0] & 1 == 1
+    }
+
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        let diff = self
+            .limbs
+            .iter()
+            .zip(other.limbs)
+            .fold(0, |acc, (a, b)| acc | (a ^ b));
+
+        diff == 0
+    }
+
+    /// `b` if `choice` else `a`, without branching on `choice`.
+    pub fn select(a: &Self, b: &Self, choice: bool) -> Self {
+        let mask = (choice as u64).wrapping_neg();
+
+        Residue {
+            limbs: std::array::from_fn(|i| a.limbs[i] ^ (mask & (a.limbs[i] ^ b.limbs[i]))),
+            _m: PhantomData,
+        }
+    }
+}
+
+impl<M: Modulus<N>, const N: usize> PartialEq for Residue<M, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl<M: Modulus<N>, const N: usize> Eq for Residue<M, N> {}
+
+impl<M: Modulus<N>, const N: usize> Add for Residue<M, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let (sum, carry) = add_limbs(&self.limbs, &rhs.limbs);
+        let (diff, borrow) = sub_limbs(&sum, &M::P);
+
+        // Keep the sum only if it is below P, i.e. it neither overflowed nor
+        // let the subtraction succeed.
+        let keep = ((carry ^ 1) & borrow).wrapping_neg();
+
+        Residue {
+            limbs: std::array::from_fn(|i| diff[i] ^ (keep & (diff[i] ^ sum[i]))),
+            _m: PhantomData,
+        }
+    }
+}
+
+impl<M: Modulus<N>, const N: usize> Sub for Residue<M, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let (diff, borrow) = sub_limbs(&self.limbs, &rhs.limbs);
+        let mask = borrow.wrapping_neg();
+
+        let p = M::P.map(|x| x & mask);
+
+        Residue {
+            limbs: add_limbs(&diff, &p).0,
+            _m: PhantomData,
+        }
+    }
+}
+
+impl<M: Modulus<N>, const N: usize> Neg for Residue<M, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl<M: Modulus<N>, const N: usize> Mul for Residue<M, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Residue {
+            limbs: mont_mul::<M, N>(&self.limbs, &rhs.limbs),
+            _m: PhantomData,
+        }
+    }
+}
+
+/// Up to `8 N` little-endian bytes as limbs.
+fn limbs<const N: usize>(bytes: &[u8]) -> [u64; N] {
+    let mut padded = vec![0u8; 8 * N];
+    padded[..bytes.len()].copy_from_slice(bytes);
+
+    std::array::from_fn(|i| u64::from_le_bytes(padded[8 * i..8 * i + 8].try_into().unwrap()))
+}
+
+fn add_limbs<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], u64) {
+    let mut res = [0; N];
+    let mut carry = 0;
+
+    for i in 0..N {
+        let t = a[i] as u128 + b[i] as u128 + carry as u128;
+        res[i] = t as u64;
+        carry = (t >> 64) as u64;
+    }
+
+    (res, carry)
+}
+
+fn sub_limbs<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], u64) {
+    let mut res = [0; N];
+    let mut borrow = 0;
+
+    for i in 0..N {
+        let t = (a[i] as u128).wrapping_sub(b[i] as u128 + borrow as u128);
+        res[i] = t as u64;
+        borrow = (t >> 127) as u64;
+    }
+
+    (res, borrow)
+}
+
+/// Montgomery multiplication `a * b / R mod P` (CIOS), for `a * b < R P`.
+fn mont_mul<M: Modulus<N>, const N: usize>(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+    // t holds the low N limbs of the accumulator, t_n the limb above them.
+    let mut t = [0u64; N];
+    let mut t_n = 0u64;
+
+    for b_i in b {
+        let mut c = 0u128;
+
+        for j in 0..N {
+            let x = t[j] as u128 + a[j] as u128 * *b_i as u128 + c;
+            t[j] = x as u64;
+            c = x >> 64;
+        }
+
+        let x = t_n as u128 + c;
+        t_n = x as u64;
+        let t_n1 = (x >> 64) as u64;
+
+        let m = t[0].wrapping_mul(M::INV);
+        let mut c = (t[0] as u128 + m as u128 * M::P[0] as u128) >> 64;
+
+        for j in 1..N {
+            let x = t[j] as u128 + m as u128 * M::P[j] as u128 + c;
+            t[j - 1] = x as u64;
+            c = x >> 64;
+        }
+
+        let x = t_n as u128 + c;
+        t[N - 1] = x as u64;
+        t_n = t_n1 + (x >> 64) as u64;
+    }
+
+    let (diff, borrow) = sub_limbs(&t, &M::P);
+
+    // t < 2P: subtract P unless that underflows with no carry limb.
+    let keep = ((t_n ^ 1) & borrow).wrapping_neg();
+
+    std::array::from_fn(|i| diff[i] ^ (keep & (diff[i] ^ t[i])))
+}
+
+const fn neg_inv(p0: u64) -> u64 {
+    // Newton's iteration doubles the number of correct low bits each time.
+    let mut inv = 1u64;
+    let mut i = 0;
+
+    while i < 6 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(p0.wrapping_mul(inv)));
+        i += 1;
+    }
+
+    inv.wrapping_neg()
+}
+
+/// `2^k mod p`, by doubling 1 `k` times.
+const fn pow2_mod<const N: usize>(k: usize, p: &[u64; N]) -> [u64; N] {
+    let mut x = [0u64; N];
+    x[0] = 1;
+
+    let mut i = 0;
+
+    while i < k {
+        let mut carry = 0;
+        let mut j = 0;
+
+        while j < N {
+            let top = x[j] >> 63;
+            x[j] = x[j] << 1 | carry;
+            carry = top;
+            j += 1;
+        }
+
+        // Subtract p if the doubled value overflowed or is >= p.
+        let mut ge = carry == 1;
+
+        if !ge {
+            ge = true;
+            let mut j = N;
+
+            while j > 0 {
+                j -= 1;
+
+                if x[j] != p[j] {
+                    ge = x[j] > p[j];
+                    break;
+                }
+            }
+        }
+
+        if ge {
+            let mut borrow = 0u64;
+            let mut j = 0;
+
+            while j < N {
+                let (d, b1) = x[j].overflowing_sub(p[j]);
+                let (d, b2) = d.overflowing_sub(borrow);
+                x[j] = d;
+                borrow = (b1 | b2) as u64;
+                j += 1;
+            }
+        }
+
+        i += 1;
+    }
+
+    x
+}
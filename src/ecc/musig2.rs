@@ -0,0 +1,1030 @@
+//! # MuSig2 (BIP-327)
+//! n-of-n multi-signatures over [`super::secp256k1`] whose result is an
+//! ordinary [`super::bip340`] signature under the aggregate key.
+//!
+//! A signing round goes:
+//! 1. [`KeyAggContext::new`] over the signers' plain public keys, plus any
+//!    tweaks;
+//! 2. [`nonce_gen`] by every signer, who sends the [`PubNonce`] and keeps the
+//!    [`SecNonce`];
+//! 3. [`nonce_agg`] of all public nonces, then a [`Session`] for the message;
+//! 4. [`Session::sign`] by every signer and [`Session::aggregate`] of the
+//!    partial signatures.
+//!
+//! Signing twice with the same secret nonce leaks the secret key. A
+//! [`SecNonce`] can't be copied, cloned or serialized, and signing consumes
+//! it, so the compiler enforces that each one is used at most once.
+
+use super::{
+    bip340,
+    secp256k1::{ProjectivePoint, PublicKey, Scalar, SecretKey},
+};
+use crate::{rng, sha::sha256::Sha256};
+
+/// The aggregate of a list of public keys, with the tweaks applied so far.
+#[derive(Debug, Clone)]
+pub struct KeyAggContext {
+    keys: Vec<[u8; 33]>,
+    list_hash: [u8; 32],
+    second_key: [u8; 33],
+    q: ProjectivePoint,
+    gacc: Scalar,
+    tacc: Scalar,
+}
+
+/// A signer's secret nonce pair `(k1, k2)`, bound to its public key.
+/// Deliberately neither `Clone` nor `Copy`.
+pub struct SecNonce {
+    k1: Scalar,
+    k2: Scalar,
+    pk: [u8; 33],
+}
+
+/// A signer's public nonce, the compressed points `[k1] G || [k2] G`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PubNonce(pub [u8; 66]);
+
+/// The sums `R1 || R2` of all public nonces, where the identity is encoded as
+/// 33 zero bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AggNonce(pub [u8; 66]);
+
+/// A signer's contribution `s_i` to the final signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialSignature(pub [u8; 32]);
+
+/// The values every signer derives from the aggregate nonce, the keys and
+/// the message.
+#[derive(Debug, Clone)]
+pub struct Session {
+    ctx: KeyAggContext,
+    b: Scalar,
+    r: ProjectivePoint,
+    e: Scalar,
+}
+
+impl KeyAggContext {
+    /// `KeyAgg` of `keys` in the given order, see [`sort_keys`]. `None` if
+    /// the aggregate is the identity.
+    pub fn new(keys: &[PublicKey]) -> Option<KeyAggContext> {
+        let keys = keys
+            .iter()
+            .map(|pk| pk.to_sec1(true).try_into().unwrap())
+            .collect::<Vec<[u8; 33]>>();
+
+        let list_hash = tagged_hash(b"KeyAgg list", &[&keys.concat()]);
+        let second_key = keys
+            .iter()
+            .find(|pk| **pk != keys[0])
+            .copied()
+            .unwrap_or([0; 33]);
+
+        let mut ctx = KeyAggContext {
+            keys,
+            list_hash,
+            second_key,
+            q: ProjectivePoint::IDENTITY,
+            gacc: Scalar::ONE,
+            tacc: Scalar::ZERO,
+        };
+
+        let scalars = ctx
+            .keys
+            .iter()
+            .map(|pk| ctx.coefficient(pk))
+            .collect::<Vec<_>>();
+        let points = ctx
+            .keys
+            .iter()
+            .map(|pk| ProjectivePoint::from_sec1(pk))
+            .collect::<Option<Vec<_>>>()?;
+
+        ctx.q = ProjectivePoint::vartime_multiscalar_mul(&scalars, &points);
+
+        (!ctx.q.is_identity()).then_some(ctx)
+    }
+
+    /// Add `[t] G` to the aggregate key, after negating it if `xonly` and its
+    /// y is odd: an x-only tweak as in Taproot, or a plain one as in BIP-32.
+    /// `None` if `tweak >= n` or the result is the identity.
+    pub fn apply_tweak(&self, tweak: &[u8; 32], xonly: bool) -> Option<KeyAggContext> {
+        let t = Scalar::from_canonical_be_bytes(tweak)?;
+        let negate = xonly && !has_even_y(&self.q);
+
+        let q = ProjectivePoint::select(&self.q, &-self.q, negate) + ProjectivePoint::mul_base(&t);
+
+        let g = Scalar::select(&Scalar::ONE, &-Scalar::ONE, negate);
+
+        (!q.is_identity()).then(|| KeyAggContext {
+            q,
+            gacc: g * self.gacc,
+            tacc: t + g * self.tacc,
+            ..self.clone()
+        })
+    }
+
+    /// The aggregate key as a BIP-340 key, under which the final signature
+    /// verifies.
+    pub fn xonly_public_key(&self) -> bip340::VerifyingKey {
+        bip340::VerifyingKey::from_bytes(&xbytes(&self.q)).unwrap()
+    }
+
+    /// The aggregate key in compressed SEC1 form, for further plain tweaking.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from_point(self.q).unwrap()
+    }
+
+    /// `a_i = hash_coefficient(L || pk_i)`, except `1` for the second
+    /// distinct key, which saves one scalar multiplication.
+    fn coefficient(&self, pk: &[u8; 33]) -> Scalar {
+        if *pk == self.second_key {
+            return Scalar::ONE;
+        }
+
+        Scalar::from_be_bytes(&tagged_hash(b"KeyAgg coefficient", &[&self.list_hash, pk]))
+    }
+}
+
+/// Sort `keys` by their compressed encodings, the `KeySort` of BIP-327, so
+/// that all signers aggregate them in the same order.
+pub fn sort_keys(keys: &mut [PublicKey]) {
+    keys.sort_by_key(|pk| pk.to_sec1(true));
+}
+
+/// `NonceGen` with 32 bytes from the operating system's CSPRNG. Every
+/// optional input that is known should be passed: they protect against a
+/// broken CSPRNG, but never replace it.
+pub fn nonce_gen(
+    sk: Option<&SecretKey>,
+    pk: &PublicKey,
+    aggpk: Option<&bip340::VerifyingKey>,
+    msg: Option<&[u8]>,
+    extra_in: &[u8],
+) -> (SecNonce, PubNonce) {
+    nonce_gen_with_rand(rng::bytes(), sk, pk, aggpk, msg, extra_in)
+}
+
+/// `NonceGen` with the given `rand'`, which must be fresh and uniformly
+/// random for every call.
+fn nonce_gen_with_rand(
+    mut rand: [u8; 32],
+    sk: Option<&SecretKey>,
+    pk: &PublicKey,
+    aggpk: Option<&bip340::VerifyingKey>,
+    msg: Option<&[u8]>,
+    extra_in: &[u8],
+) -> (SecNonce, PubNonce) {
+    let pk: [u8; 33] = pk.to_sec1(true).try_into().unwrap();
+
+    if let Some(sk) = sk {
+        let aux = tagged_hash(b"MuSig/aux", &[&rand]);
+        for ((r_i, sk_i), aux_i) in rand.iter_mut().zip(sk.to_bytes()).zip(aux) {
+            *r_i = sk_i ^ aux_i;
+        }
+    }
+
+    let aggpk = aggpk.map(|key| key.to_bytes().to_vec()).unwrap_or_default();
+    let msg_prefixed = match msg {
+        Some(msg) => [&[1], &(msg.len() as u64).to_be_bytes()[..], msg].concat(),
+        None => vec![0],
+    };
+
+    let k = |i: u8| {
+        Scalar::from_be_bytes(&tagged_hash(
+            b"MuSig/nonce",
+            &[
+                &rand,
+                &[pk.len() as u8],
+                &pk,
+                &[aggpk.len() as u8],
+                &aggpk,
+                &msg_prefixed,
+                &(extra_in.len() as u32).to_be_bytes(),
+                extra_in,
+                &[i],
+            ],
+        ))
+    };
+    let (k1, k2) = (k(0), k(1));
+
+    // Only hit with negligible probability.
+    assert!(!k1.is_zero() && !k2.is_zero(), "nonce is zero");
+
+    let mut pubnonce = [0u8; 66];
+    pubnonce[..33].copy_from_slice(&ProjectivePoint::mul_base(&k1).to_sec1(true));
+    pubnonce[33..].copy_from_slice(&ProjectivePoint::mul_base(&k2).to_sec1(true));
+
+    (SecNonce { k1, k2, pk }, PubNonce(pubnonce))
+}
+
+/// `NonceAgg`: the sums of the first and of the second points of all
+/// `pubnonces`. `None` if any of them is not a valid encoding.
+pub fn nonce_agg(pubnonces: &[PubNonce]) -> Option<AggNonce> {
+    let mut r1 = ProjectivePoint::IDENTITY;
+    let mut r2 = ProjectivePoint::IDENTITY;
+
+    for nonce in pubnonces {
+        r1 = r1 + ProjectivePoint::from_sec1(&nonce.0[..33])?;
+        r2 = r2 + ProjectivePoint::from_sec1(&nonce.0[33..])?;
+    }
+
+    let mut aggnonce = [0u8; 66];
+    aggnonce[..33].copy_from_slice(&cbytes_ext(&r1));
+    aggnonce[33..].copy_from_slice(&cbytes_ext(&r2));
+
+    Some(AggNonce(aggnonce))
+}
+
+impl Session {
+    /// `None` if `aggnonce` is not a valid encoding.
+    pub fn new(aggnonce: &AggNonce, ctx: &KeyAggContext, msg: &[u8]) -> Option<Session> {
+        let q = xbytes(&ctx.q);
+
+        let b = Scalar::from_be_bytes(&tagged_hash(b"MuSig/noncecoef", &[&aggnonce.0, &q, msg]));
+
+        let r1 = cpoint_ext(&aggnonce.0[..33])?;
+        let r2 = cpoint_ext(&aggnonce.0[33..])?;
+        let r = r1 + r2.mul(&b);
+
+        // An identity nonce can only come from malicious signers; falling
+        // back to G keeps the protocol going until they are identified.
+        let r = ProjectivePoint::select(&r, &ProjectivePoint::generator(), r.is_identity());
+
+        Some(Session {
+            ctx: ctx.clone(),
+            b,
+            e: bip340::challenge(&xbytes(&r), &q, msg),
+            r,
+        })
+    }
+
+    /// The partial signature `k1 + b k2 + e a d` of the holder of `sk`,
+    /// consuming `secnonce`. `None` if `secnonce` belongs to another key or
+    /// that key is not among the aggregated ones.
+    pub fn sign(&self, secnonce: SecNonce, sk: &SecretKey) -> Option<PartialSignature> {
+        let SecNonce { k1, k2, pk } = secnonce;
+
+        let d = Scalar::from_be_bytes(&sk.to_bytes());
+        if sk.public_key().to_sec1(true) != pk || !self.ctx.keys.contains(&pk) {
+            return None;
+        }
+
+        let a = self.ctx.coefficient(&pk);
+        let g = Scalar::select(&Scalar::ONE, &-Scalar::ONE, !has_even_y(&self.ctx.q));
+        let d = g * self.ctx.gacc * d;
+
+        let negate = !has_even_y(&self.r);
+        let k1 = Scalar::select(&k1, &-k1, negate);
+        let k2 = Scalar::select(&k2, &-k2, negate);
+
+        let s = k1 + self.b * k2 + self.e * a * d;
+
+        Some(PartialSignature(s.to_be_bytes().try_into().unwrap()))
+    }
+
+    /// `PartialSigVerify`: whether `psig` is the contribution of the signer
+    /// with key `pk` and public nonce `pubnonce`, so that a failing
+    /// aggregate can be blamed on someone.
+    pub fn verify_partial(
+        &self,
+        psig: &PartialSignature,
+        pubnonce: &PubNonce,
+        pk: &PublicKey,
+    ) -> bool {
+        let Some(s) = Scalar::from_canonical_be_bytes(&psig.0) else {
+            return false;
+        };
+        let (Some(r1), Some(r2)) = (
+            ProjectivePoint::from_sec1(&pubnonce.0[..33]),
+            ProjectivePoint::from_sec1(&pubnonce.0[33..]),
+        ) else {
+            return false;
+        };
+
+        let pk_bytes: [u8; 33] = pk.to_sec1(true).try_into().unwrap();
+        if !self.ctx.keys.contains(&pk_bytes) {
+            return false;
+        }
+
+        let r = r1 + r2.mul(&self.b);
+        let r = ProjectivePoint::select(&r, &-r, !has_even_y(&self.r));
+
+        let g = Scalar::select(&Scalar::ONE, &-Scalar::ONE, !has_even_y(&self.ctx.q));
+        let a = self.ctx.coefficient(&pk_bytes);
+
+        ProjectivePoint::mul_base(&s) == r + pk.to_point().mul(&(self.e * a * g * self.ctx.gacc))
+    }
+
+    /// `PartialSigAgg`: the BIP-340 signature `R || sum s_i + e g tacc`.
+    /// `None` if a partial signature is out of range; a wrong one only shows
+    /// when the result fails to verify.
+    pub fn aggregate(&self, psigs: &[PartialSignature]) -> Option<bip340::Signature> {
+        let g = Scalar::select(&Scalar::ONE, &-Scalar::ONE, !has_even_y(&self.ctx.q));
+
+        let mut s = self.e * g * self.ctx.tacc;
+        for psig in psigs {
+            s = s + Scalar::from_canonical_be_bytes(&psig.0)?;
+        }
+
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&xbytes(&self.r));
+        sig[32..].copy_from_slice(&s.to_be_bytes());
+
+        Some(bip340::Signature(sig))
+    }
+}
+
+fn has_even_y(point: &ProjectivePoint) -> bool {
+    point.to_affine().is_some_and(|(_, y)| !y.is_odd())
+}
+
+/// The x-coordinate of a point other than the identity.
+fn xbytes(point: &ProjectivePoint) -> [u8; 32] {
+    let (x, _) = point.to_affine().unwrap();
+
+    x.to_be_bytes().try_into().unwrap()
+}
+
+/// The compressed encoding, or 33 zero bytes for the identity.
+fn cbytes_ext(point: &ProjectivePoint) -> [u8; 33] {
+    if point.is_identity() {
+        return [0; 33];
+    }
+
+    point.to_sec1(true).try_into().unwrap()
+}
+
+/// Inverse of [`cbytes_ext`].
+fn cpoint_ext(bytes: &[u8]) -> Option<ProjectivePoint> {
+    if bytes.iter().all(|b| *b == 0) {
+        return Some(ProjectivePoint::IDENTITY);
+    }
+
+    ProjectivePoint::from_sec1(bytes)
+}
+
+/// The BIP-340 tagged hash of the concatenation of `parts`.
+fn tagged_hash(tag: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut sha = Sha256::tagged(tag);
+
+    for part in parts {
+        sha.update(part);
+    }
+
+    sha.result()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{unhex, unhex_array};
+
+    fn pk(hex: &str) -> Option<PublicKey> {
+        PublicKey::from_sec1(&unhex(hex))
+    }
+
+    fn pubnonce(hex: &str) -> PubNonce {
+        PubNonce(unhex_array(hex))
+    }
+
+    /// `KeyAgg` of `keys[i]` for `i` in `indices`, then the tweaks.
+    fn key_agg(keys: &[&str], indices: &[usize], tweaks: &[(&str, bool)]) -> KeyAggContext {
+        let keys = indices
+            .iter()
+            .map(|i| pk(keys[*i]).unwrap())
+            .collect::<Vec<_>>();
+
+        tweaks
+            .iter()
+            .fold(KeyAggContext::new(&keys).unwrap(), |ctx, (tweak, xonly)| {
+                ctx.apply_tweak(&unhex_array(tweak), *xonly).unwrap()
+            })
+    }
+
+    /// The secret nonce serialized as `k1 || k2 || pk`.
+    fn secnonce_bytes(secnonce: &SecNonce) -> Vec<u8> {
+        [
+            &secnonce.k1.to_be_bytes()[..],
+            &secnonce.k2.to_be_bytes(),
+            &secnonce.pk,
+        ]
+        .concat()
+    }
+
+    fn secnonce(hex: &str) -> SecNonce {
+        let bytes = unhex(hex);
+
+        SecNonce {
+            k1: Scalar::from_be_bytes(&bytes[..32]),
+            k2: Scalar::from_be_bytes(&bytes[32..64]),
+            pk: bytes[64..].try_into().unwrap(),
+        }
+    }
+
+    /// key_agg_vectors.json of BIP-327.
+    #[test]
+    fn key_agg_vectors() {
+        let keys = [
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+            "020000000000000000000000000000000000000000000000000000000000000005",
+            "02FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+            "04F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+        ];
+        let tweaks = [
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+            "252E4BD67410A76CDF933D30EAA1608214037F1B105A013ECCD3C5C184A6110B",
+        ];
+
+        for (indices, expected) in [
+            (
+                &[0, 1, 2][..],
+                "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C",
+            ),
+            (
+                &[2, 1, 0],
+                "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B",
+            ),
+            (
+                &[0, 0, 0],
+                "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935",
+            ),
+            (
+                &[0, 0, 1, 1],
+                "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E",
+            ),
+        ] {
+            assert_eq!(
+                key_agg(&keys, indices, &[]).xonly_public_key().to_bytes(),
+                unhex_array(expected)
+            );
+        }
+
+        // Not on the curve, x >= p, and a prefix other than 2 or 3.
+        for i in [3, 4, 5] {
+            assert_eq!(pk(keys[i]), None);
+        }
+
+        let ctx = key_agg(&keys, &[0, 1], &[]);
+        assert!(ctx.apply_tweak(&unhex_array(tweaks[0]), true).is_none());
+
+        let ctx = key_agg(&keys, &[6], &[]);
+        assert!(ctx.apply_tweak(&unhex_array(tweaks[1]), false).is_none());
+    }
+
+    /// nonce_gen_vectors.json of BIP-327.
+    #[test]
+    fn nonce_gen_vectors() {
+        let rand = unhex_array("0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F");
+        let sk = SecretKey::from_bytes(&[0x02; 32]).unwrap();
+        let key = pk("024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766").unwrap();
+        let aggpk = bip340::VerifyingKey::from_bytes(&[0x07; 32]);
+        let msg_38 = [0x26; 38];
+
+        assert_eq!(sk.public_key(), key);
+
+        for (msg, expected_secnonce, expected_pubnonce) in [
+            (
+                &[0x01; 32][..],
+                concat!(
+                    "B114E502BEAA4E301DD08A50264172C84E41650E6CB726B410C0694D59EFFB64",
+                    "95B5CAF28D045B973D63E3C99A44B807BDE375FD6CB39E46DC4A511708D0E9D2",
+                    "024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766",
+                ),
+                concat!(
+                    "02F7BE7089E8376EB355272368766B17E88E7DB72047D05E56AA881EA52B3B35DF",
+                    "02C29C8046FDD0DED4C7E55869137200FBDBFE2EB654267B6D7013602CAED3115A",
+                ),
+            ),
+            (
+                &[],
+                concat!(
+                    "E862B068500320088138468D47E0E6F147E01B6024244AE45EAC40ACE5929B9F",
+                    "0789E051170B9E705D0B9EB49049A323BBBBB206D8E05C19F46C6228742AA7A9",
+                    "024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766",
+                ),
+                concat!(
+                    "023034FA5E2679F01EE66E12225882A7A48CC66719B1B9D3B6C4DBD743EFEDA2C5",
+                    "03F3FD6F01EB3A8E9CB315D73F1F3D287CAFBB44AB321153C6287F407600205109",
+                ),
+            ),
+            (
+                &msg_38,
+                concat!(
+                    "3221975ACBDEA6820EABF02A02B7F27D3A8EF68EE42787B88CBEFD9AA06AF363",
+                    "2EE85B1A61D8EF31126D4663A00DD96E9D1D4959E72D70FE5EBB6E7696EBA66F",
+                    "024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766",
+                ),
+                concat!(
+                    "02E5BBC21C69270F59BD634FCBFA281BE9D76601295345112C58954625BF23793A",
+                    "021307511C79F95D38ACACFF1B4DA98228B77E65AA216AD075E9673286EFB4EAF3",
+                ),
+            ),
+        ] {
+            let (secnonce, pubnonce) = nonce_gen_with_rand(
+                rand,
+                Some(&sk),
+                &key,
+                aggpk.as_ref(),
+                Some(msg),
+                &[0x08; 32],
+            );
+
+            assert_eq!(secnonce_bytes(&secnonce), unhex(expected_secnonce));
+            assert_eq!(pubnonce.0.to_vec(), unhex(expected_pubnonce));
+        }
+
+        // Only the required inputs.
+        let key = pk("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9").unwrap();
+        let (secnonce, pubnonce) = nonce_gen_with_rand(rand, None, &key, None, None, &[]);
+
+        assert_eq!(
+            secnonce_bytes(&secnonce),
+            unhex(concat!(
+                "89BDD787D0284E5E4D5FC572E49E316BAB7E21E3B1830DE37DFE80156FA41A6D",
+                "0B17AE8D024C53679699A6FD7944D9C4A366B514BAF43088E0708B1023DD2897",
+                "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            ))
+        );
+        assert_eq!(
+            pubnonce.0.to_vec(),
+            unhex(concat!(
+                "02C96E7CB1E8AA5DAC64D872947914198F607D90ECDE5200DE52978AD5DED63C00",
+                "0299EC5117C2D29EDEE8A2092587C3909BE694D5CFF0667D6C02EA4059F7CD9786",
+            ))
+        );
+    }
+
+    /// nonce_agg_vectors.json of BIP-327.
+    #[test]
+    fn nonce_agg_vectors() {
+        let pnonces = [
+            concat!(
+                "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E666",
+                "03BA47FBC1834437B3212E89A84D8425E7BF12E0245D98262268EBDCB385D50641",
+            ),
+            concat!(
+                "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A6",
+                "0248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833",
+            ),
+            concat!(
+                "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E666",
+                "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            ),
+            concat!(
+                "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A6",
+                "0379BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            ),
+            concat!(
+                "04FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A6",
+                "0248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833",
+            ),
+            concat!(
+                "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A6",
+                "0248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B831",
+            ),
+            concat!(
+                "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A6",
+                "02FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+            ),
+        ];
+        let agg = |indices: &[usize]| {
+            nonce_agg(
+                &indices
+                    .iter()
+                    .map(|i| pubnonce(pnonces[*i]))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        assert_eq!(
+            agg(&[0, 1]),
+            Some(AggNonce(unhex_array(concat!(
+                "035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B",
+                "024725377345BDE0E9C33AF3C43C0A29A9249F2F2956FA8CFEB55C8573D0262DC8",
+            ))))
+        );
+        // The second points sum to the identity, encoded as 33 zero bytes.
+        assert_eq!(
+            agg(&[2, 3]),
+            Some(AggNonce(unhex_array(concat!(
+                "035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B",
+                "000000000000000000000000000000000000000000000000000000000000000000",
+            ))))
+        );
+
+        // A wrong prefix, a second half that is not an x-coordinate on the
+        // curve, and one that exceeds the field size.
+        assert_eq!(agg(&[0, 4]), None);
+        assert_eq!(agg(&[5, 0]), None);
+        assert_eq!(agg(&[6, 0]), None);
+    }
+
+    const SK: &str = "7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671";
+
+    const SECNONCE: &str = concat!(
+        "508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61",
+        "FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F7",
+        "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+    );
+
+    /// The public nonces of the sign_verify and tweak vectors; the first
+    /// belongs to [`SECNONCE`].
+    const PNONCES: [&str; 5] = [
+        concat!(
+            "0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA",
+            "0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        ),
+        concat!(
+            "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        ),
+        concat!(
+            "032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE93",
+            "03E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046",
+        ),
+        concat!(
+            "0237C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA",
+            "0387BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        ),
+        concat!(
+            "020000000000000000000000000000000000000000000000000000000000000009",
+            "0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        ),
+    ];
+
+    const AGGNONCE: &str = concat!(
+        "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61",
+        "037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9",
+    );
+
+    /// sign_verify_vectors.json of BIP-327.
+    #[test]
+    fn sign_verify_vectors() {
+        let keys = [
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661",
+            "020000000000000000000000000000000000000000000000000000000000000007",
+        ];
+        let msgs = [
+            unhex("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF"),
+            vec![],
+            vec![0x26; 38],
+        ];
+        let sk = SecretKey::from_bytes(&unhex(SK)).unwrap();
+
+        assert_eq!(pk(keys[0]), Some(sk.public_key()));
+        assert_eq!(
+            nonce_agg(&[0, 1, 2].map(|i| pubnonce(PNONCES[i]))),
+            Some(AggNonce(unhex_array(AGGNONCE)))
+        );
+
+        for (key_indices, nonce_indices, aggnonce, msg, signer, expected) in [
+            (
+                &[0, 1, 2][..],
+                &[0, 1, 2][..],
+                AGGNONCE,
+                &msgs[0],
+                0,
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+            ),
+            (
+                &[1, 0, 2],
+                &[1, 0, 2],
+                AGGNONCE,
+                &msgs[0],
+                1,
+                "9FF2F7AAA856150CC8819254218D3ADEEB0535269051897724F9DB3789513A52",
+            ),
+            (
+                &[1, 2, 0],
+                &[1, 2, 0],
+                AGGNONCE,
+                &msgs[0],
+                2,
+                "FA23C359F6FAC4E7796BB93BC9F0532A95468C539BA20FF86D7C76ED92227900",
+            ),
+            // Both halves of the aggregate nonce are the identity.
+            (
+                &[0, 1],
+                &[0, 3],
+                concat!(
+                    "000000000000000000000000000000000000000000000000000000000000000000",
+                    "000000000000000000000000000000000000000000000000000000000000000000",
+                ),
+                &msgs[0],
+                0,
+                "AE386064B26105404798F75DE2EB9AF5EDA5387B064B83D049CB7C5E08879531",
+            ),
+            (
+                &[0, 1, 2],
+                &[0, 1, 2],
+                AGGNONCE,
+                &msgs[1],
+                0,
+                "D7D63FFD644CCDA4E62BC2BC0B1D02DD32A1DC3030E155195810231D1037D82D",
+            ),
+            (
+                &[0, 1, 2],
+                &[0, 1, 2],
+                AGGNONCE,
+                &msgs[2],
+                0,
+                "E184351828DA5094A97C79CABDAAA0BFB87608C32E8829A4DF5340A6F243B78C",
+            ),
+        ] {
+            let pnonces = nonce_indices
+                .iter()
+                .map(|i| pubnonce(PNONCES[*i]))
+                .collect::<Vec<_>>();
+            let aggnonce = AggNonce(unhex_array(aggnonce));
+            assert_eq!(nonce_agg(&pnonces), Some(aggnonce));
+
+            let ctx = key_agg(&keys, key_indices, &[]);
+            let session = Session::new(&aggnonce, &ctx, msg).unwrap();
+            let psig = session.sign(secnonce(SECNONCE), &sk).unwrap();
+
+            assert_eq!(psig, PartialSignature(unhex_array(expected)));
+            assert!(session.verify_partial(&psig, &pnonces[signer], &sk.public_key()));
+        }
+
+        // The signer's key is not among the aggregated ones.
+        let ctx = key_agg(&keys, &[1, 2], &[]);
+        let session = Session::new(&AggNonce(unhex_array(AGGNONCE)), &ctx, &msgs[0]).unwrap();
+        assert_eq!(session.sign(secnonce(SECNONCE), &sk), None);
+
+        // Signer 2 provided a key that is not on the curve.
+        assert_eq!(pk(keys[3]), None);
+
+        // Aggregate nonces with a wrong prefix, a second half that is not an
+        // x-coordinate on the curve, and one that exceeds the field size.
+        let ctx = key_agg(&keys, &[1, 2, 0], &[]);
+        for aggnonce in [
+            concat!(
+                "048465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61",
+                "037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9",
+            ),
+            concat!(
+                "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61",
+                "020000000000000000000000000000000000000000000000000000000000000009",
+            ),
+            concat!(
+                "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61",
+                "02FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+            ),
+        ] {
+            assert!(Session::new(&AggNonce(unhex_array(aggnonce)), &ctx, &msgs[0]).is_none());
+        }
+
+        let ctx = key_agg(&keys, &[0, 1, 2], &[]);
+        let pnonces = [0, 1, 2].map(|i| pubnonce(PNONCES[i]));
+        let session = Session::new(&AggNonce(unhex_array(AGGNONCE)), &ctx, &msgs[0]).unwrap();
+        let signer = |i: usize| pk(keys[i]).unwrap();
+
+        for (psig, nonce, key) in [
+            // The negation of the valid partial signature.
+            (
+                "FED54434AD4CFE953FC527DC6A5E5BE8F6234907B7C187559557CE87A0541C46",
+                &pnonces[0],
+                signer(0),
+            ),
+            // The wrong signer.
+            (
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+                &pnonces[1],
+                signer(1),
+            ),
+            // Exceeds the group order.
+            (
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+                &pnonces[0],
+                signer(0),
+            ),
+            // An invalid public nonce.
+            (
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+                &pubnonce(PNONCES[4]),
+                signer(0),
+            ),
+        ] {
+            assert!(!session.verify_partial(&PartialSignature(unhex_array(psig)), nonce, &key));
+        }
+    }
+
+    /// tweak_vectors.json of BIP-327.
+    #[test]
+    fn tweak_vectors() {
+        let keys = [
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        ];
+        let tweaks = [
+            "E8F791FF9225A2AF0102AFFF4A9A723D9612A682A25EBE79802B263CDFCD83BB",
+            "AE2EA797CC0FE72AC5B97B97F3C6957D7E4199A167A58EB08BCAFFDA70AC0455",
+            "F52ECBC565B3D8BEA2DFD5B75A4F457E54369809322E4120831626F290FA87E0",
+            "1969AD73CC177FA0B4FCED6DF1F7BF9907E665FDE9BA196A74FED0A3CF5AEF9D",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+        ];
+        let msg = unhex("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF");
+        let sk = SecretKey::from_bytes(&unhex(SK)).unwrap();
+        let aggnonce = AggNonce(unhex_array(AGGNONCE));
+        let pnonces = [1, 2, 0].map(|i| pubnonce(PNONCES[i]));
+
+        for (applied, expected) in [
+            (
+                &[(0, true)][..],
+                "E28A5C66E61E178C2BA19DB77B6CF9F7E2F0F56C17918CD13135E60CC848FE91",
+            ),
+            (
+                &[(0, false)],
+                "38B0767798252F21BF5702C48028B095428320F73A4B14DB1E25DE58543D2D2D",
+            ),
+            (
+                &[(0, false), (1, true)],
+                "408A0A21C4A0F5DACAF9646AD6EB6FECD7F7A11F03ED1F48DFFF2185BC2C2408",
+            ),
+            (
+                &[(0, false), (1, false), (2, true), (3, true)],
+                "45ABD206E61E3DF2EC9E264A6FEC8292141A633C28586388235541F9ADE75435",
+            ),
+            (
+                &[(0, true), (1, false), (2, true), (3, false)],
+                "B255FDCAC27B40C7CE7848E2D3B7BF5EA0ED756DA81565AC804CCCA3E1D5D239",
+            ),
+        ] {
+            let applied = applied
+                .iter()
+                .map(|(i, xonly)| (tweaks[*i], *xonly))
+                .collect::<Vec<_>>();
+            let ctx = key_agg(&keys, &[1, 2, 0], &applied);
+            let session = Session::new(&aggnonce, &ctx, &msg).unwrap();
+            let psig = session.sign(secnonce(SECNONCE), &sk).unwrap();
+
+            assert_eq!(psig, PartialSignature(unhex_array(expected)));
+            assert!(session.verify_partial(&psig, &pnonces[2], &sk.public_key()));
+        }
+
+        // The tweak exceeds the group order.
+        let ctx = key_agg(&keys, &[1, 2, 0], &[]);
+        assert!(ctx.apply_tweak(&unhex_array(tweaks[4]), false).is_none());
+    }
+
+    /// sig_agg_vectors.json of BIP-327.
+    #[test]
+    fn sig_agg_vectors() {
+        let keys = [
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+            "02D2DC6F5DF7C56ACF38C7FA0AE7A759AE30E19B37359DFDE015872324C7EF6E05",
+            "03C7FB101D97FF930ACD0C6760852EF64E69083DE0B06AC6335724754BB4B0522C",
+            "02352433B21E7E05D3B452B81CAE566E06D2E003ECE16D1074AABA4289E0E3D581",
+        ];
+        let pnonces = [
+            concat!(
+                "036E5EE6E28824029FEA3E8A9DDD2C8483F5AF98F7177C3AF3CB6F47CAF8D94AE9",
+                "02DBA67E4A1F3680826172DA15AFB1A8CA85C7C5CC88900905C8DC8C328511B53E",
+            ),
+            concat!(
+                "03E4F798DA48A76EEC1C9CC5AB7A880FFBA201A5F064E627EC9CB0031D1D58FC51",
+                "03E06180315C5A522B7EC7C08B69DCD721C313C940819296D0A7AB8E8795AC1F00",
+            ),
+            concat!(
+                "02C0068FD25523A31578B8077F24F78F5BD5F2422AFF47C1FADA0F36B3CEB6C7D2",
+                "02098A55D1736AA5FCC21CF0729CCE852575C06C081125144763C2C4C4A05C09B6",
+            ),
+            concat!(
+                "031F5C87DCFBFCF330DEE4311D85E8F1DEA01D87A6F1C14CDFC7E4F1D8C441CFA4",
+                "0277BF176E9F747C34F81B0D9F072B1B404A86F402C2D86CF9EA9E9C69876EA3B9",
+            ),
+            concat!(
+                "023F7042046E0397822C4144A17F8B63D78748696A46C3B9F0A901D296EC3406C3",
+                "02022B0B464292CF9751D699F10980AC764E6F671EFCA15069BBE62B0D1C62522A",
+            ),
+            concat!(
+                "02D97DDA5988461DF58C5897444F116A7C74E5711BF77A9446E27806563F3B6C47",
+                "020CBAD9C363A7737F99FA06B6BE093CEAFF5397316C5AC46915C43767AE867C00",
+            ),
+        ];
+        let tweaks = [
+            "B511DA492182A91B0FFB9A98020D55F260AE86D7ECBD0399C7383D59A5F2AF7C",
+            "A815FE049EE3C5AAB66310477FBC8BCCCAC2F3395F59F921C364ACD78A2F48DC",
+            "75448A87274B056468B977BE06EB1E9F657577B7320B0A3376EA51FD420D18A8",
+        ];
+        let psigs = [
+            "B15D2CD3C3D22B04DAE438CE653F6B4ECF042F42CFDED7C41B64AAF9B4AF53FB",
+            "6193D6AC61B354E9105BBDC8937A3454A6D705B6D57322A5A472A02CE99FCB64",
+            "9A87D3B79EC67228CB97878B76049B15DBD05B8158D17B5B9114D3C226887505",
+            "66F82EA90923689B855D36C6B7E032FB9970301481B99E01CDB4D6AC7C347A15",
+            "4F5AEE41510848A6447DCD1BBC78457EF69024944C87F40250D3EF2C25D33EFE",
+            "DDEF427BBB847CC027BEFF4EDB01038148917832253EBC355FC33F4A8E2FCCE4",
+            "97B890A26C981DA8102D3BC294159D171D72810FDF7C6A691DEF02F0F7AF3FDC",
+            "53FA9E08BA5243CBCB0D797C5EE83BC6728E539EB76C2D0BF0F971EE4E909971",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+        ];
+        let msg = unhex("599C67EA410D005B9DA90817CF03ED3B1C868E4DA4EDF00A5880B0082C237869");
+
+        let session =
+            |aggnonce: &str, nonces: &[usize], keys_: &[usize], applied: &[(usize, bool)]| {
+                let aggnonce = AggNonce(unhex_array(aggnonce));
+                let pubnonces = nonces
+                    .iter()
+                    .map(|i| pubnonce(pnonces[*i]))
+                    .collect::<Vec<_>>();
+                assert_eq!(nonce_agg(&pubnonces), Some(aggnonce));
+
+                let applied = applied
+                    .iter()
+                    .map(|(i, xonly)| (tweaks[*i], *xonly))
+                    .collect::<Vec<_>>();
+                let ctx = key_agg(&keys, keys_, &applied);
+
+                (Session::new(&aggnonce, &ctx, &msg).unwrap(), ctx)
+            };
+        let partial = |indices: &[usize]| {
+            indices
+                .iter()
+                .map(|i| PartialSignature(unhex_array(psigs[*i])))
+                .collect::<Vec<_>>()
+        };
+
+        for (aggnonce, nonces, keys_, applied, psig_indices, expected) in [
+            (
+                concat!(
+                    "0341432722C5CD0268D829C702CF0D1CBCE57033EED201FD335191385227C3210C",
+                    "03D377F2D258B64AADC0E16F26462323D701D286046A2EA93365656AFD9875982B",
+                ),
+                &[0, 1][..],
+                &[0, 1][..],
+                &[][..],
+                &[0, 1][..],
+                concat!(
+                    "041DA22223CE65C92C9A0D6C2CAC828AAF1EEE56304FEC371DDF91EBB2B9EF09",
+                    "12F1038025857FEDEB3FF696F8B99FA4BB2C5812F6095A2E0004EC99CE18DE1E",
+                ),
+            ),
+            (
+                concat!(
+                    "0224AFD36C902084058B51B5D36676BBA4DC97C775873768E58822F87FE437D792",
+                    "028CB15929099EEE2F5DAE404CD39357591BA32E9AF4E162B8D3E7CB5EFE31CB20",
+                ),
+                &[0, 2],
+                &[0, 2],
+                &[],
+                &[2, 3],
+                concat!(
+                    "1069B67EC3D2F3C7C08291ACCB17A9C9B8F2819A52EB5DF8726E17E7D6B52E9F",
+                    "01800260A7E9DAC450F4BE522DE4CE12BA91AEAF2B4279219EF74BE1D286ADD9",
+                ),
+            ),
+            (
+                concat!(
+                    "0208C5C438C710F4F96A61E9FF3C37758814B8C3AE12BFEA0ED2C87FF6954FF186",
+                    "020B1816EA104B4FCA2D304D733E0E19CEAD51303FF6420BFD222335CAA402916D",
+                ),
+                &[0, 3],
+                &[0, 2],
+                &[(0, false)],
+                &[4, 5],
+                concat!(
+                    "5C558E1DCADE86DA0B2F02626A512E30A22CF5255CAEA7EE32C38E9A71A0E914",
+                    "8BA6C0E6EC7683B64220F0298696F1B878CD47B107B81F7188812D593971E0CC",
+                ),
+            ),
+            (
+                concat!(
+                    "02B5AD07AFCD99B6D92CB433FBD2A28FDEB98EAE2EB09B6014EF0F8197CD584033",
+                    "02E8616910F9293CF692C49F351DB86B25E352901F0E237BAFDA11F1C1CEF29FFD",
+                ),
+                &[0, 4],
+                &[0, 3],
+                &[(0, true), (1, false), (2, true)],
+                &[6, 7],
+                concat!(
+                    "839B08820B681DBA8DAF4CC7B104E8F2638F9388F8D7A555DC17B6E6971D7426",
+                    "CE07BF6AB01F1DB50E4E33719295F4094572B79868E440FB3DEFD3FAC1DB589E",
+                ),
+            ),
+        ] {
+            let (session, ctx) = session(aggnonce, nonces, keys_, applied);
+            let sig = session.aggregate(&partial(psig_indices)).unwrap();
+
+            assert_eq!(sig, bip340::Signature(unhex_array(expected)));
+            assert!(ctx.xonly_public_key().verify(&msg, &sig));
+        }
+
+        // The second partial signature exceeds the group order.
+        let (session, _) = session(
+            concat!(
+                "02B5AD07AFCD99B6D92CB433FBD2A28FDEB98EAE2EB09B6014EF0F8197CD584033",
+                "02E8616910F9293CF692C49F351DB86B25E352901F0E237BAFDA11F1C1CEF29FFD",
+            ),
+            &[0, 4],
+            &[0, 3],
+            &[(0, true), (1, false), (2, true)],
+        );
+        assert_eq!(session.aggregate(&partial(&[7, 8])), None);
+    }
+}
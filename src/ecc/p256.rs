@@ -0,0 +1,78 @@
+//! # P-256
+//! The NIST curve P-256 (secp256r1) of FIPS 186-5, paired with SHA-256 for
+//! ECDSA.
+
+use super::{ecdsa::EcdsaCurve, modular::Modulus, weierstrass::Curve};
+use crate::sha::sha256::Sha256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NistP256;
+
+/// `p = 2^256 - 2^224 + 2^192 + 2^96 - 1`
+#[derive(Debug, Clone, Copy)]
+pub struct FieldPrime;
+
+/// The order `n` of the base point.
+#[derive(Debug, Clone, Copy)]
+pub struct GroupOrder;
+
+impl Modulus<4> for FieldPrime {
+    const P: [u64; 4] = [
+        0xffffffffffffffff,
+        0x00000000ffffffff,
+        0x0000000000000000,
+        0xffffffff00000001,
+    ];
+}
+
+impl Modulus<4> for GroupOrder {
+    const P: [u64; 4] = [
+        0xf3b9cac2fc632551,
+        0xbce6faada7179e84,
+        0xffffffffffffffff,
+        0xffffffff00000000,
+    ];
+}
+
+impl Curve<4> for NistP256 {
+    type Field = FieldPrime;
+    type Order = GroupOrder;
+
+    /// `a = -3`
+    const A: [u64; 4] = [
+        0xfffffffffffffffc,
+        0x00000000ffffffff,
+        0x0000000000000000,
+        0xffffffff00000001,
+    ];
+    const B: [u64; 4] = [
+        0x3bce3c3e27d2604b,
+        0x651d06b0cc53b0f6,
+        0xb3ebbd55769886bc,
+        0x5ac635d8aa3a93e7,
+    ];
+    const GX: [u64; 4] = [
+        0xf4a13945d898c296,
+        0x77037d812deb33a0,
+        0xf8bce6e563a440f2,
+        0x6b17d1f2e12c4247,
+    ];
+    const GY: [u64; 4] = [
+        0xcbb6406837bf51f5,
+        0x2bce33576b315ece,
+        0x8ee7eb4a7c0f9e16,
+        0x4fe342e2fe1a7f9b,
+    ];
+}
+
+impl EcdsaCurve<4> for NistP256 {
+    type Hash = Sha256;
+}
+
+pub type Scalar = super::weierstrass::Scalar<NistP256, 4>;
+pub type ProjectivePoint = super::weierstrass::ProjectivePoint<NistP256, 4>;
+pub type SecretKey = super::weierstrass::SecretKey<NistP256, 4>;
+pub type PublicKey = super::weierstrass::PublicKey<NistP256, 4>;
+pub type SigningKey = super::ecdsa::SigningKey<NistP256, 4>;
+pub type VerifyingKey = super::ecdsa::VerifyingKey<NistP256, 4>;
+pub type Signature = super::ecdsa::Signature<NistP256, 4>;
@@ -0,0 +1,183 @@
+//! # P-384
+//! The NIST curve P-384 (secp384r1) of FIPS 186-5, paired with SHA-384 for
+//! ECDSA.
+
+use super::{ecdsa::EcdsaCurve, modular::Modulus, weierstrass::Curve};
+use crate::sha::sha384::Sha384;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NistP384;
+
+/// `p = 2^384 - 2^128 - 2^96 + 2^32 - 1`
+#[derive(Debug, Clone, Copy)]
+pub struct FieldPrime;
+
+/// The order `n` of the base point.
+#[derive(Debug, Clone, Copy)]
+pub struct GroupOrder;
+
+impl Modulus<6> for FieldPrime {
+    const P: [u64; 6] = [
+        0x00000000ffffffff,
+        0xffffffff00000000,
+        0xfffffffffffffffe,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ];
+}
+
+impl Modulus<6> for GroupOrder {
+    const P: [u64; 6] = [
+        0xecec196accc52973,
+        0x581a0db248b0a77a,
+        0xc7634d81f4372ddf,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ];
+}
+
+impl Curve<6> for NistP384 {
+    type Field = FieldPrime;
+    type Order = GroupOrder;
+
+    /// `a = -3`
+    const A: [u64; 6] = [
+        0x00000000fffffffc,
+        0xffffffff00000000,
+        0xfffffffffffffffe,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ];
+    const B: [u64; 6] = [
+        0x2a85c8edd3ec2aef,
+        0xc656398d8a2ed19d,
+        0x0314088f5013875a,
+        0x181d9c6efe814112,
+        0x988e056be3f82d19,
+        0xb3312fa7e23ee7e4,
+    ];
+    const GX: [u64; 6] = [
+        0x3a545e3872760ab7,
+        0x5502f25dbf55296c,
+        0x59f741e082542a38,
+        0x6e1d3b628ba79b98,
+        0x8eb1c71ef320ad74,
+        0xaa87ca22be8b0537,
+    ];
+    const GY: [u64; 6] = [
+        0x7a431d7c90ea0e5f,
+        0x0a60b1ce1d7e819d,
+        0xe9da3113b5f0b8c0,
+        0xf8f41dbd289a147c,
+        0x5d9e98bf9292dc29,
+        0x3617de4a96262c6f,
+    ];
+}
+
+impl EcdsaCurve<6> for NistP384 {
+    type Hash = Sha384;
+}
+
+pub type Scalar = super::weierstrass::Scalar<NistP384, 6>;
+pub type ProjectivePoint = super::weierstrass::ProjectivePoint<NistP384, 6>;
+pub type SecretKey = super::weierstrass::SecretKey<NistP384, 6>;
+pub type PublicKey = super::weierstrass::PublicKey<NistP384, 6>;
+pub type SigningKey = super::ecdsa::SigningKey<NistP384, 6>;
+pub type VerifyingKey = super::ecdsa::VerifyingKey<NistP384, 6>;
+pub type Signature = super::ecdsa::Signature<NistP384, 6>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex;
+
+    /// The first P-384 case of the NIST CAVS ECC CDH primitive tests.
+    #[test]
+    fn ecdh() {
+        let key = SecretKey::from_bytes(&unhex(concat!(
+            "3cc3122a68f0d95027ad38c067916ba0eb8c38894d22e1b1",
+            "5618b6818a661774ad463b205da88cf699ab4d43c9cf98a1",
+        )))
+        .unwrap();
+        let peer = PublicKey::from_sec1(&unhex(concat!(
+            "04",
+            "a7c76b970c3b5fe8b05d2838ae04ab47697b9eaf52e76459",
+            "2efda27fe7513272734466b400091adbf2d68c58e0c50066",
+            "ac68f19f2e1cb879aed43a9969b91a0839c4c38a49749b66",
+            "1efedf243451915ed0905a32b060992b468c64766fc8437a",
+        )))
+        .unwrap();
+
+        assert_eq!(
+            key.public_key().to_sec1(false),
+            unhex(concat!(
+                "04",
+                "9803807f2f6d2fd966cdd0290bd410c0190352fbec7ff624",
+                "7de1302df86f25d34fe4a97bef60cff548355c015dbb3e5f",
+                "ba26ca69ec2f5b5d9dad20cc9da711383a9dbe34ea3fa5a2",
+                "af75b46502629ad54dd8b7d73a8abb06a3a3be47d650cc99",
+            ))
+        );
+        assert_eq!(
+            key.diffie_hellman(&peer),
+            Some(unhex(concat!(
+                "5f9d29dc5e31a163060356213669c8ce132e22f57c9a04f4",
+                "0ba7fcead493b457e5621e766c40a2e3d4d6a04b25e533f1",
+            )))
+        );
+    }
+
+    /// RFC 6979, appendix A.2.6, with SHA-384.
+    #[test]
+    fn rfc6979() {
+        let key = SigningKey::from_bytes(&unhex(concat!(
+            "6b9d3dad2e1b8c1c05b19875b6659f4de23c3b667bf297ba",
+            "9aa47740787137d896d5724e4c70a825f872c9ea60d2edf5",
+        )))
+        .unwrap();
+
+        assert_eq!(
+            key.verifying_key().to_sec1(false),
+            unhex(concat!(
+                "04",
+                "ec3a4e415b4e19a4568618029f427fa5da9a8bc4ae92e02e",
+                "06aae5286b300c64def8f0ea9055866064a254515480bc13",
+                "8015d9b72d7d57244ea8ef9ac0c621896708a59367f9dfb9",
+                "f54ca84b3f1c9db1288b231c3ae0d4fe7344fd2533264720",
+            ))
+        );
+
+        for (msg, expected) in [
+            (
+                &b"sample"[..],
+                concat!(
+                    "94edbb92a5ecb8aad4736e56c691916b3f88140666ce9fa7",
+                    "3d64c4ea95ad133c81a648152e44acf96e36dd1e80fabe46",
+                    "99ef4aeb15f178cea1fe40db2603138f130e740a19624526",
+                    "203b6351d0a3a94fa329c145786e679e7b82c71a38628ac8",
+                ),
+            ),
+            (
+                b"test",
+                concat!(
+                    "8203b63d3c853e8d77227fb377bcf7b7b772e97892a80f36",
+                    "ab775d509d7a5feb0542a7f0812998da8f1dd3ca3cf023db",
+                    "ddd0760448d42d8a43af45af836fce4de8be06b485e9b61b",
+                    "827c2f13173923e06a739f040649a667bf3b828246baa5a5",
+                ),
+            ),
+        ] {
+            let sig = key.sign(msg);
+            assert_eq!(sig.to_bytes(), unhex(expected));
+            assert!(key.verifying_key().verify(msg, &sig));
+
+            let mut tampered = sig.to_bytes();
+            tampered[95] ^= 1;
+            let tampered = Signature::from_bytes(&tampered).unwrap();
+            assert!(!key.verifying_key().verify(msg, &tampered));
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! # ristretto255 (RFC 9496)
+//! A prime-order group built on edwards25519. Each element is a coset of the
+//! 4-torsion subgroup, represented by any of its Edwards points, with a
+//! canonical 32 byte encoding; there is no cofactor to clear or check.
+//!
+//! Scalars are the edwards25519 [`Scalar`]s modulo `l`.
+
+use std::ops::{Add, Neg, Sub};
+
+use super::{
+    curve25519::FieldElement,
+    edwards25519::{EdwardsPoint, Scalar, D},
+    group::Group,
+};
+use crate::sha::sha512::Sha512;
+
+/// An element of the ristretto255 group.
+#[derive(Debug, Clone, Copy)]
+pub struct RistrettoPoint(EdwardsPoint);
+
+impl RistrettoPoint {
+    pub const IDENTITY: RistrettoPoint = RistrettoPoint(EdwardsPoint::IDENTITY);
+
+    /// The generator, the coset of the edwards25519 base point.
+    pub const BASEPOINT: RistrettoPoint = RistrettoPoint(EdwardsPoint::BASEPOINT);
+
+    /// The canonical encoding (RFC 9496, section 4.3.2).
+    pub fn compress(&self) -> [u8; 32] {
+        let EdwardsPoint { x, y, z, t } = self.0;
+
+        let u1 = (z + y) * (z - y);
+        let u2 = x * y;
+        let (_, invsqrt) = FieldElement::sqrt_ratio_i(&FieldElement::ONE, &(u1 * u2.square()));
+        let den1 = invsqrt * u1;
+        let den2 = invsqrt * u2;
+        let z_inv = den1 * den2 * t;
+
+        let rotate = (t * z_inv).is_negative();
+        let x_r = FieldElement::select(&x, &(y * FieldElement::SQRT_M1), rotate);
+        let y_r = FieldElement::select(&y, &(x * FieldElement::SQRT_M1), rotate);
+        let den_inv = FieldElement::select(&den2, &(den1 * INVSQRT_A_MINUS_D), rotate);
+
+        let y_r = FieldElement::select(&y_r, &-y_r, (x_r * z_inv).is_negative());
+
+        abs(&(den_inv * (z - y_r))).to_bytes()
+    }
+
+    /// Decode a canonical encoding (RFC 9496, section 4.3.1); every other
+    /// 32 byte string is rejected.
+    pub fn decompress(bytes: &[u8; 32]) -> Option<RistrettoPoint> {
+        let s = FieldElement::from_bytes(bytes);
+
+        if s.to_bytes() != *bytes || s.is_negative() {
+            return None;
+        }
+
+        let ss = s.square();
+        let u1 = FieldElement::ONE - ss;
+        let u2 = FieldElement::ONE + ss;
+        let u2_sqr = u2.square();
+
+        let v = -(D * u1.square()) - u2_sqr;
+
+        let (was_square, invsqrt) = FieldElement::sqrt_ratio_i(&FieldElement::ONE, &(v * u2_sqr));
+
+        let den_x = invsqrt * u2;
+        let den_y = invsqrt * den_x * v;
+
+        let x = abs(&((s + s) * den_x));
+        let y = u1 * den_y;
+        let t = x * y;
+
+        if !was_square || t.is_negative() || y.is_zero() {
+            return None;
+        }
+
+        Some(RistrettoPoint(EdwardsPoint {
+            x,
+            y,
+            z: FieldElement::ONE,
+            t,
+        }))
+    }
+
+    /// The element derived from 64 uniformly random bytes, as the sum of
+    /// the Elligator images of both halves (RFC 9496, section 4.3.4).
+    pub fn from_uniform_bytes(bytes: &[u8; 64]) -> RistrettoPoint {
+        let p1 = map(&FieldElement::from_bytes(&bytes[..32].try_into().unwrap()));
+        let p2 = map(&FieldElement::from_bytes(&bytes[32..].try_into().unwrap()));
+
+        RistrettoPoint(p1 + p2)
+    }
+
+    /// Hash `msg` to the group through `from_uniform_bytes(SHA-512(msg))`.
+    pub fn hash_from_bytes(msg: &[u8]) -> RistrettoPoint {
+        RistrettoPoint::from_uniform_bytes(&Sha512::new(msg).result())
+    }
+
+    /// `[s] self`, in constant time.
+    pub fn mul(&self, s: &Scalar) -> RistrettoPoint {
+        RistrettoPoint(self.0.mul(s))
+    }
+
+    /// `[s] B`, in constant time.
+    pub fn mul_base(s: &Scalar) -> RistrettoPoint {
+        RistrettoPoint(EdwardsPoint::mul_base(s))
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.ct_eq(&RistrettoPoint::IDENTITY)
+    }
+
+    /// Whether both represent the same element (RFC 9496, section 4.5).
+    pub fn ct_eq(&self, other: &RistrettoPoint) -> bool {
+        let (a, b) = (&self.0, &other.0);
+
+        (a.x * b.y).ct_eq(&(a.y * b.x)) | (a.y * b.y).ct_eq(&(a.x * b.x))
+    }
+}
+
+impl Group for RistrettoPoint {
+    const IDENTITY: RistrettoPoint = RistrettoPoint::IDENTITY;
+
+    fn double(&self) -> RistrettoPoint {
+        RistrettoPoint(self.0.double())
+    }
+}
+
+impl PartialEq for RistrettoPoint {
+    fn eq(&self, other: &RistrettoPoint) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl Eq for RistrettoPoint {}
+
+impl Add for RistrettoPoint {
+    type Output = RistrettoPoint;
+
+    fn add(self, rhs: RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(self.0 + rhs.0)
+    }
+}
+
+impl Neg for RistrettoPoint {
+    type Output = RistrettoPoint;
+
+    fn neg(self) -> RistrettoPoint {
+        RistrettoPoint(-self.0)
+    }
+}
+
+impl Sub for RistrettoPoint {
+    type Output = RistrettoPoint;
+
+    fn sub(self, rhs: RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(self.0 - rhs.0)
+    }
+}
+
+/// `|x|`, the non-negative one of `x` and `-x`.
+fn abs(x: &FieldElement) -> FieldElement {
+    FieldElement::select(x, &-*x, x.is_negative())
+}
+
+/// The Elligator map of RFC 9496, section 4.3.4, from a field element to an
+/// Edwards point.
+fn map(t: &FieldElement) -> EdwardsPoint {
+    let r = FieldElement::SQRT_M1 * t.square();
+    let u = (r + FieldElement::ONE) * ONE_MINUS_D_SQ;
+    let v = (-FieldElement::ONE - r * D) * (r + D);
+
+    let (was_square, s) = FieldElement::sqrt_ratio_i(&u, &v);
+    let s_prime = -abs(&(s * *t));
+    let s = FieldElement::select(&s_prime, &s, was_square);
+    let c = FieldElement::select(&r, &-FieldElement::ONE, was_square);
+
+    let n = c * (r - FieldElement::ONE) * D_MINUS_ONE_SQ - v;
+
+    let w0 = (s + s) * v;
+    let w1 = n * SQRT_AD_MINUS_ONE;
+    let w2 = FieldElement::ONE - s.square();
+    let w3 = FieldElement::ONE + s.square();
+
+    EdwardsPoint {
+        x: w0 * w3,
+        y: w2 * w1,
+        z: w1 * w3,
+        t: w0 * w2,
+    }
+}
+
+// Constants
+
+/// `sqrt(a d - 1)`
+const SQRT_AD_MINUS_ONE: FieldElement = FieldElement([
+    0x7f6a0497b2e1b,
+    0x1836f0a97afd2,
+    0x7d747f6be7638,
+    0x456079e7e6498,
+    0x376931bf2b834,
+]);
+
+/// `1 / sqrt(a - d)`
+const INVSQRT_A_MINUS_D: FieldElement = FieldElement([
+    0x0fdaa805d40ea,
+    0x2eb482e57d339,
+    0x007610274bc58,
+    0x6510b613dc8ff,
+    0x786c8905cfaff,
+]);
+
+/// `1 - d^2`
+const ONE_MINUS_D_SQ: FieldElement = FieldElement([
+    0x409c1945fc176,
+    0x719abc6a1fc4f,
+    0x1c37f90b20684,
+    0x06bccca55eedf,
+    0x029072a8b2b3e,
+]);
+
+/// `(d - 1)^2`
+const D_MINUS_ONE_SQ: FieldElement = FieldElement([
+    0x55aaa44ed4d20,
+    0x59603c3332635,
+    0x26d3baf4a7928,
+    0x120a66e6997a9,
+    0x5968b37af66c2,
+]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex_array;
+
+    /// RFC 9496, appendix A.1: the encodings of `0 * B` to `15 * B`.
+    const MULTIPLES: [&str; 16] = [
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+        "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+        "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+        "da80862773358b466ffadfe0b3293ab3d9fd53c5ea6c955358f568322daf6a57",
+        "e882b131016b52c1d3337080187cf768423efccbb517bb495ab812c4160ff44e",
+        "f64746d3c92b13050ed8d80236a7f0007c3b3f962f5ba793d19a601ebb1df403",
+        "44f53520926ec81fbd5a387845beb7df85a96a24ece18738bdcfa6a7822a176d",
+        "903293d8f2287ebe10e2374dc1a53e0bc887e592699f02d077d5263cdd55601c",
+        "02622ace8f7303a31cafc63f8fc48fdc16e1c8c8d234b2f0d6685282a9076031",
+        "20706fd788b2720a1ed2a5dad4952b01f413bcf0e7564de8cdc816689e2db95f",
+        "bce83f8ba5dd2fa572864c24ba1810f9522bc6004afe95877ac73241cafdab42",
+        "e4549ee16b9aa03099ca208c67adafcafa4c3f3e4e5303de6026e3ca8ff84460",
+        "aa52e000df2e16f55fb1032fc33bc42742dad6bd5a8fc0be0167436c5948501f",
+        "46376b80f409b29dc2b5f6f0c52591990896e5716f41477cd30085ab7f10301e",
+        "e0c418f7c8d9c4cdd7395b93ea124f3ad99021bb681dfc3302a9d99a2e53e64e",
+    ];
+
+    /// RFC 9496, appendix A.2: non-canonical field elements, negative field
+    /// elements, non-square `x^2`, negative `xy` and `s = -1`.
+    const BAD_ENCODINGS: [&str; 29] = [
+        "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "f3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "ed57ffd8c914fb201471d1c3d245ce3c746fcbe63a3679d51b6a516ebebe0e20",
+        "c34c4e1826e5d403b78e246e88aa051c36ccf0aafebffe137d148a2bf9104562",
+        "c940e5a4404157cfb1628b108db051a8d439e1a421394ec4ebccb9ec92a8ac78",
+        "47cfc5497c53dc8e61c91d17fd626ffb1c49e2bca94eed052281b510b1117a24",
+        "f1c6165d33367351b0da8f6e4511010c68174a03b6581212c71c0e1d026c3c72",
+        "87260f7a2f12495118360f02c26a470f450dadf34a413d21042b43b9d93e1309",
+        "26948d35ca62e643e26a83177332e6b6afeb9d08e4268b650f1f5bbd8d81d371",
+        "4eac077a713c57b4f4397629a4145982c661f48044dd3f96427d40b147d9742f",
+        "de6a7b00deadc788eb6b6c8d20c0ae96c2f2019078fa604fee5b87d6e989ad7b",
+        "bcab477be20861e01e4a0e295284146a510150d9817763caf1a6f4b422d67042",
+        "2a292df7e32cababbd9de088d1d1abec9fc0440f637ed2fba145094dc14bea08",
+        "f4a9e534fc0d216c44b218fa0c42d99635a0127ee2e53c712f70609649fdff22",
+        "8268436f8c4126196cf64b3c7ddbda90746a378625f9813dd9b8457077256731",
+        "2810e5cbc2cc4d4eece54f61c6f69758e289aa7ab440b3cbeaa21995c2f4232b",
+        "3eb858e78f5a7254d8c9731174a94f76755fd3941c0ac93735c07ba14579630e",
+        "a45fdc55c76448c049a1ab33f17023edfb2be3581e9c7aade8a6125215e04220",
+        "d483fe813c6ba647ebbfd3ec41adca1c6130c2beeee9d9bf065c8d151c5f396e",
+        "8a2e1d30050198c65a54483123960ccc38aef6848e1ec8f5f780e8523769ba32",
+        "32888462f8b486c68ad7dd9610be5192bbeaf3b443951ac1a8118419d9fa097b",
+        "227142501b9d4355ccba290404bde41575b037693cef1f438c47f8fbf35d1165",
+        "5c37cc491da847cfeb9281d407efc41e15144c876e0170b499a96a22ed31e01e",
+        "445425117cb8c90edcbc7c1cc0e74f747f2c1efa5630a967c64f287792a48a4b",
+        "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    ];
+
+    /// RFC 9496, appendix A.3: 64 uniform bytes and the elements they map
+    /// to. The bytes are the SHA-512 digests of the labels.
+    const FROM_UNIFORM: [(&str, &str, &str); 7] = [
+        (
+            "Ristretto is traditionally a short shot of espresso coffee",
+            concat!(
+                "5d1be09e3d0c82fc538112490e35701979d99e06ca3e2b5b54bffe8b4dc772c1",
+                "4d98b696a1bbfb5ca32c436cc61c16563790306c79eaca7705668b47dffe5bb6",
+            ),
+            "3066f82a1a747d45120d1740f14358531a8f04bbffe6a819f86dfe50f44a0a46",
+        ),
+        (
+            "made with the normal amount of ground coffee but extracted with",
+            concat!(
+                "f116b34b8f17ceb56e8732a60d913dd10cce47a6d53bee9204be8b44f6678b27",
+                "0102a56902e2488c46120e9276cfe54638286b9e4b3cdb470b542d46c2068d38",
+            ),
+            "f26e5b6f7d362d2d2a94c5d0e7602cb4773c95a2e5c31a64f133189fa76ed61b",
+        ),
+        (
+            "about half the amount of water in the same amount of time",
+            concat!(
+                "8422e1bbdaab52938b81fd602effb6f89110e1e57208ad12d9ad767e2e25510c",
+                "27140775f9337088b982d83d7fcf0b2fa1edffe51952cbe7365e95c86eaf325c",
+            ),
+            "006ccd2a9e6867e6a2c5cea83d3302cc9de128dd2a9a57dd8ee7b9d7ffe02826",
+        ),
+        (
+            "by using a finer grind.",
+            concat!(
+                "ac22415129b61427bf464e17baee8db65940c233b98afce8d17c57beeb7876c2",
+                "150d15af1cb1fb824bbd14955f2b57d08d388aab431a391cfc33d5bafb5dbbaf",
+            ),
+            "f8f0c87cf237953c5890aec3998169005dae3eca1fbb04548c635953c817f92a",
+        ),
+        (
+            "This produces a concentrated shot of coffee per volume.",
+            concat!(
+                "165d697a1ef3d5cf3c38565beefcf88c0f282b8e7dbd28544c483432f1cec767",
+                "5debea8ebb4e5fe7d6f6e5db15f15587ac4d4d4a1de7191e0c1ca6664abcc413",
+            ),
+            "ae81e7dedf20a497e10c304a765c1767a42d6e06029758d2d7e8ef7cc4c41179",
+        ),
+        (
+            "Just pulling a normal shot short will produce a weaker shot",
+            concat!(
+                "a836e6c9a9ca9f1e8d486273ad56a78c70cf18f0ce10abb1c7172ddd605d7fd2",
+                "979854f47ae1ccf204a33102095b4200e5befc0465accc263175485f0e17ea5c",
+            ),
+            "e2705652ff9f5e44d3e841bf1c251cf7dddb77d140870d1ab2ed64f1a9ce8628",
+        ),
+        (
+            "and is not a Ristretto as some believe.",
+            concat!(
+                "2cdc11eaeb95daf01189417cdddbf95952993aa9cb9c640eb5058d09702c7462",
+                "2c9965a697a3b345ec24ee56335b556e677b30e6f90ac77d781064f866a3c982",
+            ),
+            "80bd07262511cdde4863f8a7434cef696750681cb9510eea557088f76d9e5065",
+        ),
+    ];
+
+    #[test]
+    fn multiples_of_generator() {
+        let mut point = RistrettoPoint::IDENTITY;
+
+        for (i, expected) in MULTIPLES.iter().enumerate() {
+            let expected = unhex_array(expected);
+
+            assert_eq!(point.compress(), expected, "{i} * B");
+            assert_eq!(RistrettoPoint::decompress(&expected), Some(point));
+
+            point = point + RistrettoPoint::BASEPOINT;
+        }
+    }
+
+    #[test]
+    fn bad_encodings() {
+        for bytes in BAD_ENCODINGS {
+            assert_eq!(
+                RistrettoPoint::decompress(&unhex_array(bytes)),
+                None,
+                "{bytes}"
+            );
+        }
+    }
+
+    #[test]
+    fn from_uniform_bytes() {
+        for (label, bytes, expected) in FROM_UNIFORM {
+            let expected = unhex_array(expected);
+
+            assert_eq!(
+                RistrettoPoint::from_uniform_bytes(&unhex_array(bytes)).compress(),
+                expected,
+                "{label}"
+            );
+            assert_eq!(
+                RistrettoPoint::hash_from_bytes(label.as_bytes()).compress(),
+                expected,
+                "{label}"
+            );
+        }
+    }
+}
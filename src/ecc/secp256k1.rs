@@ -0,0 +1,216 @@
+//! # secp256k1
+//! The SEC 2 Koblitz curve `y^2 = x^3 + 7` used by Bitcoin and Ethereum,
+//! paired with SHA-256 for ECDSA.
+//!
+//! Signatures always have low `s`, and the `eth_*` helpers sign and recover
+//! Ethereum "personal_sign" messages (EIP-191) with Keccak-256.
+
+use super::{ecdsa::EcdsaCurve, modular::Modulus, weierstrass::Curve};
+use crate::sha::{sha256::Sha256, sha3::keccak256};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1;
+
+/// `p = 2^256 - 2^32 - 977`
+#[derive(Debug, Clone, Copy)]
+pub struct FieldPrime;
+
+/// The order `n` of the base point.
+#[derive(Debug, Clone, Copy)]
+pub struct GroupOrder;
+
+impl Modulus<4> for FieldPrime {
+    const P: [u64; 4] = [
+        0xfffffffefffffc2f,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ];
+}
+
+impl Modulus<4> for GroupOrder {
+    const P: [u64; 4] = [
+        0xbfd25e8cd0364141,
+        0xbaaedce6af48a03b,
+        0xfffffffffffffffe,
+        0xffffffffffffffff,
+    ];
+}
+
+impl Curve<4> for Secp256k1 {
+    type Field = FieldPrime;
+    type Order = GroupOrder;
+
+    const A: [u64; 4] = [0; 4];
+    const B: [u64; 4] = [7, 0, 0, 0];
+    const GX: [u64; 4] = [
+        0x59f2815b16f81798,
+        0x029bfcdb2dce28d9,
+        0x55a06295ce870b07,
+        0x79be667ef9dcbbac,
+    ];
+    const GY: [u64; 4] = [
+        0x9c47d08ffb10d4b8,
+        0xfd17b448a6855419,
+        0x5da4fbfc0e1108a8,
+        0x483ada7726a3c465,
+    ];
+}
+
+impl EcdsaCurve<4> for Secp256k1 {
+    const LOW_S: bool = true;
+    type Hash = Sha256;
+}
+
+pub type FieldElement = super::weierstrass::FieldElement<Secp256k1, 4>;
+pub type Scalar = super::weierstrass::Scalar<Secp256k1, 4>;
+pub type ProjectivePoint = super::weierstrass::ProjectivePoint<Secp256k1, 4>;
+pub type SecretKey = super::weierstrass::SecretKey<Secp256k1, 4>;
+pub type PublicKey = super::weierstrass::PublicKey<Secp256k1, 4>;
+pub type SigningKey = super::ecdsa::SigningKey<Secp256k1, 4>;
+pub type VerifyingKey = super::ecdsa::VerifyingKey<Secp256k1, 4>;
+pub type Signature = super::ecdsa::Signature<Secp256k1, 4>;
+
+// Ethereum
+
+/// The EIP-191 hash of a signed message,
+/// `keccak256("\x19Ethereum Signed Message:\n" || len(msg) || msg)` with the
+/// length in decimal.
+pub fn eth_message_hash(msg: &[u8]) -> [u8; 32] {
+    let mut data = format!("\x19Ethereum Signed Message:\n{}", msg.len()).into_bytes();
+    data.extend_from_slice(msg);
+
+    keccak256(&data)
+}
+
+/// The last 20 bytes of the Keccak-256 of the uncompressed point, without
+/// its `0x04` prefix.
+pub fn eth_address(key: &VerifyingKey) -> [u8; 20] {
+    keccak256(&key.to_sec1(false)[1..])[12..]
+        .try_into()
+        .unwrap()
+}
+
+/// Sign `msg` as `personal_sign` does, returning `r || s || v` with
+/// `v = 27 + recovery id`.
+pub fn eth_sign(key: &SigningKey, msg: &[u8]) -> [u8; 65] {
+    // Nonces use HMAC-SHA256 over the Keccak-256 digest, as libsecp256k1
+    // does for Ethereum signers.
+    let (sig, recovery_id) = key.sign_digest_recoverable::<Sha256>(&eth_message_hash(msg));
+
+    let mut bytes = [0u8; 65];
+    bytes[..64].copy_from_slice(&sig.to_bytes());
+    bytes[64] = 27 + recovery_id;
+
+    bytes
+}
+
+/// The address that made an [`eth_sign`] signature on `msg`. Both `v` in
+/// `{27, 28}` and the raw recovery id `{0, 1}` are accepted, but not high
+/// `s`, as in EIP-2.
+pub fn eth_recover(msg: &[u8], sig: &[u8; 65]) -> Option<[u8; 20]> {
+    let recovery_id = match sig[64] {
+        0 | 1 => sig[64],
+        27 | 28 => sig[64] - 27,
+        _ => return None,
+    };
+
+    let signature = Signature::from_bytes(&sig[..64])?;
+    if !signature.is_low_s() {
+        return None;
+    }
+
+    let key = VerifyingKey::recover_from_digest(&eth_message_hash(msg), &signature, recovery_id)?;
+
+    Some(eth_address(&key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{unhex, unhex_array};
+
+    #[test]
+    fn sign_recover() {
+        let key = SigningKey::from_bytes(&[0x5a; 32]).unwrap();
+        let mut ids = [false; 2];
+
+        for i in 0..32u8 {
+            let msg = [i; 7];
+            let (sig, recovery_id) = key.sign_recoverable(&msg);
+
+            assert!(sig.is_low_s());
+            assert_eq!(sig.normalize_s(), sig);
+            assert!(key.verifying_key().verify(&msg, &sig));
+            assert_eq!(
+                VerifyingKey::recover(&msg, &sig, recovery_id),
+                Some(key.verifying_key())
+            );
+            assert_ne!(
+                VerifyingKey::recover(&msg, &sig, recovery_id ^ 1),
+                Some(key.verifying_key())
+            );
+
+            ids[recovery_id as usize & 1] = true;
+        }
+
+        // Recovery ids of both parities came up.
+        assert_eq!(ids, [true, true]);
+    }
+
+    /// `r = 2` is `x(R) - n` for a point `R`, so recovery id 2 has to add `n`
+    /// back, and both keys it can give verify.
+    #[test]
+    fn recover_overflowing_r() {
+        let mut bytes = [0; 64];
+        bytes[31] = 2;
+        bytes[63] = 3;
+        let sig = Signature::from_bytes(&bytes).unwrap();
+        let digest = [0x42; 32];
+
+        for recovery_id in [2, 3] {
+            let key = VerifyingKey::recover_from_digest(&digest, &sig, recovery_id).unwrap();
+
+            assert!(key.verify_digest(&digest, &sig));
+        }
+
+        assert_eq!(VerifyingKey::recover_from_digest(&digest, &sig, 4), None);
+    }
+
+    #[test]
+    fn eth_address_of_key_one() {
+        let mut one = [0; 32];
+        one[31] = 1;
+        let key = SigningKey::from_bytes(&one).unwrap();
+
+        assert_eq!(
+            eth_address(&key.verifying_key()).to_vec(),
+            unhex("7e5f4552091a69125d5dfcb7b8c2659029395bdf")
+        );
+    }
+
+    /// `web3.eth.accounts.sign("Some data", key)` from the web3.js
+    /// documentation.
+    #[test]
+    fn personal_sign() {
+        let key = SigningKey::from_bytes(&unhex(
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        ))
+        .unwrap();
+        let address = unhex_array("2c7536e3605d9c16a7a3d7b1898e529396a65c23");
+        let sig = unhex_array(concat!(
+            "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd",
+            "6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a029",
+            "1c",
+        ));
+
+        assert_eq!(
+            eth_message_hash(b"Some data").to_vec(),
+            unhex("1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655")
+        );
+        assert_eq!(eth_address(&key.verifying_key()), address);
+        assert_eq!(eth_sign(&key, b"Some data"), sig);
+        assert_eq!(eth_recover(b"Some data", &sig), Some(address));
+        assert_ne!(eth_recover(b"Other data", &sig), Some(address));
+    }
+}
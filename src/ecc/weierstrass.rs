@@ -0,0 +1,417 @@
+//! # Short Weierstrass curves
+//! Prime-order curves `y^2 = x^3 + a x + b` over a prime field, such as the
+//! NIST curves and secp256k1, with field and scalar arithmetic in
+//! [`Residue`]s and ECDH.
+//!
+//! Points use projective coordinates and the complete formulas of Renes,
+//! Costello and Batina ("Complete addition formulas for prime order elliptic
+//! curves", 2016), so there are no exceptional cases to branch on and scalar
+//! multiplication runs in constant time.
+
+use std::ops::{Add, Neg, Sub};
+
+use super::{
+    group::{self, Group},
+    modular::{Modulus, Residue},
+};
+use crate::rng;
+
+/// The parameters of a curve `y^2 = x^3 + a x + b` of prime order, whose
+/// field elements and scalars both fit `N` limbs.
+pub trait Curve<const N: usize>: Copy + std::fmt::Debug {
+    /// The field prime `p`.
+    type Field: Modulus<N>;
+    /// The group order `n`, which must be `64 N` bits long.
+    type Order: Modulus<N>;
+
+    const A: [u64; N];
+    const B: [u64; N];
+    const GX: [u64; N];
+    const GY: [u64; N];
+}
+
+/// An element of the field of `C`.
+pub type FieldElement<C, const N: usize> = Residue<<C as Curve<N>>::Field, N>;
+
+/// An integer modulo the order of `C`.
+pub type Scalar<C, const N: usize> = Residue<<C as Curve<N>>::Order, N>;
+
+/// A point in projective coordinates `(X : Y : Z)`, with `x = X / Z` and
+/// `y = Y / Z`; the identity is `(0 : 1 : 0)`.
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectivePoint<C: Curve<N>, const N: usize> {
+    x: FieldElement<C, N>,
+    y: FieldElement<C, N>,
+    z: FieldElement<C, N>,
+}
+
+impl<C: Curve<N>, const N: usize> ProjectivePoint<C, N> {
+    pub const IDENTITY: Self = ProjectivePoint {
+        x: Residue::ZERO,
+        y: Residue::ONE,
+        z: Residue::ZERO,
+    };
+
+    /// The standard base point `G`.
+    pub fn generator() -> Self {
+        ProjectivePoint::from_affine_unchecked(
+            Residue::from_limbs(C::GX),
+            Residue::from_limbs(C::GY),
+        )
+    }
+
+    /// The affine point `(x, y)`, or `None` if it is not on the curve.
+    pub fn from_affine(x: FieldElement<C, N>, y: FieldElement<C, N>) -> Option<Self> {
+        (y.square() == rhs::<C, N>(&x)).then(|| ProjectivePoint::from_affine_unchecked(x, y))
+    }
+
+    /// The affine point `(x, y)`, which must be on the curve.
+    fn from_affine_unchecked(x: FieldElement<C, N>, y: FieldElement<C, N>) -> Self {
+        ProjectivePoint {
+            x,
+            y,
+            z: Residue::ONE,
+        }
+    }
+
+    /// The affine coordinates, or `None` for the identity.
+    pub fn to_affine(&self) -> Option<(FieldElement<C, N>, FieldElement<C, N>)> {
+        let z_inv = self.z.invert();
+
+        (!self.is_identity()).then(|| (self.x * z_inv, self.y * z_inv))
+    }
+
+    /// The SEC1 encoding, `0x02` or `0x03` and `x` when `compressed`, else
+    /// `0x04 || x || y`. The identity is the single byte `0x00`.
+    pub fn to_sec1(&self, compressed: bool) -> Vec<u8> {
+        let Some((x, y)) = self.to_affine() else {
+            return vec![0];
+        };
+
+        let mut bytes = if compressed {
+            vec![2 | y.is_odd() as u8]
+        } else {
+            vec![4]
+        };
+        bytes.extend(x.to_be_bytes());
+
+        if !compressed {
+            bytes.extend(y.to_be_bytes());
+        }
+
+        bytes
+    }
+
+    /// Decode a compressed or uncompressed SEC1 encoding of a point on the
+    /// curve. The identity and non-canonical coordinates are rejected.
+    pub fn from_sec1(bytes: &[u8]) -> Option<Self> {
+        let len = 8 * N;
+
+        let x = FieldElement::<C, N>::from_canonical_be_bytes(bytes.get(1..len + 1)?)?;
+
+        match (bytes[0], bytes.len() - 1) {
+            (2 | 3, l) if l == len => {
+                let y = rhs::<C, N>(&x).sqrt()?;
+                let y = Residue::select(&y, &-y, y.is_odd() != (bytes[0] == 3));
+
+                Some(ProjectivePoint::from_affine_unchecked(x, y))
+            }
+            (4, l) if l == 2 * len => {
+                let y = FieldElement::<C, N>::from_canonical_be_bytes(&bytes[len + 1..])?;
+
+                ProjectivePoint::from_affine(x, y)
+            }
+            _ => None,
+        }
+    }
+
+    /// Doubling, algorithm 3 of Renes-Costello-Batina.
+    pub fn double(&self) -> Self {
+        let a = Residue::from_limbs(C::A);
+        let b3 = Residue::from_limbs(C::B) * Residue::from_u64(3);
+        let (x, y, z) = (self.x, self.y, self.z);
+
+        let t0 = x.square();
+        let t1 = y.square();
+        let t2 = z.square();
+        let t3 = x * y;
+        let t3 = t3 + t3;
+        let z3 = x * z;
+        let z3 = z3 + z3;
+        let x3 = a * z3;
+        let y3 = x3 + b3 * t2;
+        let x3 = t1 - y3;
+        let y3 = x3 * (t1 + y3);
+        let x3 = t3 * x3;
+        let z3 = b3 * z3;
+        let t2 = a * t2;
+        let t3 = a * (t0 - t2) + z3;
+        let z3 = t0 + t0;
+        let t0 = z3 + t0 + t2;
+        let y3 = y3 + t0 * t3;
+        let t2 = y * z;
+        let t2 = t2 + t2;
+        let x3 = x3 - t2 * t3;
+        let z3 = t2 * t1;
+        let z3 = z3 + z3;
+        let z3 = z3 + z3;
+
+        ProjectivePoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// `[s] self`, in constant time.
+    pub fn mul(&self, s: &Scalar<C, N>) -> Self {
+        let k = s.to_le_bytes();
+        let mut acc = ProjectivePoint::IDENTITY;
+
+        for i in (0..64 * N).rev() {
+            acc = acc.double();
+
+            let sum = acc + *self;
+            acc = ProjectivePoint::select(&acc, &sum, (k[i / 8] >> (i % 8)) & 1 == 1);
+        }
+
+        acc
+    }
+
+    /// `[s] G`, in constant time.
+    pub fn mul_base(s: &Scalar<C, N>) -> Self {
+        ProjectivePoint::generator().mul(s)
+    }
+
+    /// `sum [s_i] P_i`, in variable time: only for public inputs.
+    pub fn vartime_multiscalar_mul(scalars: &[Scalar<C, N>], points: &[Self]) -> Self {
+        group::vartime_multiscalar_mul(scalars, points)
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        (self.x * other.z).ct_eq(&(other.x * self.z))
+            & (self.y * other.z).ct_eq(&(other.y * self.z))
+    }
+
+    /// `b` if `choice` else `a`, without branching on `choice`.
+    pub fn select(a: &Self, b: &Self, choice: bool) -> Self {
+        ProjectivePoint {
+            x: Residue::select(&a.x, &b.x, choice),
+            y: Residue::select(&a.y, &b.y, choice),
+            z: Residue::select(&a.z, &b.z, choice),
+        }
+    }
+}
+
+impl<C: Curve<N>, const N: usize> Group for ProjectivePoint<C, N> {
+    const IDENTITY: Self = ProjectivePoint::IDENTITY;
+
+    fn double(&self) -> Self {
+        ProjectivePoint::double(self)
+    }
+}
+
+impl<C: Curve<N>, const N: usize> PartialEq for ProjectivePoint<C, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl<C: Curve<N>, const N: usize> Eq for ProjectivePoint<C, N> {}
+
+impl<C: Curve<N>, const N: usize> Add for ProjectivePoint<C, N> {
+    type Output = Self;
+
+    /// Complete addition, algorithm 1 of Renes-Costello-Batina.
+    fn add(self, rhs: Self) -> Self {
+        let a = Residue::from_limbs(C::A);
+        let b3 = Residue::from_limbs(C::B) * Residue::from_u64(3);
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (rhs.x, rhs.y, rhs.z);
+
+        let t0 = x1 * x2;
+        let t1 = y1 * y2;
+        let t2 = z1 * z2;
+        let t3 = (x1 + y1) * (x2 + y2) - (t0 + t1);
+        let t4 = (x1 + z1) * (x2 + z2) - (t0 + t2);
+        let t5 = (y1 + z1) * (y2 + z2) - (t1 + t2);
+        let z3 = a * t4 + b3 * t2;
+        let x3 = t1 - z3;
+        let z3 = t1 + z3;
+        let y3 = x3 * z3;
+        let t1 = t0 + t0 + t0;
+        let t2 = a * t2;
+        let t4 = b3 * t4;
+        let t1 = t1 + t2;
+        let t2 = a * (t0 - t2);
+        let t4 = t4 + t2;
+        let y3 = y3 + t1 * t4;
+        let x3 = t3 * x3 - t5 * t4;
+        let z3 = t5 * z3 + t3 * t1;
+
+        ProjectivePoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+}
+
+impl<C: Curve<N>, const N: usize> Neg for ProjectivePoint<C, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        ProjectivePoint {
+            x: self.x,
+            y: -self.y,
+            z: self.z,
+        }
+    }
+}
+
+impl<C: Curve<N>, const N: usize> Sub for ProjectivePoint<C, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+/// `x^3 + a x + b`
+fn rhs<C: Curve<N>, const N: usize>(x: &FieldElement<C, N>) -> FieldElement<C, N> {
+    (x.square() + Residue::from_limbs(C::A)) * *x + Residue::from_limbs(C::B)
+}
+
+/// A uniformly random non-zero scalar from the operating system's CSPRNG.
+pub fn random_scalar<C: Curve<N>, const N: usize>() -> Scalar<C, N> {
+    loop {
+        // 64 extra bits make the bias of the reduction negligible.
+        let mut bytes = vec![0u8; 8 * N + 8];
+        rng::fill(&mut bytes);
+
+        let s = Scalar::<C, N>::from_le_bytes(&bytes);
+
+        if !s.is_zero() {
+            return s;
+        }
+    }
+}
+
+// ECDH
+
+/// An ECDH private key, a non-zero scalar.
+#[derive(Clone)]
+pub struct SecretKey<C: Curve<N>, const N: usize>(Scalar<C, N>);
+
+/// An ECDH public key, `[d] G`, never the identity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey<C: Curve<N>, const N: usize>(ProjectivePoint<C, N>);
+
+impl<C: Curve<N>, const N: usize> SecretKey<C, N> {
+    /// A fresh key from the operating system's CSPRNG.
+    pub fn generate() -> Self {
+        SecretKey(random_scalar::<C, N>())
+    }
+
+    /// `None` unless `bytes` is a big-endian integer in `1..n`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let d = Scalar::<C, N>::from_canonical_be_bytes(bytes)?;
+
+        (!d.is_zero()).then_some(SecretKey(d))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_be_bytes()
+    }
+
+    pub fn public_key(&self) -> PublicKey<C, N> {
+        PublicKey(ProjectivePoint::mul_base(&self.0))
+    }
+
+    /// The big-endian x-coordinate of `[d] Q`, or `None` if it is the
+    /// identity, which can't happen for a valid key on a curve of prime
+    /// order.
+    pub fn diffie_hellman(&self, peer: &PublicKey<C, N>) -> Option<Vec<u8>> {
+        let (x, _) = peer.0.mul(&self.0).to_affine()?;
+
+        Some(x.to_be_bytes())
+    }
+}
+
+impl<C: Curve<N>, const N: usize> PublicKey<C, N> {
+    /// `None` if `point` is the identity.
+    pub fn from_point(point: ProjectivePoint<C, N>) -> Option<Self> {
+        (!point.is_identity()).then_some(PublicKey(point))
+    }
+
+    /// `None` unless `bytes` encodes a point on the curve.
+    pub fn from_sec1(bytes: &[u8]) -> Option<Self> {
+        PublicKey::from_point(ProjectivePoint::from_sec1(bytes)?)
+    }
+
+    pub fn to_point(&self) -> ProjectivePoint<C, N> {
+        self.0
+    }
+
+    pub fn to_sec1(&self, compressed: bool) -> Vec<u8> {
+        self.0.to_sec1(compressed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ecc::p256::{ProjectivePoint, PublicKey, SecretKey},
+        test_util::unhex,
+    };
+
+    /// The first P-256 case of the NIST CAVS ECC CDH primitive tests.
+    #[test]
+    fn ecdh() {
+        let key = SecretKey::from_bytes(&unhex(
+            "7d7dc5f71eb29ddaf80d6214632eeae03d9058af1fb6d22ed80badb62bc1a534",
+        ))
+        .unwrap();
+        let peer = PublicKey::from_sec1(&unhex(concat!(
+            "04700c48f77f56584c5cc632ca65640db91b6bacce3a4df6b42ce7cc838833d287",
+            "db71e509e3fd9b060ddb20ba5c51dcc5948d46fbf640dfe0441782cab85fa4ac",
+        )))
+        .unwrap();
+
+        assert_eq!(
+            key.public_key().to_sec1(false),
+            unhex(concat!(
+                "04ead218590119e8876b29146ff89ca61770c4edbbf97d38ce385ed281d8a6b230",
+                "28af61281fd35e2fa7002523acc85a429cb06ee6648325389f59edfce1405141",
+            ))
+        );
+        assert_eq!(
+            key.diffie_hellman(&peer),
+            Some(unhex(
+                "46fc62106420ff012e54a434fbdd2d25ccc5852060561e68040dd7778997bd7b"
+            ))
+        );
+    }
+
+    #[test]
+    fn invalid_keys() {
+        assert_eq!(PublicKey::from_point(ProjectivePoint::IDENTITY), None);
+
+        let (x, y) = ProjectivePoint::generator().to_affine().unwrap();
+        assert_eq!(
+            ProjectivePoint::from_affine(x, y),
+            Some(ProjectivePoint::generator())
+        );
+        assert_eq!(ProjectivePoint::from_affine(x, y + y), None);
+
+        // Off the curve, and the encoding some libraries use for the identity.
+        let mut sec1 = SecretKey::generate().public_key().to_sec1(false);
+        sec1[64] ^= 1;
+        assert_eq!(PublicKey::from_sec1(&sec1), None);
+        assert_eq!(PublicKey::from_sec1(&[0]), None);
+    }
+}
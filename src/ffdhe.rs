@@ -0,0 +1,639 @@
+//! # Finite-field Diffie-Hellman (RFC 7919)
+//! Key agreement in the named groups ffdhe2048 to ffdhe8192 of TLS, with the
+//! same interface as X25519 in [`crate::ecc::curve25519`].
+//!
+//! Each prime is a safe prime `p = 2q + 1`, derived from the digits of `e`,
+//! and the generator `g = 2` has prime order `q`. Secret exponents are as
+//! short as appendix A allows for the strength of the group, and are used
+//! with the constant-time [`Montgomery::pow`].
+//!
+//! A peer's public value `y` must satisfy `1 < y < p - 1` and `y^q = 1`,
+//! which leaves only elements of order `q`. Anything else would let the
+//! peer learn bits of the exponent from the shared secret.
+
+use crate::bigint::{BoxedUint, Montgomery};
+
+/// The named groups of RFC 7919.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Group {
+    Ffdhe2048,
+    Ffdhe3072,
+    Ffdhe4096,
+    Ffdhe6144,
+    Ffdhe8192,
+}
+
+impl Group {
+    /// The safe prime `p`.
+    pub fn prime(self) -> BoxedUint {
+        BoxedUint::from_limbs(self.limbs().to_vec())
+    }
+
+    /// The length of `p`, and of public values and shared secrets, in bytes.
+    pub fn size(self) -> usize {
+        8 * self.limbs().len()
+    }
+
+    fn limbs(self) -> &'static [u64] {
+        match self {
+            Group::Ffdhe2048 => &FFDHE2048,
+            Group::Ffdhe3072 => &FFDHE3072,
+            Group::Ffdhe4096 => &FFDHE4096,
+            Group::Ffdhe6144 => &FFDHE6144,
+            Group::Ffdhe8192 => &FFDHE8192,
+        }
+    }
+
+    /// The length of secret exponents, twice the estimated strength.
+    fn exponent_bits(self) -> usize {
+        match self {
+            Group::Ffdhe2048 => 225,
+            Group::Ffdhe3072 => 275,
+            Group::Ffdhe4096 => 325,
+            Group::Ffdhe6144 => 375,
+            Group::Ffdhe8192 => 400,
+        }
+    }
+
+    fn montgomery(self) -> Montgomery {
+        Montgomery::new(&self.prime()).unwrap()
+    }
+}
+
+/// An FFDHE private key, the exponent `x` in `1..q`.
+#[derive(Clone)]
+pub struct SecretKey {
+    group: Group,
+    x: BoxedUint,
+}
+
+/// An FFDHE public key `y = g^x mod p`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    group: Group,
+    y: BoxedUint,
+}
+
+impl SecretKey {
+    /// A fresh key from the operating system's CSPRNG, with the top bit of
+    /// the exponent set so that every key takes the same time.
+    pub fn generate(group: Group) -> SecretKey {
+        let bits = group.exponent_bits();
+        let top = &BoxedUint::one() << (bits - 1);
+
+        SecretKey {
+            group,
+            x: &BoxedUint::random_bits(bits - 1) + &top,
+        }
+    }
+
+    /// `None` unless `bytes` is a big-endian exponent in `1..q`.
+    pub fn from_bytes(group: Group, bytes: &[u8]) -> Option<SecretKey> {
+        let x = BoxedUint::from_be_bytes(bytes);
+        let q = &group.prime() >> 1;
+
+        (!x.is_zero() && x < q).then_some(SecretKey { group, x })
+    }
+
+    /// The shortest big-endian encoding of the exponent.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.x.to_be_bytes()
+    }
+
+    pub fn group(&self) -> Group {
+        self.group
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            group: self.group,
+            y: self
+                .group
+                .montgomery()
+                .pow(&BoxedUint::from_u64(2), &self.x),
+        }
+    }
+
+    /// The shared secret `y^x mod p` with `peer`, left-padded to the length
+    /// of `p` as TLS 1.3 does, or `None` if `peer` is in another group.
+    pub fn diffie_hellman(&self, peer: &PublicKey) -> Option<Vec<u8>> {
+        if peer.group != self.group {
+            return None;
+        }
+
+        let shared = self.group.montgomery().pow(&peer.y, &self.x);
+
+        shared.to_be_bytes_padded(self.group.size())
+    }
+}
+
+impl PublicKey {
+    /// `None` unless `bytes` is a big-endian `y` with `1 < y < p - 1` and
+    /// `y^q = 1 mod p`, of any length up to that of `p`.
+    pub fn from_bytes(group: Group, bytes: &[u8]) -> Option<PublicKey> {
+        if bytes.len() > group.size() {
+            return None;
+        }
+
+        let y = BoxedUint::from_be_bytes(bytes);
+        let p = group.prime();
+        let p_minus_1 = &p - &BoxedUint::one();
+
+        if y <= BoxedUint::one() || y >= p_minus_1 {
+            return None;
+        }
+
+        let q = &p >> 1;
+        let valid = group.montgomery().pow_vartime(&y, &q) == BoxedUint::one();
+
+        valid.then_some(PublicKey { group, y })
+    }
+
+    /// `y` padded to the length of `p`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.y.to_be_bytes_padded(self.group.size()).unwrap()
+    }
+
+    pub fn group(&self) -> Group {
+        self.group
+    }
+}
+
+// Primes
+
+/// `2^2048 - 2^1984 + ([2^1918 e] + 560316) 2^64 - 1`
+const FFDHE2048: [u64; 32] = [
+    0xffffffffffffffff,
+    0x886b423861285c97,
+    0xc6f34a26c1b2effa,
+    0xc58ef1837d1683b2,
+    0x3bb5fcbc2ec22005,
+    0xc3fe3b1b4c6fad73,
+    0x8e4f1232eef28183,
+    0x9172fe9ce98583ff,
+    0xc03404cd28342f61,
+    0x9e02fce1cdf7e2ec,
+    0x0b07a7c8ee0a6d70,
+    0xae56ede76372bb19,
+    0x1d4f42a3de394df4,
+    0xb96adab760d7f468,
+    0xd108a94bb2c8e3fb,
+    0xbc0ab182b324fb61,
+    0x30acca4f483a797a,
+    0x1df158a136ade735,
+    0xe2a689daf3efe872,
+    0x984f0c70e0e68b77,
+    0xb557135e7f57c935,
+    0x856365553ded1af3,
+    0x2433f51f5f066ed0,
+    0xd3df1ed5d5fd6561,
+    0xf681b202aec4617a,
+    0x7d2fe363630c75d8,
+    0xcc939dce249b3ef9,
+    0xa9e13641146433fb,
+    0xd8b9c583ce2d3695,
+    0xafdc5620273d3cf1,
+    0xadf85458a2bb4a9a,
+    0xffffffffffffffff,
+];
+
+/// `2^3072 - 2^3008 + ([2^2942 e] + 2625351) 2^64 - 1`
+const FFDHE3072: [u64; 48] = [
+    0xffffffffffffffff,
+    0x25e41d2b66c62e37,
+    0x3c1b20ee3fd59d7c,
+    0x0abcd06bfa53ddef,
+    0x1dbf9a42d5c4484e,
+    0xabc521979b0deada,
+    0xe86d2bc522363a0d,
+    0x5cae82ab9c9df69e,
+    0x64f2e21e71f54bff,
+    0xf4fd4452e2d74dd3,
+    0xb4130c93bc437944,
+    0xaefe130985139270,
+    0x598cb0fac186d91c,
+    0x7ad91d2691f7f7ee,
+    0x61b46fc9d6e6c907,
+    0xbc34f4def99c0238,
+    0xde355b3b6519035b,
+    0x886b4238611fcfdc,
+    0xc6f34a26c1b2effa,
+    0xc58ef1837d1683b2,
+    0x3bb5fcbc2ec22005,
+    0xc3fe3b1b4c6fad73,
+    0x8e4f1232eef28183,
+    0x9172fe9ce98583ff,
+    0xc03404cd28342f61,
+    0x9e02fce1cdf7e2ec,
+    0x0b07a7c8ee0a6d70,
+    0xae56ede76372bb19,
+    0x1d4f42a3de394df4,
+    0xb96adab760d7f468,
+    0xd108a94bb2c8e3fb,
+    0xbc0ab182b324fb61,
+    0x30acca4f483a797a,
+    0x1df158a136ade735,
+    0xe2a689daf3efe872,
+    0x984f0c70e0e68b77,
+    0xb557135e7f57c935,
+    0x856365553ded1af3,
+    0x2433f51f5f066ed0,
+    0xd3df1ed5d5fd6561,
+    0xf681b202aec4617a,
+    0x7d2fe363630c75d8,
+    0xcc939dce249b3ef9,
+    0xa9e13641146433fb,
+    0xd8b9c583ce2d3695,
+    0xafdc5620273d3cf1,
+    0xadf85458a2bb4a9a,
+    0xffffffffffffffff,
+];
+
+/// `2^4096 - 2^4032 + ([2^3966 e] + 5736041) 2^64 - 1`
+const FFDHE4096: [u64; 64] = [
+    0xffffffffffffffff,
+    0xc68a007e5e655f6a,
+    0x4db5a851f44182e1,
+    0x8ec9b55a7f88a46b,
+    0x0a8291cdcec97dcf,
+    0x2a4ecea9f98d0acc,
+    0x1a1db93d7140003c,
+    0x092999a333cb8b7a,
+    0x6dc778f971ad0038,
+    0xa907600a918130c4,
+    0xed6a1e012d9e6832,
+    0x7135c886efb4318a,
+    0x87f55ba57e31cc7a,
+    0x7763cf1d55034004,
+    0xac7d5f42d69f6d18,
+    0x7930e9e4e58857b6,
+    0x6e6f52c3164df4fb,
+    0x25e41d2b669e1ef1,
+    0x3c1b20ee3fd59d7c,
+    0x0abcd06bfa53ddef,
+    0x1dbf9a42d5c4484e,
+    0xabc521979b0deada,
+    0xe86d2bc522363a0d,
+    0x5cae82ab9c9df69e,
+    0x64f2e21e71f54bff,
+    0xf4fd4452e2d74dd3,
+    0xb4130c93bc437944,
+    0xaefe130985139270,
+    0x598cb0fac186d91c,
+    0x7ad91d2691f7f7ee,
+    0x61b46fc9d6e6c907,
+    0xbc34f4def99c0238,
+    0xde355b3b6519035b,
+    0x886b4238611fcfdc,
+    0xc6f34a26c1b2effa,
+    0xc58ef1837d1683b2,
+    0x3bb5fcbc2ec22005,
+    0xc3fe3b1b4c6fad73,
+    0x8e4f1232eef28183,
+    0x9172fe9ce98583ff,
+    0xc03404cd28342f61,
+    0x9e02fce1cdf7e2ec,
+    0x0b07a7c8ee0a6d70,
+    0xae56ede76372bb19,
+    0x1d4f42a3de394df4,
+    0xb96adab760d7f468,
+    0xd108a94bb2c8e3fb,
+    0xbc0ab182b324fb61,
+    0x30acca4f483a797a,
+    0x1df158a136ade735,
+    0xe2a689daf3efe872,
+    0x984f0c70e0e68b77,
+    0xb557135e7f57c935,
+    0x856365553ded1af3,
+    0x2433f51f5f066ed0,
+    0xd3df1ed5d5fd6561,
+    0xf681b202aec4617a,
+    0x7d2fe363630c75d8,
+    0xcc939dce249b3ef9,
+    0xa9e13641146433fb,
+    0xd8b9c583ce2d3695,
+    0xafdc5620273d3cf1,
+    0xadf85458a2bb4a9a,
+    0xffffffffffffffff,
+];
+
+/// `2^6144 - 2^6080 + ([2^6014 e] + 15705020) 2^64 - 1`
+const FFDHE6144: [u64; 96] = [
+    0xffffffffffffffff,
+    0xa40e329cd0e40e65,
+    0xa41d570d7938dad4,
+    0x62a69526d43161c1,
+    0x3fdd4a8e9adb1e69,
+    0x5b3b71f9dc6b80d6,
+    0xec9d1810c6272b04,
+    0x8ccf2dd5cacef403,
+    0xe49f5235c95b9117,
+    0x505dc82db854338a,
+    0x62292c311562a846,
+    0xd72b03746ae77f5e,
+    0xf9c9091b462d538c,
+    0x0ae8db5847a67cbe,
+    0xb3a739c122611682,
+    0xeeaac0232a281bf6,
+    0x94c6651e77caf992,
+    0x763e4e4b94b2bbc1,
+    0x587e38da0077d9b4,
+    0x7fb29f8c183023c3,
+    0x0abec1fff9e3a26e,
+    0xa00ef092350511e3,
+    0xb855322edb6340d8,
+    0xa52471f7a9a96910,
+    0x388147fb4cfdb477,
+    0x9b1f5c3e4e46041f,
+    0xcdad0657fccfec71,
+    0xb38e8c334c701c3a,
+    0x917bdd64b1c0fd4c,
+    0x3bb454329b7624c8,
+    0x23ba4442caf53ea6,
+    0x4e677d2c38532a3a,
+    0x0bfd64b645036c7a,
+    0xc68a007e5e0dd902,
+    0x4db5a851f44182e1,
+    0x8ec9b55a7f88a46b,
+    0x0a8291cdcec97dcf,
+    0x2a4ecea9f98d0acc,
+    0x1a1db93d7140003c,
+    0x092999a333cb8b7a,
+    0x6dc778f971ad0038,
+    0xa907600a918130c4,
+    0xed6a1e012d9e6832,
+    0x7135c886efb4318a,
+    0x87f55ba57e31cc7a,
+    0x7763cf1d55034004,
+    0xac7d5f42d69f6d18,
+    0x7930e9e4e58857b6,
+    0x6e6f52c3164df4fb,
+    0x25e41d2b669e1ef1,
+    0x3c1b20ee3fd59d7c,
+    0x0abcd06bfa53ddef,
+    0x1dbf9a42d5c4484e,
+    0xabc521979b0deada,
+    0xe86d2bc522363a0d,
+    0x5cae82ab9c9df69e,
+    0x64f2e21e71f54bff,
+    0xf4fd4452e2d74dd3,
+    0xb4130c93bc437944,
+    0xaefe130985139270,
+    0x598cb0fac186d91c,
+    0x7ad91d2691f7f7ee,
+    0x61b46fc9d6e6c907,
+    0xbc34f4def99c0238,
+    0xde355b3b6519035b,
+    0x886b4238611fcfdc,
+    0xc6f34a26c1b2effa,
+    0xc58ef1837d1683b2,
+    0x3bb5fcbc2ec22005,
+    0xc3fe3b1b4c6fad73,
+    0x8e4f1232eef28183,
+    0x9172fe9ce98583ff,
+    0xc03404cd28342f61,
+    0x9e02fce1cdf7e2ec,
+    0x0b07a7c8ee0a6d70,
+    0xae56ede76372bb19,
+    0x1d4f42a3de394df4,
+    0xb96adab760d7f468,
+    0xd108a94bb2c8e3fb,
+    0xbc0ab182b324fb61,
+    0x30acca4f483a797a,
+    0x1df158a136ade735,
+    0xe2a689daf3efe872,
+    0x984f0c70e0e68b77,
+    0xb557135e7f57c935,
+    0x856365553ded1af3,
+    0x2433f51f5f066ed0,
+    0xd3df1ed5d5fd6561,
+    0xf681b202aec4617a,
+    0x7d2fe363630c75d8,
+    0xcc939dce249b3ef9,
+    0xa9e13641146433fb,
+    0xd8b9c583ce2d3695,
+    0xafdc5620273d3cf1,
+    0xadf85458a2bb4a9a,
+    0xffffffffffffffff,
+];
+
+/// `2^8192 - 2^8128 + ([2^8062 e] + 10965728) 2^64 - 1`
+const FFDHE8192: [u64; 128] = [
+    0xffffffffffffffff,
+    0xd68c8bb7c5c6424c,
+    0x011e2a94838ff88c,
+    0x0822e506a9f4614e,
+    0x97d11d49f7a8443d,
+    0xa6bbfde530677f0d,
+    0x2f741ef8c1fe86fe,
+    0xfafabe1c5d71a87e,
+    0xded2fbabfbe58a30,
+    0xb6855dfe72b0a66e,
+    0x1efc8ce0ba8a4fe8,
+    0x83f81d4a3f2fa457,
+    0xa1fe3075a577e231,
+    0xd5b8019488d9c0a0,
+    0x624816cdad9a95f9,
+    0x99e9e31650c1217b,
+    0x51aa691e0e423cfc,
+    0x1c217e6c3826e52c,
+    0x51a8a93109703fee,
+    0xbb7099876a460e74,
+    0x541fc68c9c86b022,
+    0x59160cc046fd8251,
+    0x2846c0ba35c35f5c,
+    0x54504ac78b758282,
+    0x29388839d2af05e4,
+    0xcb2c0f1cc01bd702,
+    0x555b2f747c932665,
+    0x86b63142a3ab8829,
+    0x0b8cc3bdf64b10ef,
+    0x687feb69edd1cc5e,
+    0xfdb23fcec9509d43,
+    0x1e425a31d951ae64,
+    0x36ad004cf600c838,
+    0xa40e329ccff46aaa,
+    0xa41d570d7938dad4,
+    0x62a69526d43161c1,
+    0x3fdd4a8e9adb1e69,
+    0x5b3b71f9dc6b80d6,
+    0xec9d1810c6272b04,
+    0x8ccf2dd5cacef403,
+    0xe49f5235c95b9117,
+    0x505dc82db854338a,
+    0x62292c311562a846,
+    0xd72b03746ae77f5e,
+    0xf9c9091b462d538c,
+    0x0ae8db5847a67cbe,
+    0xb3a739c122611682,
+    0xeeaac0232a281bf6,
+    0x94c6651e77caf992,
+    0x763e4e4b94b2bbc1,
+    0x587e38da0077d9b4,
+    0x7fb29f8c183023c3,
+    0x0abec1fff9e3a26e,
+    0xa00ef092350511e3,
+    0xb855322edb6340d8,
+    0xa52471f7a9a96910,
+    0x388147fb4cfdb477,
+    0x9b1f5c3e4e46041f,
+    0xcdad0657fccfec71,
+    0xb38e8c334c701c3a,
+    0x917bdd64b1c0fd4c,
+    0x3bb454329b7624c8,
+    0x23ba4442caf53ea6,
+    0x4e677d2c38532a3a,
+    0x0bfd64b645036c7a,
+    0xc68a007e5e0dd902,
+    0x4db5a851f44182e1,
+    0x8ec9b55a7f88a46b,
+    0x0a8291cdcec97dcf,
+    0x2a4ecea9f98d0acc,
+    0x1a1db93d7140003c,
+    0x092999a333cb8b7a,
+    0x6dc778f971ad0038,
+    0xa907600a918130c4,
+    0xed6a1e012d9e6832,
+    0x7135c886efb4318a,
+    0x87f55ba57e31cc7a,
+    0x7763cf1d55034004,
+    0xac7d5f42d69f6d18,
+    0x7930e9e4e58857b6,
+    0x6e6f52c3164df4fb,
+    0x25e41d2b669e1ef1,
+    0x3c1b20ee3fd59d7c,
+    0x0abcd06bfa53ddef,
+    0x1dbf9a42d5c4484e,
+    0xabc521979b0deada,
+    0xe86d2bc522363a0d,
+    0x5cae82ab9c9df69e,
+    0x64f2e21e71f54bff,
+    0xf4fd4452e2d74dd3,
+    0xb4130c93bc437944,
+    0xaefe130985139270,
+    0x598cb0fac186d91c,
+    0x7ad91d2691f7f7ee,
+    0x61b46fc9d6e6c907,
+    0xbc34f4def99c0238,
+    0xde355b3b6519035b,
+    0x886b4238611fcfdc,
+    0xc6f34a26c1b2effa,
+    0xc58ef1837d1683b2,
+    0x3bb5fcbc2ec22005,
+    0xc3fe3b1b4c6fad73,
+    0x8e4f1232eef28183,
+    0x9172fe9ce98583ff,
+    0xc03404cd28342f61,
+    0x9e02fce1cdf7e2ec,
+    0x0b07a7c8ee0a6d70,
+    0xae56ede76372bb19,
+    0x1d4f42a3de394df4,
+    0xb96adab760d7f468,
+    0xd108a94bb2c8e3fb,
+    0xbc0ab182b324fb61,
+    0x30acca4f483a797a,
+    0x1df158a136ade735,
+    0xe2a689daf3efe872,
+    0x984f0c70e0e68b77,
+    0xb557135e7f57c935,
+    0x856365553ded1af3,
+    0x2433f51f5f066ed0,
+    0xd3df1ed5d5fd6561,
+    0xf681b202aec4617a,
+    0x7d2fe363630c75d8,
+    0xcc939dce249b3ef9,
+    0xa9e13641146433fb,
+    0xd8b9c583ce2d3695,
+    0xafdc5620273d3cf1,
+    0xadf85458a2bb4a9a,
+    0xffffffffffffffff,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex;
+
+    const GROUPS: [Group; 5] = [
+        Group::Ffdhe2048,
+        Group::Ffdhe3072,
+        Group::Ffdhe4096,
+        Group::Ffdhe6144,
+        Group::Ffdhe8192,
+    ];
+
+    /// RFC 7919, appendix A: the first and last 128 bits of each prime.
+    #[test]
+    fn primes() {
+        for (group, last) in GROUPS.into_iter().zip([
+            "886b423861285c97ffffffffffffffff",
+            "25e41d2b66c62e37ffffffffffffffff",
+            "c68a007e5e655f6affffffffffffffff",
+            "a40e329cd0e40e65ffffffffffffffff",
+            "d68c8bb7c5c6424cffffffffffffffff",
+        ]) {
+            let p = group.prime().to_be_bytes();
+
+            assert_eq!(p.len(), group.size());
+            assert_eq!(p[..16], unhex("ffffffffffffffffadf85458a2bb4a9a"));
+            assert_eq!(p[p.len() - 16..], unhex(last));
+        }
+    }
+
+    #[test]
+    fn agreement() {
+        for group in [Group::Ffdhe2048, Group::Ffdhe3072] {
+            let alice = SecretKey::generate(group);
+            let bob = SecretKey::generate(group);
+
+            let alice_public =
+                PublicKey::from_bytes(group, &alice.public_key().to_bytes()).unwrap();
+            let bob_public = PublicKey::from_bytes(group, &bob.public_key().to_bytes()).unwrap();
+            assert_eq!(alice_public, alice.public_key());
+
+            let shared = alice.diffie_hellman(&bob_public).unwrap();
+            assert_eq!(shared.len(), group.size());
+            assert_eq!(Some(shared), bob.diffie_hellman(&alice_public));
+
+            let bob = SecretKey::from_bytes(group, &bob.to_bytes()).unwrap();
+            assert_eq!(bob.public_key(), bob_public);
+        }
+
+        let other = SecretKey::generate(Group::Ffdhe3072).public_key();
+        assert_eq!(
+            SecretKey::generate(Group::Ffdhe2048).diffie_hellman(&other),
+            None
+        );
+    }
+
+    #[test]
+    fn bad_keys() {
+        let group = Group::Ffdhe2048;
+        let p = group.prime();
+        let one = BoxedUint::one();
+        let q = &p >> 1;
+
+        // 0, 1, p - 1 and p + 1 are below or above the range, and p - 2 has
+        // order 2q since 2 is a square and -1 is not.
+        for y in [
+            BoxedUint::zero(),
+            one.clone(),
+            &p - &one,
+            p.clone(),
+            &p + &one,
+            &p - &BoxedUint::from_u64(2),
+        ] {
+            assert_eq!(PublicKey::from_bytes(group, &y.to_be_bytes()), None);
+        }
+
+        let two = BoxedUint::from_u64(2)
+            .to_be_bytes_padded(group.size() + 1)
+            .unwrap();
+        assert_eq!(PublicKey::from_bytes(group, &two), None);
+        assert!(PublicKey::from_bytes(group, &two[1..]).is_some());
+
+        assert!(SecretKey::from_bytes(group, &[]).is_none());
+        assert!(SecretKey::from_bytes(group, &q.to_be_bytes()).is_none());
+        assert!(SecretKey::from_bytes(group, &(&q - &one).to_be_bytes()).is_some());
+    }
+}
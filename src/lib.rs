@@ -1,5 +1,14 @@
 #![feature(array_chunks)]
 #![feature(iter_array_chunks)]
 
+pub mod ascon;
+pub mod bigint;
 pub mod ecc;
+pub mod ffdhe;
+pub mod rng;
+pub mod rsa;
 pub mod sha;
+pub mod sss;
+
+#[cfg(test)]
+mod test_util;
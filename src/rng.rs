@@ -0,0 +1,18 @@
+//! Randomness from the operating system's CSPRNG.
+
+use std::{fs::File, io::Read};
+
+/// Fill `buf` with random bytes.
+pub fn fill(buf: &mut [u8]) {
+    File::open("/dev/urandom")
+        .and_then(|mut f| f.read_exact(buf))
+        .expect("failed to read from /dev/urandom");
+}
+
+/// `N` random bytes.
+pub fn bytes<const N: usize>() -> [u8; N] {
+    let mut buf = [0u8; N];
+    fill(&mut buf);
+
+    buf
+}
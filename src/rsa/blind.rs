@@ -0,0 +1,450 @@
+//! # RSA blind signatures (RFC 9474)
+//! RSABSSA with SHA-384: a client blinds a message, the signer signs the
+//! blinded message without learning it, and the client unblinds the result
+//! into an ordinary RSASSA-PSS signature of the message, which the signer
+//! can't link to the signing request.
+//!
+//! 1. The client calls [`prepare`] on the message, and signs and verifies
+//!    the prepared message from then on.
+//! 2. The client calls [`blind`] and sends the blinded message.
+//! 3. The signer answers with [`blind_sign`].
+//! 4. The client calls [`finalize`] to get the signature, which anyone can
+//!    check with [`verify`].
+//!
+//! The signing key must not be used for anything else, since [`blind_sign`]
+//! signs whatever it is given.
+
+use super::{
+    key::{PrivateKey, PublicKey},
+    pss, Digest,
+};
+use crate::{bigint::BoxedUint, rng, sha::sha384::Sha384};
+
+/// The four variants of section 5, named `RSABSSA-SHA384-...` in the RFC.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// `PSS-Randomized`, the one the RFC recommends: a salt of 48 bytes and
+    /// a random prefix on the message.
+    #[default]
+    PssRandomized,
+    /// `PSSZERO-Randomized`: deterministic PSS and a random prefix.
+    PssZeroRandomized,
+    /// `PSS-Deterministic`: a salt of 48 bytes and the message as it is.
+    PssDeterministic,
+    /// `PSSZERO-Deterministic`: both deterministic, so signatures are
+    /// unique, for applications that need to check for duplicates.
+    PssZeroDeterministic,
+}
+
+impl Variant {
+    fn salt_len(self) -> usize {
+        match self {
+            Variant::PssRandomized | Variant::PssDeterministic => Sha384::OUTPUT_LEN,
+            Variant::PssZeroRandomized | Variant::PssZeroDeterministic => 0,
+        }
+    }
+
+    fn is_randomized(self) -> bool {
+        matches!(self, Variant::PssRandomized | Variant::PssZeroRandomized)
+    }
+}
+
+/// The client's secret `r^-1 mod n` between [`blind`] and [`finalize`].
+pub struct Blinding {
+    inv: BoxedUint,
+}
+
+/// `msg` with a random 32-byte prefix for the randomized variants, or as it
+/// is for the deterministic ones.
+pub fn prepare(variant: Variant, msg: &[u8]) -> Vec<u8> {
+    if variant.is_randomized() {
+        [&rng::bytes::<32>()[..], msg].concat()
+    } else {
+        msg.to_vec()
+    }
+}
+
+/// The blinded message `m r^e mod n` for the PSS encoding `m` of the
+/// prepared message, and the blinding to finalize the signature with.
+/// `None` if the key is too short for the encoding, or if `m` or `r` shares
+/// a factor with `n`, which happens only with negligible probability.
+pub fn blind(key: &PublicKey, variant: Variant, msg: &[u8]) -> Option<(Vec<u8>, Blinding)> {
+    let mut salt = vec![0u8; variant.salt_len()];
+    rng::fill(&mut salt);
+
+    blind_with(key, variant, msg, &salt, &BoxedUint::random_below(key.n()))
+}
+
+/// [`blind`] with the given salt and blinding factor `r`.
+fn blind_with(
+    key: &PublicKey,
+    variant: Variant,
+    msg: &[u8],
+    salt: &[u8],
+    r: &BoxedUint,
+) -> Option<(Vec<u8>, Blinding)> {
+    debug_assert_eq!(salt.len(), variant.salt_len());
+
+    let em = pss::encode::<Sha384>(msg, key.n().bits() - 1, salt)?;
+    let m = BoxedUint::from_be_bytes(&em);
+
+    if m.gcd(key.n()) != BoxedUint::one() {
+        return None;
+    }
+
+    let inv = r.mod_inverse(key.n())?;
+    let z = &(&m * &key.encrypt_raw(r)?) % key.n();
+
+    Some((z.to_be_bytes_padded(key.size())?, Blinding { inv }))
+}
+
+/// The signer's `z^d mod n` of the blinded message `z`, or `None` if it is
+/// not `k` bytes long and below `n`.
+pub fn blind_sign(key: &PrivateKey, blinded_msg: &[u8]) -> Option<Vec<u8>> {
+    let public_key = key.public_key();
+
+    if blinded_msg.len() != public_key.size() {
+        return None;
+    }
+
+    let m = BoxedUint::from_be_bytes(blinded_msg);
+    let s = key.decrypt_raw(&m)?;
+
+    if public_key.encrypt_raw(&s)? != m {
+        return None;
+    }
+
+    s.to_be_bytes_padded(public_key.size())
+}
+
+/// The signature of the prepared message, from the signer's blinded
+/// signature, or `None` if it doesn't verify.
+pub fn finalize(
+    key: &PublicKey,
+    variant: Variant,
+    msg: &[u8],
+    blind_sig: &[u8],
+    blinding: Blinding,
+) -> Option<Vec<u8>> {
+    if blind_sig.len() != key.size() {
+        return None;
+    }
+
+    let z = BoxedUint::from_be_bytes(blind_sig);
+    let s = &(&z * &blinding.inv) % key.n();
+    let sig = s.to_be_bytes_padded(key.size())?;
+
+    verify(key, variant, msg, &sig).then_some(sig)
+}
+
+/// Whether `sig` is a signature of the prepared message, i.e. an
+/// RSASSA-PSS signature with SHA-384 and the salt length of `variant`.
+pub fn verify(key: &PublicKey, variant: Variant, msg: &[u8], sig: &[u8]) -> bool {
+    pss::verify::<Sha384>(key, msg, sig, variant.salt_len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex;
+
+    /// The key of RFC 9474, appendix A.
+    fn key() -> PrivateKey {
+        let int = |s: &str| BoxedUint::from_be_bytes(&unhex(s));
+
+        PrivateKey::from_components(
+            int(concat!(
+                "e1f4d7a34802e27c7392a3cea32a262a34dc3691bd87f3f310dc756734889305",
+                "59c120fd0410194fb8a0da55bd0b81227e843fdca6692ae80e5a5d414116d480",
+                "3fca7d8c30eaaae57e44a1816ebb5c5b0606c536246c7f11985d731684150b63",
+                "c9a3ad9e41b04c0b5b27cb188a692c84696b742a80d3cd00ab891f2457443dad",
+                "feba6d6daf108602be26d7071803c67105a5426838e6889d77e8474b29244cef",
+                "af418e381b312048b457d73419213063c60ee7b0d81820165864fef93523c963",
+                "5c22210956e53a8d96322493ffc58d845368e2416e078e5bcb5d2fd68ae6acfa",
+                "54f9627c42e84a9d3f2774017e32ebca06308a12ecc290c7cd1156dcccfb2311",
+            )),
+            int(concat!(
+                "c601a9caea66dc3835827b539db9df6f6f5ae77244692780cd334a006ab353c8",
+                "06426b60718c05245650821d39445d3ab591ed10a7339f15d83fe13f6a3dfb20",
+                "b9452c6a9b42eaa62a68c970df3cadb2139f804ad8223d56108dfde30ba7d367",
+                "e9b0a7a80c4fdba2fd9dde6661fc73fc2947569d2029f2870fc02d8325acf28c",
+                "9afa19ecf962daa7916e21afad09eb62fe9f1cf91b77dc879b7974b490d3ebd2",
+                "e95426057f35d0a3c9f45f79ac727ab81a519a8b9285932d9b2e5ccd347e59f3",
+                "f32ad9ca359115e7da008ab7406707bd0e8e185a5ed8758b5ba266e8828f8d86",
+                "3ae133846304a2936ad7bc7c9803879d2fc4a28e69291d73dbd799f8bc238385",
+            )),
+            BoxedUint::from_u64(65537),
+            int(concat!(
+                "0d43242aefe1fb2c13fbc66e20b678c4336d20b1808c558b6e62ad16a2870771",
+                "80b177e1f01b12f9c6cd6c52630257ccef26a45135a990928773f3bd2fc01a31",
+                "3f1dac97a51cec71cb1fd7efc7adffdeb05f1fb04812c924ed7f4a8269925dad",
+                "88bd7dcfbc4ef01020ebfc60cb3e04c54f981fdbd273e69a8a58b8ceb7c2d83f",
+                "bcbd6f784d052201b88a9848186f2a45c0d2826870733e6fd9aa46983e0a6e82",
+                "e35ca20a439c5ee7b502a9062e1066493bdadf8b49eb30d9558ed85abc7afb29",
+                "b3c9bc644199654a4676681af4babcea4e6f71fe4565c9c1b85d9985b84ec1ab",
+                "f1a820a9bbebee0df1398aae2c85ab580a9f13e7743afd3108eb32100b870648",
+                "fa6bc17e8abac4d3c99246b1f0ea9f7f93a5dd5458c56d9f3f81ff2216b3c368",
+                "0a13591673c43194d8e6fc93fc1e37ce2986bd628ac48088bc723d8fbe293861",
+                "ca7a9f4a73e9fa63b1b6d0074f5dea2a624c5249ff3ad811b6255b299d6bc545",
+                "1ba7477f19c5a0db690c3e6476398b1483d10314afd38bbaf6e2fbdbcd62c3ca",
+                "9797a420ca6034ec0a83360a3ee2adf4b9d4ba29731d131b099a38d6a23cc463",
+                "db754603211260e99d19affc902c915d7854554aabf608e3ac52c19b8aa26ae0",
+                "42249b17b2d29669b5c859103ee53ef9bdc73ba3c6b537d5c34b6d8f034671d7",
+                "f3a8a6966cc4543df223565343154140fd7391c7e7be03e241f4ecfeb877a051",
+            )),
+        )
+        .unwrap()
+    }
+
+    const MSG: &str = concat!(
+        "8f3dc6fb8c4a02f4d6352edf0907822c1210a9b32f9bdda4c45a698c80023aa6",
+        "b59f8cfec5fdbb36331372ebefedae7d",
+    );
+    const SALT: &str = concat!(
+        "051722b35f458781397c3a671a7d3bd3096503940e4c4f1aaa269d60300ce449",
+        "555cd7340100df9d46944c5356825abf",
+    );
+    const INV: &str = concat!(
+        "80682c48982407b489d53d1261b19ec8627d02b8cda5336750b8cee332ae260d",
+        "e57b02d72609c1e0e9f28e2040fc65b6f02d56dbd6aa9af8fde656f70495dfb7",
+        "23ba01173d4707a12fddac628ca29f3e32340bd8f7ddb557cf819f6b01e445ad",
+        "96f874ba235584ee71f6581f62d4f43bf03f910f6510deb85e8ef06c7f09d979",
+        "4a008be7ff2529f0ebb69decef646387dc767b74939265fec0223aa6d84d2a8a",
+        "1cc912d5ca25b4e144ab8f6ba054b54910176d5737a2cff011da431bd5f2a0d2",
+        "d66b9e70b39f4b050e45c0d9c16f02deda9ddf2d00f3e4b01037d7029cd49c2d",
+        "46a8e1fc2c0c17520af1f4b5e25ba396afc4cd60c494a4c426448b35b49635b3",
+        "37cfb08e7c22a39b256dd032c00adddafb51a627f99a0e1704170ac1f1912e49",
+        "d9db10ec04c19c58f420212973e0cb329524223a6aa56c7937c5dffdb5d966b6",
+        "cd4cbc26f3201dd25c80960a1a111b32947bb78973d269fac7f5186530930ed1",
+        "9f68507540eed9e1bab8b00f00d8ca09b3f099aae46180e04e3584bd7ca054df",
+        "18a1504b89d1d1675d0966c4ae1407be325cdf623cf13ff13e4a28b594d59e3e",
+        "adbadf6136eee7a59d6a444c9eb4e2198e8a974f27a39eb63af2c9af3870488b",
+        "8adaad444674f512133ad80b9220e09158521614f1faadfe8505ef57b7df6813",
+        "048603f0dd04f4280177a11380fbfc861dbcbd7418d62155248dad5fdec0991f",
+    );
+
+    /// The client's and signer's side of [`Variant`] on `msg`, with [`SALT`]
+    /// if it has one and the blinding factor `1 / INV`.
+    fn sign(key: &PrivateKey, variant: Variant, msg: &[u8]) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        let public_key = key.public_key();
+        let salt = &unhex(SALT)[..variant.salt_len()];
+        let r = BoxedUint::from_be_bytes(&unhex(INV))
+            .mod_inverse(public_key.n())
+            .unwrap();
+
+        let (blinded_msg, blinding) = blind_with(public_key, variant, msg, salt, &r).unwrap();
+        let blind_sig = blind_sign(key, &blinded_msg).unwrap();
+        let sig = finalize(public_key, variant, msg, &blind_sig, blinding).unwrap();
+
+        (blinded_msg, blind_sig, sig)
+    }
+
+    /// RFC 9474, appendix A: RSABSSA-SHA384-PSS-Deterministic.
+    #[test]
+    fn pss_deterministic() {
+        let key = key();
+        let (blinded_msg, blind_sig, sig) = sign(&key, Variant::PssDeterministic, &unhex(MSG));
+
+        assert_eq!(
+            blinded_msg,
+            unhex(concat!(
+                "10c166c6a711e81c46f45b18e5873cc4f494f003180dd7f115585d871a289302",
+                "59654fe28a54dab319cc5011204c8373b50a57b0fdc7a678bd74c523259dfe4f",
+                "d5ea9f52f170e19dfa332930ad1609fc8a00902d725cfe50685c95e5b2968c9a",
+                "2828a21207fcf393d15f849769e2af34ac4259d91dfd98c3a707c509e1af5564",
+                "7efaa31290ddf48e0133b798562af5eabd327270ac2fb6c594734ce339a14ea4",
+                "fe1b9a2f81c0bc230ca523bda17ff42a377266bc2778a274c0ae5ec5a8cbbe36",
+                "4fcf0d2403f7ee178d77ff28b67a20c7ceec009182dbcaa9bc99b51ebbf13b7d",
+                "542be337172c6474f2cd3561219fe0dfa3fb207cff89632091ab841cf38d8aa8",
+                "8af6891539f263adb8eac6402c41b6ebd72984e43666e537f5f5fe27b2b5aa11",
+                "4957e9a580730308a5f5a9c63a1eb599f093ab401d0c6003a451931b6d124180",
+                "305705845060ebba6b0036154fcef3e5e9f9e4b87e8f084542fd1dd67e7782a5",
+                "585150181c01eb6d90cb95883837384a5b91dbb606f266059ecc51b5acbaa280",
+                "e45cfd2eec8cc1cdb1b7211c8e14805ba683f9b78824b2eb005bc8a7d7179a36",
+                "c152cb87c8219e5569bba911bb32a1b923ca83de0e03fb10fba75d85c55907dd",
+                "a5a2606bf918b056c3808ba496a4d95532212040a5f44f37e1097f26dc27b98a",
+                "51837daa78f23e532156296b64352669c94a8a855acf30533d8e0594ace7c442",
+            ))
+        );
+        assert_eq!(
+            blind_sig,
+            unhex(concat!(
+                "364f6a40dbfbc3bbb257943337eeff791a0f290898a6791283bba581d9eac90a",
+                "6376a837241f5f73a78a5c6746e1306ba3adab6067c32ff69115734ce014d354",
+                "e2f259d4cbfb890244fd451a497fe6ecf9aa90d19a2d441162f7eaa7ce3fc4e8",
+                "9fd4e76b7ae585be2a2c0fd6fb246b8ac8d58bcb585634e30c9168a434786fe5",
+                "e0b74bfe8187b47ac091aa571ffea0a864cb906d0e28c77a00e8cd8f6aba4317",
+                "a8cc7bf32ce566bd1ef80c64de041728abe087bee6cadd0b7062bde5ceef308a",
+                "23bd1ccc154fd0c3a26110df6193464fc0d24ee189aea8979d722170ba945fdc",
+                "ce9b1b4b63349980f3a92dc2e5418c54d38a862916926b3f9ca270a8cf40dfb9",
+                "772bfbdd9a3e0e0892369c18249211ba857f35963d0e05d8da98f1aa0c6bba58",
+                "f47487b8f663e395091275f82941830b050b260e4767ce2fa903e75ff8970c98",
+                "bfb3a08d6db91ab1746c86420ee2e909bf681cac173697135983c3594b2def67",
+                "3736220452fde4ddec867d40ff42dd3da36c84e3e52508b891a00f50b4f62d11",
+                "2edb3b6b6cc3dbd546ba10f36b03f06c0d82aeec3b25e127af545fac28e1613a",
+                "0517a6095ad18a98ab79f68801e05c175e15bae21f821e80c80ab4fdec6fb34c",
+                "a315e194502b8f3dcf7892b511aee45060e3994cd15e003861bc7220a2babd7b",
+                "40eda03382548a34a7110f9b1779bf3ef6011361611e6bc5c0dc851e1509de1a",
+            ))
+        );
+        assert_eq!(
+            sig,
+            unhex(concat!(
+                "6fef8bf9bc182cd8cf7ce45c7dcf0e6f3e518ae48f06f3c670c649ac737a8b81",
+                "19a34d51641785be151a697ed7825fdfece82865123445eab03eb4bb91cecf4d",
+                "6951738495f8481151b62de869658573df4e50a95c17c31b52e154ae26a04067",
+                "d5ecdc1592c287550bb982a5bb9c30fd53a768cee6baabb3d483e9f1e2da954c",
+                "7f4cf492fe3944d2fe456c1ecaf0840369e33fb4010e6b44bb1d721840513524",
+                "d8e9a3519f40d1b81ae34fb7a31ee6b7ed641cb16c2ac999004c2191de020145",
+                "7523f5a4700dd649267d9286f5c1d193f1454c9f868a57816bf5ff76c838a2ee",
+                "b616a3fc9976f65d4371deecfbab29362caebdff69c635fe5a2113da4d4d8c24",
+                "f0b16a0584fa05e80e607c5d9a2f765f1f069f8d4da21f27c2a3b5c984b4ab24",
+                "899bef46c6d9323df4862fe51ce300fca40fb539c3bb7fe2dcc9409e425f2d3b",
+                "95e70e9c49c5feb6ecc9d43442c33d50003ee936845892fb8be475647da9a080",
+                "f5bc7f8a716590b3745c2209fe05b17992830ce15f32c7b22cde755c8a2fe50b",
+                "d814a0434130b807dc1b7218d4e85342d70695a5d7f29306f25623ad1e8aa08e",
+                "f71b54b8ee447b5f64e73d09bdd6c3b7ca224058d7c67cc7551e9241688ada12",
+                "d859cb7646fbd3ed8b34312f3b49d69802f0eaa11bc4211c2f7a29cd5c01ed01",
+                "a39001c5856fab36228f5ee2f2e1110811872fe7c865c42ed59029c706195d52",
+            ))
+        );
+    }
+
+    /// The other variants on the same key, message, salt and blinding, with
+    /// a fixed prefix for the randomized ones. Known answers from a Python
+    /// implementation of the RFC.
+    #[test]
+    fn variants() {
+        let key = key();
+        let msg = unhex(MSG);
+        let prefixed = [
+            &unhex("190db6270a235a568a5f397012c635cb5603e9cb6fc4c8f8ccbb8bb5f1a1c5aa")[..],
+            &msg,
+        ]
+        .concat();
+
+        for (variant, msg, expected) in [
+            (
+                Variant::PssRandomized,
+                &prefixed,
+                concat!(
+                    "a62266418371d98cf4ddf563551237829e348d336c61ce428ad8df33d136f433",
+                    "06f4d283be8357844e24c453cd78eb50dc8558d450fb0a304babc9271e3d26eb",
+                    "22e2bfd744cbbf043c3ba70d2677932fbcaad919558b17f28f132793182b859f",
+                    "5324290fc9e0c4d8f1f2fc9791bb75165ef238347de67c2b99073b1b12ec2ce8",
+                    "a70e0581d5f788e8339855e6f134b95ea6b80cfdbf4094db7fd62205d4b0e002",
+                    "acfe1c36a7dfaf6dd1ccbf39706ff253f4b24ef881b398f258104e3772cd43fb",
+                    "8c167940547d963b812ad71211ad07d9e03041b7b1397fd1f32b8d3b2645aba1",
+                    "8f56dfeb35f0d22fae681179602e3b95fa7ed1655070b8d253aae8af2a58b312",
+                    "12f7805375bfa6c3029300b1927e1886b797ec86504f857212b300566fcfa4fc",
+                    "4cfbce95e46e146628b6aebf7f78ebf52e9f1ed37a647dddae245e3311734657",
+                    "91bac0e60db09452b1f143fd3f1468c6c4710f9b09a4da0531a86bcc220f7ec7",
+                    "8e44f0b146d8d52dc15f92215e8ed77fb18ec368fac29a641cbc7e1d1772defd",
+                    "6a7b91e38e78c06bce2d4b4ae9bd68291afe9a99ce9632da72012f69a981c5af",
+                    "ccaa7a7581c9bea52a29ece2e0ca845e9365ca684227526c6844a8ba425a9507",
+                    "1a60a03de2d64cf7162f0611a49d3f405e5ce6216f07cdfcb042b5f67fa12220",
+                    "52abbea12c9c0e60fc4189311de1a8f019bdd839e59acef3f253a528e87b8f91",
+                ),
+            ),
+            (
+                Variant::PssZeroRandomized,
+                &prefixed,
+                concat!(
+                    "76a8cb4157bd770014d3f9745a96cd29e0331c06854ebbe486286ae615365c27",
+                    "4c1ffbf565a2aae09b1a910978aa940bb05c0dd7769789f97b01c4aca6687d79",
+                    "3233315dfb5f030445e8e6d650b53db6195977de141e49fe1a06f8d16ce7ce11",
+                    "5a0970b4f052b8ab8bd232985ed9c6c26bc51fdbfdfe6c2b34f2d6623bce713c",
+                    "e18d1f58964c245e797951e7bc4e53129ddbe4c0b3d0777d650c72276a12445a",
+                    "ec7f468c056f779acadee739396addf861d4af6ee6634075d1c925f71e654f60",
+                    "6becd1049a65beb07757275940c2f67e1cc68a165f51ea6022212ada8ba16709",
+                    "1984822ec81e6e3c00838446145faea5e8d50750c379f3b188bc646167f66943",
+                    "6d3bf83f8c718a3c31e71a69b8db11728cb342c9dd4823be399b225cbe6ae02f",
+                    "fdc5d3a95dc74cedf6624c551f517c99b1112054d53c50125974db48af46c221",
+                    "ddc6d7e50c26f60578529e4ec21f8b1aabff7fa0f28d53fc5947a7174165a514",
+                    "68b880878223355bcd05c9005723aec65e959f5adb0eed41c52258da2d4bf152",
+                    "c71da73c18214028eaf026d309d941bdb8eaaa3c99edbf916e842f723cdac6ef",
+                    "931065ab2406532a6b5898458795f9fdd138ee99c4b5bcfb380fad82efb497f9",
+                    "cf3903942094be14807db4c2d8f7e65a727a2039b5a2c6858685f0c84b29908b",
+                    "63e871e7501a8cdc70897f9e13a21632611ec7cce3e6728fcb4302b6041b6e7c",
+                ),
+            ),
+            (
+                Variant::PssZeroDeterministic,
+                &msg,
+                concat!(
+                    "4454b6983ff01cb28545329f394936efa42ed231e15efbc025fdaca00277acf0",
+                    "c8e00e3d8b0ecebd35b057b8ebfc14e1a7097368a4abd20b555894ccef3d1b95",
+                    "28c6bcbda6b95376bef230d0f1feff0c1064c62c60a7ae7431d1fdfa43a81eed",
+                    "9235e363e1ffa0b2797aba6aad6082fcd285e14fc8b71de6b9c87cb4059c7dc1",
+                    "e96ae1e63795a1e9af86b9073d1d848aef3eca8a03421bcd116572456b53bcfd",
+                    "4dabb0a9691f1fabda3ed0ce357aee2cfee5b1a0eb226f69716d4e011d96eede",
+                    "5e38a9acb531a64336a0d5b0bae3ab085b658692579a376740ff6ce69e89b06f",
+                    "360520b864e33d82d029c808248a19e18e31f0ecd16fac5cd4870f8d3ebc1c32",
+                    "c718124152dc905672ab0b7af48bf7d1ac1ff7b9c742549c91275ab105458ae3",
+                    "7621757add83482bbcf779e777bbd61126e93686635d4766aedf5103cf7978f3",
+                    "856ccac9e28d21a850dbb03c811128616d315d717be1c2b6254f8509acae8620",
+                    "42c034530329ce15ca2e2f6b1f5fd59272746e3918c748c0eb810bf76884fa10",
+                    "fcf749326bbfaa5ba285a0186a22e4f628dbf178d3bb5dc7e165ca73f6a55ecc",
+                    "14c4f5a26c4693ce5da032264cbec319b12ddb9787d0efa4fcf1e5ccee35ad85",
+                    "ecd453182df9ed735893f830b570faae8be0f6fe2e571a4e0d927cba4debd368",
+                    "d3b4fca33ec6251897a137cf75474a32ac8256df5e5ffa518b88b43fb6f63a24",
+                ),
+            ),
+        ] {
+            let (_, _, sig) = sign(&key, variant, msg);
+            assert_eq!(sig, unhex(expected));
+            assert!(verify(key.public_key(), variant, msg, &sig));
+        }
+    }
+
+    #[test]
+    fn bad_signatures() {
+        let key = key();
+        let public_key = key.public_key();
+        let msg = unhex(MSG);
+        let (_, blind_sig, sig) = sign(&key, Variant::PssDeterministic, &msg);
+
+        assert!(verify(public_key, Variant::PssDeterministic, &msg, &sig));
+        assert!(!verify(
+            public_key,
+            Variant::PssDeterministic,
+            &msg[1..],
+            &sig
+        ));
+        assert!(!verify(
+            public_key,
+            Variant::PssZeroDeterministic,
+            &msg,
+            &sig
+        ));
+
+        // The blinded signature is not a signature of the message.
+        assert!(!verify(
+            public_key,
+            Variant::PssDeterministic,
+            &msg,
+            &blind_sig
+        ));
+    }
+
+    #[test]
+    fn round_trip() {
+        let key = key();
+        let public_key = key.public_key();
+
+        for variant in [
+            Variant::PssRandomized,
+            Variant::PssZeroRandomized,
+            Variant::PssDeterministic,
+            Variant::PssZeroDeterministic,
+        ] {
+            let msg = prepare(variant, b"hello, world");
+            let (blinded_msg, blinding) = blind(public_key, variant, &msg).unwrap();
+            let blind_sig = blind_sign(&key, &blinded_msg).unwrap();
+            let sig = finalize(public_key, variant, &msg, &blind_sig, blinding).unwrap();
+
+            assert!(verify(public_key, variant, &msg, &sig));
+            assert!(!verify(public_key, variant, b"hello, world!", &sig));
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! # RSA keys
+//! The public key `(n, e)` and the private key with its CRT values, and the
+//! raw primitives `RSAEP`/`RSAVP1` and `RSADP`/`RSASP1` of section 5 on
+//! integers below `n`.
+//!
+//! Private-key operations run two exponentiations modulo `p` and `q` with
+//! the constant-time [`Montgomery::pow`], and check the result against the
+//! public key so that a fault in one half can't leak a factor of `n`. Every
+//! one of them is blinded: the input is multiplied by `r^e` for a fresh
+//! random `r` and the output by `r^-1`, so that the variable-time parts of
+//! the CRT only ever see values unrelated to the input.
+
+use super::prime::{generate_prime, is_probable_prime};
+use crate::bigint::{BoxedUint, Montgomery};
+
+/// The exponent of generated keys, `2^16 + 1`.
+pub const DEFAULT_EXPONENT: u64 = 65537;
+
+/// An RSA public key `(n, e)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    n: BoxedUint,
+    e: BoxedUint,
+    mont: Montgomery,
+}
+
+/// An RSA private key, as the primes `p` and `q` and the exponents
+/// `dP = d mod (p - 1)`, `dQ = d mod (q - 1)` and `qInv = q^-1 mod p`.
+#[derive(Clone)]
+pub struct PrivateKey {
+    public_key: PublicKey,
+    d: BoxedUint,
+    p: BoxedUint,
+    q: BoxedUint,
+    dp: BoxedUint,
+    dq: BoxedUint,
+    qinv: BoxedUint,
+    mont_p: Montgomery,
+    mont_q: Montgomery,
+}
+
+impl PublicKey {
+    /// `None` unless `n` is odd and `e` is odd with `3 <= e < n`.
+    pub fn new(n: BoxedUint, e: BoxedUint) -> Option<PublicKey> {
+        if !e.is_odd() || e < BoxedUint::from_u64(3) || e >= n {
+            return None;
+        }
+
+        Some(PublicKey {
+            mont: Montgomery::new(&n)?,
+            n,
+            e,
+        })
+    }
+
+    /// A key from the big-endian encodings of `n` and `e`.
+    pub fn from_be_bytes(n: &[u8], e: &[u8]) -> Option<PublicKey> {
+        PublicKey::new(BoxedUint::from_be_bytes(n), BoxedUint::from_be_bytes(e))
+    }
+
+    pub fn n(&self) -> &BoxedUint {
+        &self.n
+    }
+
+    pub fn e(&self) -> &BoxedUint {
+        &self.e
+    }
+
+    /// The length `k` of `n` in bytes, which is that of signatures and
+    /// ciphertexts.
+    pub fn size(&self) -> usize {
+        self.n.bits().div_ceil(8)
+    }
+
+    /// `RSAEP`/`RSAVP1`: `m^e mod n`, or `None` unless `m < n`.
+    pub fn encrypt_raw(&self, m: &BoxedUint) -> Option<BoxedUint> {
+        (*m < self.n).then(|| self.mont.pow_vartime(m, &self.e))
+    }
+}
+
+impl PrivateKey {
+    /// A fresh key with a modulus of exactly `bits` bits and
+    /// [`DEFAULT_EXPONENT`], from the operating system's CSPRNG.
+    ///
+    /// Panics if `bits < 512`.
+    pub fn generate(bits: usize) -> PrivateKey {
+        assert!(bits >= 512, "modulus too small");
+
+        let e = BoxedUint::from_u64(DEFAULT_EXPONENT);
+
+        loop {
+            let p = generate_prime(bits - bits / 2, &e);
+            let q = generate_prime(bits / 2, &e);
+
+            // FIPS 186-5 A.1.3 asks for |p - q| > 2^(bits / 2 - 100).
+            let diff = p.checked_sub(&q).unwrap_or_else(|| &q - &p);
+            if diff.bits() <= bits / 2 - 100 {
+                continue;
+            }
+
+            // ...and for d > 2^(bits / 2), which fails with negligible
+            // probability.
+            match PrivateKey::from_primes(p, q, e.clone()) {
+                Some(key) if key.d.bits() > bits / 2 => return key,
+                _ => continue,
+            }
+        }
+    }
+
+    /// The key for the distinct odd primes `p` and `q` and the exponent `e`,
+    /// with `d = e^-1 mod lcm(p - 1, q - 1)`. `None` if `p` or `q` is not a
+    /// probable prime or `e` is not invertible.
+    pub fn from_primes(p: BoxedUint, q: BoxedUint, e: BoxedUint) -> Option<PrivateKey> {
+        let d = e.mod_inverse(&lambda(&p, &q))?;
+
+        PrivateKey::from_components(p, q, e, d)
+    }
+
+    /// The key with the private exponent `d` given as well, as in a PKCS #1
+    /// `RSAPrivateKey`, where it is often `e^-1 mod (p - 1)(q - 1)` instead.
+    /// The signatures and decryptions are the same for either, but the
+    /// messages of implicit rejection in [`super::pkcs1v15`] depend on `d`.
+    /// `None` if `p` or `q` is not a probable prime or `e d != 1` modulo
+    /// `lcm(p - 1, q - 1)`.
+    pub fn from_components(
+        p: BoxedUint,
+        q: BoxedUint,
+        e: BoxedUint,
+        d: BoxedUint,
+    ) -> Option<PrivateKey> {
+        if p == q || !is_probable_prime(&p, 20) || !is_probable_prime(&q, 20) {
+            return None;
+        }
+
+        if &(&e * &d) % &lambda(&p, &q) != BoxedUint::one() {
+            return None;
+        }
+
+        let one = BoxedUint::one();
+        let (p1, q1) = (&p - &one, &q - &one);
+
+        Some(PrivateKey {
+            public_key: PublicKey::new(&p * &q, e)?,
+            dp: &d % &p1,
+            dq: &d % &q1,
+            qinv: q.mod_inverse(&p)?,
+            mont_p: Montgomery::new(&p)?,
+            mont_q: Montgomery::new(&q)?,
+            d,
+            p,
+            q,
+        })
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// The primes `p` and `q`.
+    pub fn primes(&self) -> (&BoxedUint, &BoxedUint) {
+        (&self.p, &self.q)
+    }
+
+    /// The private exponent `d`.
+    pub(crate) fn d(&self) -> &BoxedUint {
+        &self.d
+    }
+
+    /// `RSADP`/`RSASP1`: `c^d mod n` by the CRT with blinding, or `None`
+    /// unless `c < n`.
+    pub fn decrypt_raw(&self, c: &BoxedUint) -> Option<BoxedUint> {
+        let public_key = &self.public_key;
+
+        if *c >= public_key.n {
+            return None;
+        }
+
+        let (r, r_inv) = loop {
+            let r = BoxedUint::random_below(&public_key.n);
+
+            if let Some(r_inv) = r.mod_inverse(&public_key.n) {
+                break (r, r_inv);
+            }
+        };
+
+        let blinded = public_key.mont.mul(c, &public_key.encrypt_raw(&r)?);
+        let m = self.crt(&blinded);
+
+        Some(public_key.mont.mul(&m, &r_inv))
+    }
+
+    /// `c^d mod n` for `c < n`, from the exponentiations modulo `p` and `q`.
+    fn crt(&self, c: &BoxedUint) -> BoxedUint {
+        let m1 = self.mont_p.pow(c, &self.dp);
+        let m2 = self.mont_q.pow(c, &self.dq);
+
+        // h = qInv (m1 - m2) mod p, and m = m2 + q h.
+        let diff = &(&m1 + &self.p) - &(&m2 % &self.p);
+        let h = self.mont_p.mul(&self.qinv, &diff);
+        let m = &m2 + &(&self.q * &h);
+
+        if self.public_key.encrypt_raw(&m).as_ref() == Some(c) {
+            m
+        } else {
+            // A fault in one of the halves: fall back to the slow path
+            // rather than release m.
+            self.public_key.mont.pow(c, &self.d)
+        }
+    }
+}
+
+/// `lcm(p - 1, q - 1)`, Carmichael's function of `n = p q`.
+fn lambda(p: &BoxedUint, q: &BoxedUint) -> BoxedUint {
+    let one = BoxedUint::one();
+    let (p1, q1) = (p - &one, q - &one);
+
+    &(&p1 * &q1) / &p1.gcd(&q1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate() {
+        for bits in [512, 600] {
+            let key = PrivateKey::generate(bits);
+            let (p, q) = key.primes();
+            let one = BoxedUint::one();
+
+            assert_eq!(key.public_key().n(), &(p * q));
+            assert_eq!(key.public_key().n().bits(), bits);
+            assert_eq!(&(key.public_key().e() * key.d()) % &lambda(p, q), one);
+
+            let m = BoxedUint::from_u64(0x1234_5678);
+            let c = key.public_key().encrypt_raw(&m).unwrap();
+            assert_eq!(key.decrypt_raw(&c), Some(m));
+        }
+    }
+
+    #[test]
+    fn from_components() {
+        let key = crate::rsa::tests::key();
+        let (p, q) = key.primes();
+        let public_key = key.public_key();
+
+        for m in [
+            BoxedUint::zero(),
+            BoxedUint::one(),
+            public_key.n() - &BoxedUint::one(),
+        ] {
+            let c = public_key.encrypt_raw(&m).unwrap();
+            assert_eq!(key.decrypt_raw(&c), Some(m));
+        }
+        assert_eq!(public_key.encrypt_raw(public_key.n()), None);
+        assert_eq!(key.decrypt_raw(public_key.n()), None);
+
+        // A d that doesn't invert e.
+        let d = key.d() + &BoxedUint::one();
+        let e = public_key.e().clone();
+        assert!(PrivateKey::from_components(p.clone(), q.clone(), e, d).is_none());
+    }
+}
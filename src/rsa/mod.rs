@@ -0,0 +1,133 @@
+//! # RSA (PKCS #1 v2.2, RFC 8017)
+//! Keys with CRT private-key operations in [`key`], generated from the
+//! probable primes of [`prime`], and the two signature schemes: the
+//! deterministic RSASSA-PKCS1-v1_5 of [`pkcs1v15`], still common in
+//! certificates, and the randomized RSASSA-PSS of [`pss`]. Encryption is
+//! RSAES-OAEP in [`oaep`], and RSAES-PKCS1-v1_5 with implicit rejection in
+//! [`pkcs1v15`] for legacy protocols. [`blind`] has the blind signatures
+//! of RFC 9474 on top of PSS.
+//!
+//! Integers are converted to and from strings of the length of the modulus
+//! with the `I2OSP` and `OS2IP` of the RFC, i.e. big-endian with leading
+//! zeros.
+
+pub mod blind;
+pub mod key;
+pub mod oaep;
+pub mod pkcs1v15;
+pub mod prime;
+pub mod pss;
+
+use crate::sha::{sha256::Sha256, sha384::Sha384, sha512::Sha512};
+
+/// A hash function for RSA padding.
+pub trait Digest {
+    /// The length of the digest in bytes, `hLen`.
+    const OUTPUT_LEN: usize;
+
+    /// The DER encoding of the PKCS #1 v1.5 `DigestInfo` up to the digest.
+    const DIGEST_INFO_PREFIX: &'static [u8];
+
+    fn digest(msg: &[u8]) -> Vec<u8>;
+}
+
+impl Digest for Sha256 {
+    const OUTPUT_LEN: usize = 32;
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01,
+        0x05, 0x00, 0x04, 0x20,
+    ];
+
+    fn digest(msg: &[u8]) -> Vec<u8> {
+        Sha256::new(msg).result().to_vec()
+    }
+}
+
+impl Digest for Sha384 {
+    const OUTPUT_LEN: usize = 48;
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02,
+        0x05, 0x00, 0x04, 0x30,
+    ];
+
+    fn digest(msg: &[u8]) -> Vec<u8> {
+        Sha384::new(msg).result().to_vec()
+    }
+}
+
+impl Digest for Sha512 {
+    const OUTPUT_LEN: usize = 64;
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03,
+        0x05, 0x00, 0x04, 0x40,
+    ];
+
+    fn digest(msg: &[u8]) -> Vec<u8> {
+        Sha512::new(msg).result().to_vec()
+    }
+}
+
+/// MGF1 (appendix B.2.1): `H(seed || 0) || H(seed || 1) || ...` truncated
+/// to `len` bytes.
+pub fn mgf1<H: Digest>(seed: &[u8], len: usize) -> Vec<u8> {
+    let mut mask = (0..len.div_ceil(H::OUTPUT_LEN) as u32)
+        .flat_map(|counter| H::digest(&[seed, &counter.to_be_bytes()].concat()))
+        .collect::<Vec<_>>();
+    mask.truncate(len);
+
+    mask
+}
+
+/// All ones if `x` is zero, else zero, without branching.
+fn ct_is_zero(x: usize) -> usize {
+    (((x | x.wrapping_neg()) >> (usize::BITS - 1)) ^ 1).wrapping_neg()
+}
+
+/// All ones if `a < b`, else zero, for `a, b < 2^(usize::BITS - 1)`.
+fn ct_lt(a: usize, b: usize) -> usize {
+    (a.wrapping_sub(b) >> (usize::BITS - 1)).wrapping_neg()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::key::PrivateKey;
+    use crate::{bigint::BoxedUint, test_util::unhex};
+
+    /// A 1024-bit key with `e = 65537` from OpenSSL, which produced the
+    /// known answers of the schemes.
+    pub(super) fn key() -> PrivateKey {
+        let int = |s: &str| BoxedUint::from_be_bytes(&unhex(s));
+
+        PrivateKey::from_components(
+            int(concat!(
+                "f13c0f1135bb5f983bab9e0ebcabbc1c75a2917509d5d822a8be5588cf183b45",
+                "0631c56b896bdc9643fa804b6029d0958f8cf1f9f06f87658a65a6f544fcdfe1",
+            )),
+            int(concat!(
+                "dc393887ce78f56bfbebee73e1bdb8a980c1ac3c0462f7bed7a492c02374818",
+                "090f4c6ac5f8e9475cf8961ddd51b2e33b3aae6c01732d8c599aa3cb0003c375f",
+            )),
+            BoxedUint::from_u64(65537),
+            int(concat!(
+                "315e5c2357b524598cd4ff1ccb828e725f48f53f96b9a1e043ff90a3b62d6bd3",
+                "3e15874f23a21687ed9df598b4430c0d2f32441d17b43851cac8ae7586bef30a",
+                "36d0db4f951e72b77c84ba3d2ac9dc318b76675ace60a2341e210a801335e94c",
+                "eebab167c6bc6ba3b782c050b266d262e9bb8427105367b3e68b5430310f05c1",
+            )),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn key_matches_modulus() {
+        assert_eq!(
+            key().public_key().n(),
+            &BoxedUint::from_be_bytes(&unhex(concat!(
+                "cf858897418a023e941d6c59b1dd415106e6d65a24d544f1dc4cfc7537aa4197",
+                "5f3b0d42fdd6197bd2d809b2d32fbcbfa383db032217ad7981f84d9a61b4880c",
+                "14363f154585c9a50d61604e7c924245d99f9431b755ffdaf06783e589a4d561",
+                "e820645d883421591a88b97288b0b48824d51048bdd5d5e72ad078cb66ac6b7f",
+            )))
+        );
+    }
+}
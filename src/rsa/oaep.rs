@@ -0,0 +1,145 @@
+//! # RSAES-OAEP (section 7.1)
+//! Encryption with the optimal asymmetric encryption padding, with MGF1
+//! over the same hash as the label. The label is usually empty.
+//!
+//! Decryption checks the whole padding with masks and fails only at the
+//! end, so that which check failed doesn't show in the timing (Manger's
+//! attack).
+
+use super::{
+    ct_is_zero,
+    key::{PrivateKey, PublicKey},
+    mgf1, Digest,
+};
+use crate::{bigint::BoxedUint, rng};
+
+/// The encryption of `msg` under `key` with `label`, or `None` if `msg` is
+/// longer than `k - 2 hLen - 2` bytes.
+pub fn encrypt<H: Digest>(key: &PublicKey, msg: &[u8], label: &[u8]) -> Option<Vec<u8>> {
+    let k = key.size();
+    let ps_len = k.checked_sub(msg.len() + 2 * H::OUTPUT_LEN + 2)?;
+
+    let db = [&H::digest(label)[..], &vec![0u8; ps_len], &[0x01], msg].concat();
+    let mut seed = vec![0u8; H::OUTPUT_LEN];
+    rng::fill(&mut seed);
+
+    let masked_db = xor(&db, &mgf1::<H>(&seed, db.len()));
+    let masked_seed = xor(&seed, &mgf1::<H>(&masked_db, H::OUTPUT_LEN));
+
+    let em = [&[0x00], &masked_seed[..], &masked_db].concat();
+    let c = key.encrypt_raw(&BoxedUint::from_be_bytes(&em))?;
+
+    c.to_be_bytes_padded(k)
+}
+
+/// The decryption of `ct` under `key` with `label`, or `None` if it is
+/// invalid.
+pub fn decrypt<H: Digest>(key: &PrivateKey, ct: &[u8], label: &[u8]) -> Option<Vec<u8>> {
+    let k = key.public_key().size();
+
+    if ct.len() != k || k < 2 * H::OUTPUT_LEN + 2 {
+        return None;
+    }
+
+    let m = key.decrypt_raw(&BoxedUint::from_be_bytes(ct))?;
+    let em = m.to_be_bytes_padded(k).unwrap();
+
+    let (masked_seed, masked_db) = em[1..].split_at(H::OUTPUT_LEN);
+    let seed = xor(masked_seed, &mgf1::<H>(masked_db, H::OUTPUT_LEN));
+    let db = xor(masked_db, &mgf1::<H>(&seed, masked_db.len()));
+
+    // Y = 0 and lHash' = lHash.
+    let mut bad = em[0] as usize;
+    for (a, b) in db.iter().zip(H::digest(label)) {
+        bad |= (a ^ b) as usize;
+    }
+    let mut good = ct_is_zero(bad);
+
+    // DB = lHash || 00..00 || 01 || M: find the 01 in constant time.
+    let mut looking = usize::MAX;
+    let mut one_index = 0;
+    for (i, b) in db.iter().enumerate().skip(H::OUTPUT_LEN) {
+        let is_zero = ct_is_zero(*b as usize);
+        let is_one = ct_is_zero(*b as usize ^ 1);
+
+        one_index |= i & looking & is_one;
+        good &= !looking | is_zero | is_one;
+        looking &= is_zero;
+    }
+    good &= !looking;
+
+    if good != 0 {
+        Some(db[one_index + 1..].to_vec())
+    } else {
+        None
+    }
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(a, b)| a ^ b).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rsa::tests::key,
+        sha::{sha256::Sha256, sha512::Sha512},
+        test_util::unhex,
+    };
+
+    const MSG: &[u8] = b"hello, world";
+
+    /// Encryptions of [`MSG`] by OpenSSL with SHA-256, without a label and
+    /// with the label "label".
+    const CT: &str = concat!(
+        "22b74a0df4f4074023388a39273026395e2af2d4836b55d60bd8adfd606a68b2",
+        "597dae9653c46fe6da8dfbed28e93ac77da342d718a1105cfb6475b1858c0ee2",
+        "695723641c35f207235c3a940ac2f5b764a0a3c2ef8fa448874fbc18216ef8b2",
+        "a7ab8b747199918f0211c25db140bbf2acf0a28de2ee86338567e090234ffd3f",
+    );
+    const LABELLED_CT: &str = concat!(
+        "aa12186c150066c2a348df2db1dbc3fa136ac24d5930d74f47011575d7eddac4",
+        "f9a1e9ce6348e641dc57651543dd85e338050920a0f364c7be521075f5b4e7c9",
+        "5315635b67512cbda2246d445a3fceda888c94289fd64e5e9638ade79985bb9c",
+        "a4dadd96080753a51e93fbef2d554b6129d2fad97c43cc429fcf8a8dcd2aa46e",
+    );
+
+    #[test]
+    fn external_ciphertexts() {
+        let key = key();
+        let ct = unhex(CT);
+        let labelled_ct = unhex(LABELLED_CT);
+
+        assert_eq!(decrypt::<Sha256>(&key, &ct, b"").as_deref(), Some(MSG));
+        assert_eq!(
+            decrypt::<Sha256>(&key, &labelled_ct, b"label").as_deref(),
+            Some(MSG)
+        );
+
+        // The wrong label or hash, or a changed ciphertext.
+        assert_eq!(decrypt::<Sha256>(&key, &ct, b"label"), None);
+        assert_eq!(decrypt::<Sha256>(&key, &labelled_ct, b""), None);
+        assert_eq!(decrypt::<Sha256>(&key, &labelled_ct, b"label!"), None);
+        assert_eq!(decrypt::<Sha512>(&key, &ct, b""), None);
+
+        let mut flipped = ct.clone();
+        flipped[64] ^= 0x01;
+        assert_eq!(decrypt::<Sha256>(&key, &flipped, b""), None);
+        assert_eq!(decrypt::<Sha256>(&key, &ct[1..], b""), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let key = key();
+
+        for msg in [&b""[..], MSG, &[0xaa; 62]] {
+            let ct = encrypt::<Sha256>(key.public_key(), msg, b"label").unwrap();
+            assert_eq!(decrypt::<Sha256>(&key, &ct, b"label").as_deref(), Some(msg));
+            assert_eq!(decrypt::<Sha256>(&key, &ct, b""), None);
+        }
+
+        // k - 2 hLen - 2 = 62 bytes at most.
+        assert_eq!(encrypt::<Sha256>(key.public_key(), &[0xaa; 63], b""), None);
+    }
+}
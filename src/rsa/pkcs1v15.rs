@@ -0,0 +1,306 @@
+//! # PKCS #1 v1.5 signatures and encryption
+//! RSASSA-PKCS1-v1_5 (section 8.2) signs `EM = 00 01 FF..FF 00 || T`
+//! deterministically, where `T` is the DER `DigestInfo` of the hash
+//! algorithm and the digest of the message. Verification encodes the
+//! expected `EM` and compares, rather than parsing the signed one.
+//!
+//! RSAES-PKCS1-v1_5 (section 7.2) encrypts `EM = 00 02 PS 00 || M` with
+//! random non-zero padding `PS`. Its padding check is the oracle of
+//! Bleichenbacher's attack, so decryption uses the implicit rejection of
+//! draft-irtf-cfrg-rsa-guidance, as OpenSSL 3.2 does: a badly padded
+//! ciphertext decrypts, without any error or difference in timing, to a
+//! random-looking message derived from the ciphertext and the private key.
+//! Prefer [`super::oaep`] for anything new.
+
+use super::{
+    ct_is_zero, ct_lt,
+    key::{PrivateKey, PublicKey},
+    Digest,
+};
+use crate::{bigint::BoxedUint, rng, sha::hmac::hmac_sha256, sha::sha256::Sha256};
+
+// Signatures
+
+/// The signature of `msg` under `key`, as long as the modulus.
+///
+/// Panics if the modulus is too short for the digest.
+pub fn sign<H: Digest>(key: &PrivateKey, msg: &[u8]) -> Vec<u8> {
+    let k = key.public_key().size();
+    let em = encode::<H>(msg, k).expect("modulus too short for the digest");
+
+    let s = key.decrypt_raw(&BoxedUint::from_be_bytes(&em)).unwrap();
+
+    s.to_be_bytes_padded(k).unwrap()
+}
+
+/// Whether `sig` is the signature of `msg` under `key`.
+pub fn verify<H: Digest>(key: &PublicKey, msg: &[u8], sig: &[u8]) -> bool {
+    let k = key.size();
+
+    if sig.len() != k {
+        return false;
+    }
+
+    let Some(m) = key.encrypt_raw(&BoxedUint::from_be_bytes(sig)) else {
+        return false;
+    };
+
+    match encode::<H>(msg, k) {
+        Some(em) => m.to_be_bytes_padded(k) == Some(em),
+        None => false,
+    }
+}
+
+/// `EMSA-PKCS1-v1_5-ENCODE` (section 9.2) into `k` bytes, or `None` if `k`
+/// leaves fewer than 8 bytes of `FF` padding.
+fn encode<H: Digest>(msg: &[u8], k: usize) -> Option<Vec<u8>> {
+    let t = [H::DIGEST_INFO_PREFIX, &H::digest(msg)].concat();
+    let ps_len = k.checked_sub(t.len() + 3).filter(|len| *len >= 8)?;
+
+    Some([&[0x00, 0x01][..], &vec![0xff; ps_len], &[0x00], &t].concat())
+}
+
+// Encryption
+
+/// The encryption of `msg` under `key`, or `None` if `msg` is longer than
+/// `k - 11` bytes.
+pub fn encrypt(key: &PublicKey, msg: &[u8]) -> Option<Vec<u8>> {
+    let k = key.size();
+    let ps_len = k.checked_sub(msg.len() + 3).filter(|len| *len >= 8)?;
+
+    let mut ps = vec![0u8; ps_len];
+    rng::fill(&mut ps);
+    for b in ps.iter_mut() {
+        while *b == 0 {
+            *b = rng::bytes::<1>()[0];
+        }
+    }
+
+    let em = [&[0x00, 0x02][..], &ps, &[0x00], msg].concat();
+    let c = key.encrypt_raw(&BoxedUint::from_be_bytes(&em))?;
+
+    c.to_be_bytes_padded(k)
+}
+
+/// The decryption of `ct` under `key`, or the synthetic message if its
+/// padding is wrong. `None` only for a ciphertext that is not `k` bytes
+/// long or not below `n`, which reveals nothing about the plaintext.
+pub fn decrypt(key: &PrivateKey, ct: &[u8]) -> Option<Vec<u8>> {
+    let k = key.public_key().size();
+
+    if ct.len() != k || k < 11 {
+        return None;
+    }
+
+    let m = key.decrypt_raw(&BoxedUint::from_be_bytes(ct))?;
+    let em = m.to_be_bytes_padded(k).unwrap();
+    let (synthetic, synthetic_start) = synthetic_message(key, ct);
+
+    // The index of the first zero after 00 02, or 0 if there is none.
+    let mut zero_index = 0;
+    for (i, b) in em.iter().enumerate().skip(2) {
+        let first = ct_is_zero(*b as usize) & ct_is_zero(zero_index);
+        zero_index |= i & first;
+    }
+
+    // At least 8 bytes of padding put the separator at index 10 or later.
+    let good = ct_is_zero(em[0] as usize) & ct_is_zero(em[1] as usize ^ 2) & !ct_lt(zero_index, 10);
+
+    let start = (good & (zero_index + 1)) | (!good & synthetic_start);
+    let mask = good as u8;
+    let out = em
+        .iter()
+        .zip(&synthetic)
+        .map(|(e, s)| s ^ (mask & (e ^ s)))
+        .collect::<Vec<_>>();
+
+    Some(out[start..].to_vec())
+}
+
+/// The message for a badly padded `ct`, as the bytes of a `k`-byte string
+/// from `start` on, both derived from `ct` and `d`.
+fn synthetic_message(key: &PrivateKey, ct: &[u8]) -> (Vec<u8>, usize) {
+    let k = ct.len();
+    let d = key.d().to_be_bytes_padded(k).unwrap();
+    let kdk = hmac_sha256(&Sha256::new(&d).result(), ct);
+
+    let synthetic = prf(&kdk, b"message", 8 * k);
+    let lengths = prf(&kdk, b"length", 128 * 16);
+
+    // The last of 128 candidate lengths below the longest possible message,
+    // masked to the bit length of that, in constant time.
+    let max_len = k - 2 - 8;
+    let len_mask = usize::MAX >> max_len.leading_zeros();
+    let mut len = 0;
+    for candidate in lengths.chunks_exact(2) {
+        let candidate = u16::from_be_bytes([candidate[0], candidate[1]]) as usize & len_mask;
+        let below = ct_lt(candidate, max_len);
+        len = (below & candidate) | (!below & len);
+    }
+
+    (synthetic, k - len)
+}
+
+/// The PRF of the draft: HMAC-SHA256 over a counter, the label and the
+/// output length in bits, truncated to `bits / 8` bytes.
+fn prf(key: &[u8], label: &[u8], bits: usize) -> Vec<u8> {
+    let mut out = (0..bits.div_ceil(256) as u16)
+        .flat_map(|i| {
+            hmac_sha256(
+                key,
+                &[&i.to_be_bytes()[..], label, &(bits as u16).to_be_bytes()].concat(),
+            )
+        })
+        .collect::<Vec<_>>();
+    out.truncate(bits / 8);
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rsa::tests::key,
+        sha::{sha256::Sha256, sha512::Sha512},
+        test_util::unhex,
+    };
+
+    const MSG: &[u8] = b"hello, world";
+
+    /// Signatures of [`MSG`] by OpenSSL.
+    const SHA256_SIG: &str = concat!(
+        "9fdc809abece60c028e5fb945c4173cfafb40bdbfd177afc041f6e21162c923f",
+        "23266be275130ecb49460ca8c77020e4f5dc65e2b0e9560e20ce92b09664738a",
+        "e2470a2a22c7370f0eff609fa174d9eea3e860c5d39b5595d5e27b1dbaabea5a",
+        "a5242f0e10dcf9625b9ea75998a3b02bea0d21305f98d08bcb9fd2ba18b5bddd",
+    );
+    const SHA512_SIG: &str = concat!(
+        "42a19bd05124b71d1bc90ec051aac79bf349bf32b19d13ca0c38c44d5335934b",
+        "2ede878d41442c34b83320f62ca0003ca6057c5ecd852d9ae1c73b6a47e10b81",
+        "b0abea0ddcf2084b1d00cafb31c1d74a8162debbf8dcff9f0b6257a0189e5a9e",
+        "b5badd2c46cd4558b93c238ffd9e5a90eb234548c1318e2855076b15d868e545",
+    );
+
+    /// An encryption of [`MSG`] by OpenSSL.
+    const CT: &str = concat!(
+        "8b688fa45bc798a4f5fb972a44b4c40d32f72183e5bd05e44e6d5f07b15d6948",
+        "720bb11d4c43202896d7f9377d00a8cacfb37b9506e08388bb1bd4804fbaad14",
+        "4f237990d2610fec96f68dbf2f5f823ade53b23a28180ef391cc85109f8c3b63",
+        "625dbfb181c7e4e186b7364f9c65fd11a5eefe56a3dda15c0487c685de879519",
+    );
+
+    /// Badly padded ciphertexts with the synthetic messages OpenSSL's
+    /// implicit rejection decrypts them to: a block type of 01, no zero
+    /// after the padding, and only 5 bytes of padding.
+    const BAD_CTS: [(&str, &str); 3] = [
+        (
+            concat!(
+                "335d2a83495c0a663d074cd0055641bb7f20e565581ca1b0cedf0655a1cb7735",
+                "9d6c7b6097ecc722e39908509ceef464232bfee5af7e127f17f429efe67cbbb0",
+                "09a392fc1636890fd53d0ec5e65b65d0fc53d2f6365ee9d3a72fa1f1fcee0a04",
+                "331d4366f4eeb5885d794e89cc7a4c399e16ad460498d0dbd46f904625abbf9b",
+            ),
+            concat!(
+                "8d9f19d0d61a10a90fd7aa83b2bb4200ac7974b418f93e2bb96493741e9d2581",
+                "a1d596cea2bb230871d10f3749e094e9c862",
+            ),
+        ),
+        (
+            concat!(
+                "c21a8270ee6601c742c17916b3c582db4d0e81dbfa27bd0cac1b4ae7f88dc613",
+                "fa3664e3655f0de80c735a5e1db9aa292f8bfbdec7f711955c8a28b5a7deb029",
+                "db9b92e14f943fa38263ed12c379bb2e248e8ffa7f2056b8e0e9d0066905a565",
+                "d8dec54f0d474d57df3975806cab148a98f3730cc2ee627eaa2dca3dca159e2e",
+            ),
+            concat!(
+                "4a22f12ac8ea325f178e76f1a3eca6e5e175d2235ae336d45677a5bf8a7522d6",
+                "f02d21af12bc7d463c49c3ae9b604e02e7dbda12f1ea698854a50a5c7abb6762",
+                "47f0173c491f7b40f06079e3bc63bc469ba84aac05e51e74ca567c8e48",
+            ),
+        ),
+        (
+            concat!(
+                "a45efb4257fd2af8eaa1bcc35dc9440b3b57f36172a9cf6bd9fd67e4352d166e",
+                "5f79b7e3940029cd8e350055669e2dc03879170cf8024fcf80b7b2cfccdd3d9a",
+                "c378c0422bbfce75e06d18b251130f35813397736a6a7d13d138bd95d7772b07",
+                "4bfc0baabdbf3129abed12f600c3ec7aa25668fb8939aec2ebf7bf6274cba3e7",
+            ),
+            concat!(
+                "b197df9e9744a1e579f1a233ec6d128c9d29577c3c5c020d7f6adc4ec0d432d8",
+                "adee0a2cef97a1b01dfd53b56d93c3b8da5b2248e7c3dd6f365734a4c91b6f65",
+                "ab04f0",
+            ),
+        ),
+    ];
+
+    #[test]
+    fn signatures() {
+        let key = key();
+
+        assert_eq!(sign::<Sha256>(&key, MSG), unhex(SHA256_SIG));
+        assert_eq!(sign::<Sha512>(&key, MSG), unhex(SHA512_SIG));
+
+        assert!(verify::<Sha256>(key.public_key(), MSG, &unhex(SHA256_SIG)));
+        assert!(verify::<Sha512>(key.public_key(), MSG, &unhex(SHA512_SIG)));
+    }
+
+    #[test]
+    fn bad_signatures() {
+        let key = key();
+        let public_key = key.public_key();
+        let sig = unhex(SHA256_SIG);
+
+        assert!(!verify::<Sha512>(public_key, MSG, &sig));
+        assert!(!verify::<Sha256>(public_key, b"hello, world!", &sig));
+
+        for i in [0, 64, 127] {
+            let mut flipped = sig.clone();
+            flipped[i] ^= 0x10;
+            assert!(!verify::<Sha256>(public_key, MSG, &flipped));
+        }
+
+        // Not below n, and not k bytes long.
+        let n = public_key.n();
+        assert!(!verify::<Sha256>(public_key, MSG, &n.to_be_bytes()));
+        assert!(!verify::<Sha256>(
+            public_key,
+            MSG,
+            &(n + &BoxedUint::one()).to_be_bytes()
+        ));
+        assert!(!verify::<Sha256>(
+            public_key,
+            MSG,
+            &[&[0][..], &sig].concat()
+        ));
+        assert!(!verify::<Sha256>(public_key, MSG, &sig[1..]));
+    }
+
+    #[test]
+    fn decryption() {
+        let key = key();
+
+        assert_eq!(decrypt(&key, &unhex(CT)).as_deref(), Some(MSG));
+
+        for (ct, synthetic) in BAD_CTS {
+            assert_eq!(decrypt(&key, &unhex(ct)), Some(unhex(synthetic)));
+        }
+
+        // Not k bytes long, or not below n.
+        assert_eq!(decrypt(&key, &unhex(CT)[1..]), None);
+        assert_eq!(decrypt(&key, &key.public_key().n().to_be_bytes()), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let key = key();
+
+        for msg in [&b""[..], MSG, &[0xaa; 117]] {
+            let ct = encrypt(key.public_key(), msg).unwrap();
+            assert_eq!(decrypt(&key, &ct).as_deref(), Some(msg));
+        }
+
+        // k - 11 = 117 bytes at most.
+        assert_eq!(encrypt(key.public_key(), &[0xaa; 118]), None);
+    }
+}
@@ -0,0 +1,117 @@
+//! # Probable primes
+//! Miller-Rabin testing after trial division by the small primes, and the
+//! generation of random primes for RSA keys as in FIPS 186-5 appendix A.1.3.
+
+use crate::bigint::{BoxedUint, Montgomery};
+
+/// The odd primes below 2000, for trial division.
+fn small_primes() -> Vec<u64> {
+    (3..2000u64)
+        .step_by(2)
+        .filter(|k| {
+            (3..)
+                .step_by(2)
+                .take_while(|d| d * d <= *k)
+                .all(|d| k % d != 0)
+        })
+        .collect()
+}
+
+/// Whether `n` passes `rounds` rounds of Miller-Rabin with random bases.
+/// A composite passes each round with probability at most 1/4.
+pub fn is_probable_prime(n: &BoxedUint, rounds: usize) -> bool {
+    if *n < BoxedUint::from_u64(4) {
+        return *n == BoxedUint::from_u64(2) || *n == BoxedUint::from_u64(3);
+    }
+
+    if !n.is_odd() {
+        return false;
+    }
+
+    for p in small_primes() {
+        if n.rem_u64(p) == 0 {
+            return *n == BoxedUint::from_u64(p);
+        }
+    }
+
+    miller_rabin(n, rounds)
+}
+
+/// A random prime of exactly `bits` bits with the top two bits set, so that
+/// the product of two of them has exactly the sum of their lengths, and with
+/// `p - 1` coprime to the odd public exponent `e`.
+///
+/// Panics if `bits < 16`.
+pub fn generate_prime(bits: usize, e: &BoxedUint) -> BoxedUint {
+    assert!(bits >= 16, "prime too small");
+
+    let primes = small_primes();
+    let top = &BoxedUint::from_u64(3) << (bits - 2);
+
+    loop {
+        let mut candidate = &BoxedUint::random_bits(bits - 2) + &top;
+        if !candidate.is_odd() {
+            candidate = &candidate + &BoxedUint::one();
+        }
+
+        if primes.iter().any(|p| candidate.rem_u64(*p) == 0) {
+            continue;
+        }
+
+        if (&candidate - &BoxedUint::one()).gcd(e) != BoxedUint::one() {
+            continue;
+        }
+
+        if miller_rabin(&candidate, rounds(bits)) {
+            return candidate;
+        }
+    }
+}
+
+/// The rounds of Miller-Rabin for a random candidate of `bits` bits, from
+/// table B.1 of FIPS 186-5, for an error probability below 2^-100.
+fn rounds(bits: usize) -> usize {
+    match bits {
+        1536.. => 4,
+        1024.. => 5,
+        512.. => 8,
+        _ => 40,
+    }
+}
+
+/// Miller-Rabin for an odd `n > 3`, with exponentiations that don't leak
+/// the candidate, which may become a secret prime.
+fn miller_rabin(n: &BoxedUint, rounds: usize) -> bool {
+    let one = BoxedUint::one();
+    let n_minus_1 = n - &one;
+
+    // n - 1 = 2^s d with d odd.
+    let s = (0..).find(|i| n_minus_1.bit(*i)).unwrap();
+    let d = &n_minus_1 >> s;
+
+    let mont = Montgomery::new(n).unwrap();
+    let two = BoxedUint::from_u64(2);
+    let three = BoxedUint::from_u64(3);
+
+    'round: for _ in 0..rounds {
+        // A base in 2..n - 1.
+        let a = &BoxedUint::random_below(&(n - &three)) + &two;
+        let mut x = mont.pow(&a, &d);
+
+        if x == one || x == n_minus_1 {
+            continue;
+        }
+
+        for _ in 1..s {
+            x = mont.mul(&x, &x);
+
+            if x == n_minus_1 {
+                continue 'round;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
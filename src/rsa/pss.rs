@@ -0,0 +1,169 @@
+//! # RSASSA-PSS (section 8.1)
+//! Randomized signatures over the encoding `EMSA-PSS` with MGF1 over the
+//! same hash as the message. The salt is usually as long as the digest, the
+//! default here; verifiers need to know its length.
+
+use super::{
+    key::{PrivateKey, PublicKey},
+    mgf1, Digest,
+};
+use crate::{bigint::BoxedUint, rng};
+
+/// The signature of `msg` under `key` with a random salt of `hLen` bytes.
+///
+/// Panics if the modulus is too short for the digest and salt.
+pub fn sign<H: Digest>(key: &PrivateKey, msg: &[u8]) -> Vec<u8> {
+    let mut salt = vec![0u8; H::OUTPUT_LEN];
+    rng::fill(&mut salt);
+
+    sign_with_salt::<H>(key, msg, &salt)
+}
+
+/// The signature of `msg` under `key` with the given salt, which may be
+/// empty for deterministic signatures.
+///
+/// Panics if the modulus is too short for the digest and salt.
+pub fn sign_with_salt<H: Digest>(key: &PrivateKey, msg: &[u8], salt: &[u8]) -> Vec<u8> {
+    let public_key = key.public_key();
+    let em = encode::<H>(msg, public_key.n().bits() - 1, salt)
+        .expect("modulus too short for the digest and salt");
+
+    let s = key.decrypt_raw(&BoxedUint::from_be_bytes(&em)).unwrap();
+
+    s.to_be_bytes_padded(public_key.size()).unwrap()
+}
+
+/// Whether `sig` is the signature of `msg` under `key` with a salt of
+/// `salt_len` bytes.
+pub fn verify<H: Digest>(key: &PublicKey, msg: &[u8], sig: &[u8], salt_len: usize) -> bool {
+    if sig.len() != key.size() {
+        return false;
+    }
+
+    let Some(m) = key.encrypt_raw(&BoxedUint::from_be_bytes(sig)) else {
+        return false;
+    };
+
+    let em_bits = key.n().bits() - 1;
+
+    match m.to_be_bytes_padded(em_bits.div_ceil(8)) {
+        Some(em) => verify_encoded::<H>(msg, &em, em_bits, salt_len),
+        None => false,
+    }
+}
+
+/// `EMSA-PSS-ENCODE` (section 9.1.1) of `msg` into an integer of at most
+/// `em_bits` bits, or `None` if that is too short.
+pub(crate) fn encode<H: Digest>(msg: &[u8], em_bits: usize, salt: &[u8]) -> Option<Vec<u8>> {
+    let em_len = em_bits.div_ceil(8);
+    let ps_len = em_len.checked_sub(H::OUTPUT_LEN + salt.len() + 2)?;
+
+    let m_hash = H::digest(msg);
+    let h = H::digest(&[&[0u8; 8], &m_hash[..], salt].concat());
+
+    let db = [&vec![0u8; ps_len][..], &[0x01], salt].concat();
+    let mut masked_db = mgf1::<H>(&h, db.len());
+    for (m, d) in masked_db.iter_mut().zip(&db) {
+        *m ^= d;
+    }
+    masked_db[0] &= 0xff >> (8 * em_len - em_bits);
+
+    Some([&masked_db[..], &h, &[0xbc]].concat())
+}
+
+/// `EMSA-PSS-VERIFY` (section 9.1.2): whether `em` encodes `msg` with a
+/// salt of `salt_len` bytes.
+pub(crate) fn verify_encoded<H: Digest>(
+    msg: &[u8],
+    em: &[u8],
+    em_bits: usize,
+    salt_len: usize,
+) -> bool {
+    let em_len = em_bits.div_ceil(8);
+
+    if em.len() != em_len || em_len < H::OUTPUT_LEN + salt_len + 2 || em[em_len - 1] != 0xbc {
+        return false;
+    }
+
+    let (masked_db, h) = em[..em_len - 1].split_at(em_len - H::OUTPUT_LEN - 1);
+    let top_mask = 0xff >> (8 * em_len - em_bits);
+
+    if masked_db[0] & !top_mask != 0 {
+        return false;
+    }
+
+    let mut db = mgf1::<H>(h, masked_db.len());
+    for (d, m) in db.iter_mut().zip(masked_db) {
+        *d ^= m;
+    }
+    db[0] &= top_mask;
+
+    // DB = PS || 01 || salt, with PS all zero.
+    let ps_len = db.len() - salt_len - 1;
+    if db[..ps_len].iter().any(|b| *b != 0) || db[ps_len] != 0x01 {
+        return false;
+    }
+
+    let m_hash = H::digest(msg);
+    let salt = &db[ps_len + 1..];
+
+    H::digest(&[&[0u8; 8], &m_hash[..], salt].concat()) == h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rsa::tests::key, sha::sha256::Sha256, test_util::unhex};
+
+    const MSG: &[u8] = b"hello, world";
+
+    /// A signature of [`MSG`] by OpenSSL with a 32 byte salt.
+    const SIG: &str = concat!(
+        "8cb89748f13c717fed071bd4dd3147d81c06f5245b7bab67d9dec0138c936a7a",
+        "f5a00403d0dfb2f86630c3f080ec4ae3ce5259510d7b253ed1b98c5ee11c927c",
+        "846169ee915ab218ebacd5c4796c76da6cf4d81480a83fa412d4bf5f598f0e4e",
+        "fd75d40d6c1adeb547cd05422fe4e84b4edc02e88449492927cad7f32d66e4e0",
+    );
+
+    #[test]
+    fn external_signature() {
+        let key = key();
+        let public_key = key.public_key();
+        let sig = unhex(SIG);
+
+        assert!(verify::<Sha256>(public_key, MSG, &sig, 32));
+
+        // The wrong salt length, message and signature.
+        assert!(!verify::<Sha256>(public_key, MSG, &sig, 31));
+        assert!(!verify::<Sha256>(public_key, MSG, &sig, 0));
+        assert!(!verify::<Sha256>(public_key, b"hello, world!", &sig, 32));
+
+        for i in [0, 64, 127] {
+            let mut flipped = sig.clone();
+            flipped[i] ^= 0x01;
+            assert!(!verify::<Sha256>(public_key, MSG, &flipped, 32));
+        }
+
+        assert!(!verify::<Sha256>(
+            public_key,
+            MSG,
+            &public_key.n().to_be_bytes(),
+            32
+        ));
+    }
+
+    #[test]
+    fn round_trip() {
+        let key = key();
+        let public_key = key.public_key();
+
+        let sig = sign::<Sha256>(&key, MSG);
+        assert_ne!(sig, sign::<Sha256>(&key, MSG));
+        assert!(verify::<Sha256>(public_key, MSG, &sig, 32));
+
+        let sig = sign_with_salt::<Sha256>(&key, MSG, &[]);
+        assert_eq!(sig, sign_with_salt::<Sha256>(&key, MSG, &[]));
+        assert!(verify::<Sha256>(public_key, MSG, &sig, 0));
+        assert!(!verify::<Sha256>(public_key, MSG, &sig, 32));
+    }
+}
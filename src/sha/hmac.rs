@@ -0,0 +1,42 @@
+use super::{sha256::Sha256, sha384::Sha384, sha512::Sha512};
+
+/// # HMAC (RFC 2104)
+/// `H((K' ^ opad) || H((K' ^ ipad) || msg))`, where `K'` is `key`, or
+/// `H(key)` if it is longer than the block, zero-padded to `block_size`.
+pub fn hmac<H>(hash: H, block_size: usize, key: &[u8], msg: &[u8]) -> Vec<u8>
+where
+    H: Fn(&[u8]) -> Vec<u8>,
+{
+    let mut k = if key.len() > block_size {
+        hash(key)
+    } else {
+        key.to_vec()
+    };
+    k.resize(block_size, 0);
+
+    let mut inner = k.iter().map(|x| x ^ 0x36).collect::<Vec<_>>();
+    inner.extend_from_slice(msg);
+
+    let mut outer = k.iter().map(|x| x ^ 0x5c).collect::<Vec<_>>();
+    outer.append(&mut hash(&inner));
+
+    hash(&outer)
+}
+
+pub fn hmac_sha256(key: &[u8], msg: &[u8]) -> [u8; 32] {
+    hmac(|x| Sha256::new(x).result().to_vec(), 64, key, msg)
+        .try_into()
+        .unwrap()
+}
+
+pub fn hmac_sha384(key: &[u8], msg: &[u8]) -> [u8; 48] {
+    hmac(|x| Sha384::new(x).result().to_vec(), 128, key, msg)
+        .try_into()
+        .unwrap()
+}
+
+pub fn hmac_sha512(key: &[u8], msg: &[u8]) -> [u8; 64] {
+    hmac(|x| Sha512::new(x).result().to_vec(), 128, key, msg)
+        .try_into()
+        .unwrap()
+}
@@ -0,0 +1,109 @@
+use super::turboshake::TurboShake;
+
+const CHUNK: usize = 8192;
+
+/// # KangarooTwelve (RFC 9861)
+/// KT128 and KT256: TurboSHAKE in a tree mode. Inputs longer than one 8 KiB
+/// chunk are split into leaves whose chaining values are absorbed by the
+/// final node, so the leaves could be hashed in parallel.
+#[derive(Debug, Clone)]
+pub struct KangarooTwelve {
+    capacity: usize,
+    msg: Vec<u8>,
+    custom: Vec<u8>,
+}
+
+impl KangarooTwelve {
+    pub fn kt128(msg: &[u8], custom: &[u8]) -> KangarooTwelve {
+        KangarooTwelve {
+            capacity: 256,
+            msg: msg.to_vec(),
+            custom: custom.to_vec(),
+        }
+    }
+
+    pub fn kt256(msg: &[u8], custom: &[u8]) -> KangarooTwelve {
+        KangarooTwelve {
+            capacity: 512,
+            msg: msg.to_vec(),
+            custom: custom.to_vec(),
+        }
+    }
+
+    /// Squeeze `l` bytes of output.
+    pub fn result(&self, l: usize) -> Vec<u8> {
+        let mut s = self.msg.clone();
+        s.extend_from_slice(&self.custom);
+        s.append(&mut length_encode(self.custom.len()));
+
+        if s.len() <= CHUNK {
+            return TurboShake::new(self.capacity, &s, 0x07).result(l);
+        }
+
+        let cv_len = self.capacity / 8;
+        let chunks = s.chunks(CHUNK).collect::<Vec<_>>();
+
+        let mut node = chunks[0].to_vec();
+        node.extend_from_slice(&[0x03, 0, 0, 0, 0, 0, 0, 0]);
+
+        for chunk in &chunks[1..] {
+            node.append(&mut TurboShake::new(self.capacity, chunk, 0x0B).result(cv_len));
+        }
+
+        node.append(&mut length_encode(chunks.len() - 1));
+        node.extend_from_slice(&[0xFF, 0xFF]);
+
+        TurboShake::new(self.capacity, &node, 0x06).result(l)
+    }
+}
+
+/// `x` as big-endian bytes without leading zeros, followed by their count.
+fn length_encode(x: usize) -> Vec<u8> {
+    let mut res = x
+        .to_be_bytes()
+        .into_iter()
+        .skip_while(|b| *b == 0)
+        .collect::<Vec<_>>();
+    res.push(res.len() as u8);
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex;
+
+    /// The pattern `ptn(n)` of RFC 9861: `00 01 .. FA` repeated, cut to `n`.
+    fn ptn(n: usize) -> Vec<u8> {
+        (0..n).map(|i| (i % 251) as u8).collect()
+    }
+
+    /// RFC 9861, section 5, with the empty message and customization.
+    #[test]
+    fn empty() {
+        assert_eq!(
+            KangarooTwelve::kt128(&[], &[]).result(64),
+            unhex(concat!(
+                "1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5",
+                "4269c056b8c82e48276038b6d292966cc07a3d4645272e31ff38508139eb0a71",
+            ))
+        );
+        assert_eq!(
+            KangarooTwelve::kt256(&[], &[]).result(64),
+            unhex(concat!(
+                "b23d2e9cea9f4904e02bec06817fc10ce38ce8e93ef4c89e6537076af8646404",
+                "e3e8b68107b8833a5d30490aa33482353fd4adc7148ecb782855003aaebde4a9",
+            ))
+        );
+    }
+
+    /// RFC 9861, section 5: `ptn(17^4)` spans eleven chunks.
+    #[test]
+    fn tree() {
+        assert_eq!(
+            KangarooTwelve::kt128(&ptn(17usize.pow(4)), &[]).result(32),
+            unhex("8701045e22205345ff4dda05555cbb5c3af1a771c2b89baef37db43d9998b9fe")
+        );
+    }
+}
@@ -1,9 +1,43 @@
+pub mod duplex;
+pub mod hmac;
+pub mod k12;
+pub mod merlin;
 pub mod sha1;
 pub mod sha256;
 pub mod sha3;
+pub mod sha384;
 pub mod sha512;
+pub mod strobe;
+pub mod turboshake;
+pub mod xoodoo;
+pub mod xoodyak;
 
-use self::{sha1::Sha1, sha256::Sha256, sha512::Sha512};
+use std::fmt::{Display, LowerHex};
+
+use self::{sha1::Sha1, sha256::Sha256, sha384::Sha384, sha512::Sha512};
+
+/// The working variables `a..h` after round `t` of message block `block`, as
+/// listed in the NIST SHA-2 example documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sha2Round<W> {
+    pub block: usize,
+    pub t: usize,
+    pub vars: [W; 8],
+}
+
+impl<W: LowerHex> Display for Sha2Round<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = 2 * std::mem::size_of::<W>();
+
+        write!(f, "t={:>2}:", self.t)?;
+
+        for var in &self.vars {
+            write!(f, " {var:0width$x}")?;
+        }
+
+        Ok(())
+    }
+}
 
 pub fn print_digest(sha: usize, msg: &str) -> String {
     match sha {
@@ -15,6 +49,11 @@ pub fn print_digest(sha: usize, msg: &str) -> String {
             .result()
             .iter()
             .fold("".to_string(), |acc, x| format!("{acc}{:x}", *x)),
+        384 => Sha384::new(msg.as_bytes())
+            .result()
+            .chunks_exact(8)
+            .map(|word| u64::from_be_bytes(word.try_into().unwrap()))
+            .fold("".to_string(), |acc, x| format!("{acc}{x:016x}")),
         512 => Sha512::new(msg.as_bytes())
             .result()
             .iter()
@@ -1,23 +1,89 @@
+use super::Sha2Round;
+
 #[derive(Debug)]
 pub struct Sha256 {
     pub hash: [u32; 8],
     state: Vec<[u32; 16]>,
+    buffer: Vec<u8>,
+    len: u64,
+    blocks: usize,
     schedule: [u32; 64],
+    trace: Option<Vec<Sha2Round<u32>>>,
 }
 
 impl Sha256 {
     pub fn new(msg: &[u8]) -> Sha256 {
-        Sha256 {
-            hash: [
+        let mut sha = Sha256::from_midstate(
+            [
                 0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB,
                 0x5BE0CD19,
             ],
-            state: preprocess(msg),
+            0,
+        );
+        sha.absorb(msg);
+
+        sha
+    }
+
+    /// Resume hashing from the chaining value `hash` after the first `len`
+    /// bytes of a message, `len` being a multiple of 64.
+    pub fn from_midstate(hash: [u32; 8], len: u64) -> Sha256 {
+        assert!(len.is_multiple_of(64), "midstate not on a block boundary");
+
+        Sha256 {
+            hash,
+            state: vec![],
+            buffer: vec![],
+            len,
+            blocks: 0,
             schedule: [0u32; 64],
+            trace: None,
         }
     }
 
+    /// The hasher of the BIP-340 tagged hash
+    /// `SHA256(SHA256(tag) || SHA256(tag) || msg)`, with the tag block
+    /// already compressed. Keep its [`Sha256::midstate`] to skip even that.
+    pub fn tagged(tag: &[u8]) -> Sha256 {
+        let tag_hash = Sha256::new(tag).result();
+
+        let mut sha = Sha256::new(&[]);
+        sha.update(&[tag_hash, tag_hash].concat());
+
+        sha
+    }
+
+    /// The chaining value and length of the message so far, for
+    /// [`Sha256::from_midstate`]; `None` unless it ends on a block boundary.
+    pub fn midstate(&self) -> Option<([u32; 8], u64)> {
+        (self.state.is_empty() && self.buffer.is_empty()).then_some((self.hash, self.len))
+    }
+
+    /// Append `msg` to the message, compressing every complete block.
+    pub fn update(&mut self, msg: &[u8]) {
+        self.absorb(msg);
+        self.compute();
+    }
+
+    /// Queue the complete blocks of `msg` for compression.
+    fn absorb(&mut self, msg: &[u8]) {
+        self.len += msg.len() as u64;
+        self.buffer.extend_from_slice(msg);
+
+        let full = self.buffer.len() / 64 * 64;
+
+        for block in self.buffer[..full].chunks_exact(64) {
+            self.state.push(std::array::from_fn(|i| {
+                u32::from_be_bytes(block[4 * i..4 * i + 4].try_into().unwrap())
+            }));
+        }
+
+        self.buffer.drain(..full);
+    }
+
     pub fn result(&mut self) -> [u8; 32] {
+        self.state.append(&mut preprocess(&self.buffer, self.len));
+        self.buffer.clear();
         self.compute();
 
         self.hash
@@ -29,9 +95,21 @@ impl Sha256 {
             .unwrap()
     }
 
+    /// Record the working variables after every round of [`Sha256::result`].
+    pub fn traced(mut self) -> Sha256 {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    /// Take the rounds recorded so far, if tracing was enabled.
+    pub fn take_trace(&mut self) -> Option<Vec<Sha2Round<u32>>> {
+        self.trace.as_mut().map(std::mem::take)
+    }
+
     fn compute(&mut self) {
-        for i in 1..=self.state.len() {
-            self.schedule = prepare_schedule(self.schedule, self.state[i - 1]);
+        for block in std::mem::take(&mut self.state) {
+            self.schedule = prepare_schedule(self.schedule, block);
+            self.blocks += 1;
 
             let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h]: [u32; 8] = self.hash;
 
@@ -52,6 +130,14 @@ impl Sha256 {
                 c = b;
                 b = a;
                 a = t1.wrapping_add(t2);
+
+                if let Some(trace) = &mut self.trace {
+                    trace.push(Sha2Round {
+                        block: self.blocks - 1,
+                        t,
+                        vars: [a, b, c, d, e, f, g, h],
+                    });
+                }
             }
 
             let prev = self.hash;
@@ -69,7 +155,8 @@ impl Sha256 {
     }
 }
 
-fn preprocess(msg: &[u8]) -> Vec<[u32; 16]> {
+/// Pad the final, partial block `msg` of a message of `total` bytes.
+fn preprocess(msg: &[u8], total: u64) -> Vec<[u32; 16]> {
     let len = msg.len() + 64 - msg.len() % 64;
     let mut res = vec![0; len + 64 * (msg.len() % 64 >= 56) as usize];
 
@@ -77,7 +164,7 @@ fn preprocess(msg: &[u8]) -> Vec<[u32; 16]> {
     res[msg.len()] = 0b10000000;
 
     let l = res.len();
-    res[l - 8..].copy_from_slice(&(msg.len() as u64 * 8).to_be_bytes()[..]);
+    res[l - 8..].copy_from_slice(&(total * 8).to_be_bytes()[..]);
 
     res.array_chunks()
         .map(|xs| u32::from_be_bytes(*xs))
@@ -137,3 +224,36 @@ static K: [u32; 64] = [
     0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
     0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex;
+
+    /// The one-block message "abc" of FIPS 180-4, appendix B.1, against the
+    /// intermediate values of the NIST SHA-256 example.
+    #[test]
+    fn trace() {
+        let mut sha = Sha256::new(b"abc").traced();
+
+        assert_eq!(
+            sha.result().to_vec(),
+            unhex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+
+        let trace = sha.take_trace().unwrap();
+        assert_eq!(trace.len(), 64);
+        assert!(trace
+            .iter()
+            .enumerate()
+            .all(|(t, round)| round.block == 0 && round.t == t));
+        assert_eq!(
+            trace[0].to_string(),
+            "t= 0: 5d6aebcd 6a09e667 bb67ae85 3c6ef372 fa2a4622 510e527f 9b05688c 1f83d9ab"
+        );
+        assert_eq!(
+            trace[63].to_string(),
+            "t=63: 506e3058 d39a2165 04d24d6c b85e2ce9 5ef50f24 fb121210 948d25b6 961f4894"
+        );
+    }
+}
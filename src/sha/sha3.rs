@@ -59,6 +59,7 @@ lazy_static! {
 pub struct Sha3 {
     pub bstr: Vec<bool>,
     pub state: [[Vec<bool>; 5]; 5],
+    trace: Option<Vec<KeccakRound>>,
 }
 
 impl Display for Sha3 {
@@ -88,7 +89,11 @@ impl Sha3 {
     pub fn new(bstr: Vec<bool>) -> Sha3 {
         let state = Sha3::bstr_to_state(bstr.clone());
 
-        Sha3 { state, bstr }
+        Sha3 {
+            state,
+            bstr,
+            trace: None,
+        }
     }
 
     fn bstr_to_state(bstr: Vec<bool>) -> [[Vec<bool>; 5]; 5] {
@@ -113,14 +118,22 @@ impl Sha3 {
     }
 
     pub fn keccak_p(bstr: Vec<bool>, n_r: isize) -> Vec<bool> {
+        Sha3::permute(bstr, n_r, None)
+    }
+
+    /// Same as [`Sha3::keccak_p`], but appends the state after every step
+    /// mapping of every round to `trace`.
+    pub fn keccak_p_traced(bstr: Vec<bool>, n_r: isize, trace: &mut Vec<KeccakRound>) -> Vec<bool> {
+        Sha3::permute(bstr, n_r, Some(trace))
+    }
+
+    fn permute(bstr: Vec<bool>, n_r: isize, mut trace: Option<&mut Vec<KeccakRound>>) -> Vec<bool> {
         let w = bstr.len() / 25;
         let l = (w as f32).log2().round() as isize;
         let mut a = Sha3::bstr_to_state(bstr);
 
-        for i_r in (12 + 2 * l - n_r)..(12 + 2 * l - 1) {
-            println!("Round {i_r}");
-            a = rnd(a, i_r);
-            println!("\n");
+        for i_r in (12 + 2 * l - n_r)..(12 + 2 * l) {
+            a = rnd(a, i_r, trace.as_deref_mut());
         }
 
         Sha3::state_to_bstr(a)
@@ -133,9 +146,9 @@ impl Sha3 {
         Sha3::keccak_p(bstr, 12 + 2 * l)
     }
 
-    pub fn sponge<F, Pad>(f: F, pad: Pad, rate: usize, bstr: Vec<bool>, d: usize) -> Vec<bool>
+    pub fn sponge<F, Pad>(mut f: F, pad: Pad, rate: usize, bstr: Vec<bool>, d: usize) -> Vec<bool>
     where
-        F: Fn(Vec<bool>) -> Vec<bool>,
+        F: FnMut(Vec<bool>) -> Vec<bool>,
         Pad: Fn(isize, isize) -> Vec<bool>,
     {
         let mut p = bstr.clone();
@@ -160,8 +173,7 @@ impl Sha3 {
         let mut z: Vec<bool> = vec![];
 
         loop {
-            s.truncate(rate);
-            z.append(&mut s);
+            z.extend_from_slice(&s[..rate]);
 
             if d <= z.len() {
                 z.truncate(d);
@@ -175,8 +187,12 @@ impl Sha3 {
     pub fn keccak(&mut self, capacity: usize) -> &mut Sha3 {
         self.bstr.append(&mut vec![false, true]);
 
+        let trace = &mut self.trace;
         self.bstr = Sha3::sponge(
-            Sha3::keccak_f,
+            |s| match trace {
+                Some(trace) => Sha3::keccak_p_traced(s, 24, trace),
+                None => Sha3::keccak_f(s),
+            },
             pad,
             1600 - capacity,
             self.bstr.clone(),
@@ -185,25 +201,83 @@ impl Sha3 {
 
         self
     }
+
+    /// Record every Keccak-f round of subsequent [`Sha3::keccak`] calls.
+    pub fn traced(mut self) -> Sha3 {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    /// Take the rounds recorded so far, if tracing was enabled.
+    pub fn take_trace(&mut self) -> Option<Vec<KeccakRound>> {
+        self.trace.as_mut().map(std::mem::take)
+    }
+}
+
+/// The state after each step mapping of one round of Keccak-p, laid out as in
+/// the NIST "intermediate values" examples.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeccakRound {
+    pub i_r: isize,
+    pub theta: Vec<bool>,
+    pub rho: Vec<bool>,
+    pub pi: Vec<bool>,
+    pub chi: Vec<bool>,
+    pub iota: Vec<bool>,
 }
 
-fn rnd(state: [[Vec<bool>; 5]; 5], _i_r: isize) -> [[Vec<bool>; 5]; 5] {
+impl Display for KeccakRound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Round #{}", self.i_r)?;
+
+        for (name, bstr) in [
+            ("Theta", &self.theta),
+            ("Rho", &self.rho),
+            ("Pi", &self.pi),
+            ("Chi", &self.chi),
+            ("Iota", &self.iota),
+        ] {
+            writeln!(f, "After {name}:\n{}", Sha3::new(bstr.clone()))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn rnd(
+    state: [[Vec<bool>; 5]; 5],
+    i_r: isize,
+    trace: Option<&mut Vec<KeccakRound>>,
+) -> [[Vec<bool>; 5]; 5] {
+    let Some(trace) = trace else {
+        return iota(chi(pi(rho(theta(state)))), i_r);
+    };
+
     let a = theta(state);
-    println!("Theta:\n{}", Sha3::new(Sha3::state_to_bstr(a.clone())));
-    // let a = rho(a);
-    // println!("Rho:\n{}", Sha3::new(Sha3::state_to_bstr(a.clone())));
-    // let a = pi(state);
-    // println!("Pi:\n{}", Sha3::new(Sha3::state_to_bstr(a.clone())));
-    // let a = chi(a);
-    // println!("Chi:\n{}", Sha3::new(Sha3::state_to_bstr(a.clone())));
-    // let a = iota(a, i_r);
-    // println!("Iota:\n{}", Sha3::new(Sha3::state_to_bstr(a.clone())));
+    let theta = Sha3::state_to_bstr(a.clone());
+    let a = rho(a);
+    let rho = Sha3::state_to_bstr(a.clone());
+    let a = pi(a);
+    let pi = Sha3::state_to_bstr(a.clone());
+    let a = chi(a);
+    let chi = Sha3::state_to_bstr(a.clone());
+    let a = iota(a, i_r);
+    let iota = Sha3::state_to_bstr(a.clone());
+
+    trace.push(KeccakRound {
+        i_r,
+        theta,
+        rho,
+        pi,
+        chi,
+        iota,
+    });
 
     a
 }
 
 /// x > 0, m >= 0
-fn pad(x: isize, m: isize) -> Vec<bool> {
+pub fn pad(x: isize, m: isize) -> Vec<bool> {
     let j = (-m - 2).rem_euclid(x);
 
     let mut p = vec![true];
@@ -213,8 +287,65 @@ fn pad(x: isize, m: isize) -> Vec<bool> {
     p
 }
 
+/// `l` bytes of SHAKE128 output.
+pub fn shake128(msg: &[u8], l: usize) -> Vec<u8> {
+    shake(256, msg, l)
+}
+
+/// `l` bytes of SHAKE256 output.
+pub fn shake256(msg: &[u8], l: usize) -> Vec<u8> {
+    shake(512, msg, l)
+}
+
+/// The SHAKE XOFs append the suffix `1111` before padding.
+fn shake(capacity: usize, msg: &[u8], l: usize) -> Vec<u8> {
+    let mut bstr = bytes_to_bstr(msg);
+    bstr.append(&mut vec![true; 4]);
+
+    bstr_to_bytes(&Sha3::sponge(
+        Sha3::keccak_f,
+        pad,
+        1600 - capacity,
+        bstr,
+        8 * l,
+    ))
+}
+
+/// The original Keccak-256 submission, as used by Ethereum: SHA3-256 without
+/// the `01` domain suffix.
+pub fn keccak256(msg: &[u8]) -> [u8; 32] {
+    bstr_to_bytes(&Sha3::sponge(
+        Sha3::keccak_f,
+        pad,
+        1088,
+        bytes_to_bstr(msg),
+        256,
+    ))
+    .try_into()
+    .unwrap()
+}
+
 // Helpers
 
+/// Bytes to a bit string, least significant bit of each byte first.
+pub fn bytes_to_bstr(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|x| (0..8).map(move |i| (x >> i) & 1 == 1))
+        .collect()
+}
+
+/// Inverse of [`bytes_to_bstr`]; `bstr.len()` must be a multiple of 8.
+pub fn bstr_to_bytes(bstr: &[bool]) -> Vec<u8> {
+    bstr.chunks_exact(8)
+        .map(|bits| {
+            bits.iter()
+                .enumerate()
+                .fold(0, |acc, (i, bit)| acc | (*bit as u8) << i)
+        })
+        .collect()
+}
+
 pub fn row(state: [[Vec<bool>; 5]; 5], y: usize, z: usize) -> [bool; 5] {
     state.map(|x| x[y][z])
 }
@@ -240,9 +371,7 @@ pub fn sheet(state: [[Vec<bool>; 5]; 5], x: usize) -> [Vec<bool>; 5] {
 }
 
 // Step Mappings
-// ALL state accesses on the x and y axis are in the order:
-// 3 4 0 1 2
-// so, just add 2 and ensure mod 5.
+// These follow Algorithms 1-6 of FIPS 202, with `state[x][y][z]` = A[x, y, z].
 
 pub fn theta(state: [[Vec<bool>; 5]; 5]) -> [[Vec<bool>; 5]; 5] {
     let w = state[0][0].len();
@@ -251,17 +380,16 @@ pub fn theta(state: [[Vec<bool>; 5]; 5]) -> [[Vec<bool>; 5]; 5] {
     let mut a: [[Vec<bool>; 5]; 5] = state.clone();
 
     for (x, z) in iproduct!(0..5, 0..w) {
-        let x = (x + 2) % 5;
         c[x][z] =
-            state[x][3][z] ^ state[x][4][z] ^ state[x][0][z] ^ state[x][1][z] ^ state[x][2][z];
+            state[x][0][z] ^ state[x][1][z] ^ state[x][2][z] ^ state[x][3][z] ^ state[x][4][z];
     }
 
     for (x, z) in iproduct!(0..5, 0..w) {
-        d[x][z] = c[(x + 5 - 1) % 5][z] ^ c[(x + 1) % 5][(z + w - 1) % w];
+        d[x][z] = c[(x + 4) % 5][z] ^ c[(x + 1) % 5][(z + w - 1) % w];
     }
 
     for (x, y, z) in iproduct!(0..5, 0..5, 0..w) {
-        a[(x + 2) % 5][(y + 2) % 5][z] ^= d[x][z];
+        a[x][y][z] ^= d[x][z];
     }
 
     a
@@ -269,19 +397,16 @@ pub fn theta(state: [[Vec<bool>; 5]; 5]) -> [[Vec<bool>; 5]; 5] {
 
 pub fn rho(state: [[Vec<bool>; 5]; 5]) -> [[Vec<bool>; 5]; 5] {
     let w = state[0][0].len();
-    let mut a = INIT.clone();
-
-    a[2][2].copy_from_slice(&state[0][0]);
+    let mut a = state.clone();
 
     let (mut x, mut y) = (1, 0);
 
     for t in 0..24 {
         for z in 0..w {
-            a[(x + 2) % 5][(y + 2) % 5][z] =
-                state[(x + 2) % 5][(y + 2) % 5][(z + 10 * w - (t + 1) * (t + 2) / 2) % w];
-
-            (x, y) = (y, (2 * x + 3 * y) % 5)
+            a[x][y][z] = state[x][y][(z + 10 * w - (t + 1) * (t + 2) / 2 % w) % w];
         }
+
+        (x, y) = (y, (2 * x + 3 * y) % 5)
     }
 
     a
@@ -289,10 +414,10 @@ pub fn rho(state: [[Vec<bool>; 5]; 5]) -> [[Vec<bool>; 5]; 5] {
 
 pub fn pi(state: [[Vec<bool>; 5]; 5]) -> [[Vec<bool>; 5]; 5] {
     let w = state[0][0].len();
-    let mut a = INIT.clone();
+    let mut a = state.clone();
 
     for (x, y, z) in iproduct!(0..5, 0..5, 0..w) {
-        a[(x + 2) % 5][(y + 2) % 5][z] = state[(x + 3 * y + 2) % 5][(x + 2) % 5][z];
+        a[x][y][z] = state[(x + 3 * y) % 5][x][z];
     }
 
     a
@@ -303,8 +428,7 @@ pub fn chi(state: [[Vec<bool>; 5]; 5]) -> [[Vec<bool>; 5]; 5] {
     let mut a = state.clone();
 
     for (x, y, z) in iproduct!(0..5, 0..5, 0..w) {
-        a[(x + 2) % 5][(y + 2) % 5][z] ^=
-            (state[(x + 3) % 5][(y + 2) % 5][z] ^ true) & state[(x + 4) % 5][(y + 2) % 5][z]
+        a[x][y][z] ^= (state[(x + 1) % 5][y][z] ^ true) & state[(x + 2) % 5][y][z]
     }
 
     a
@@ -318,12 +442,12 @@ pub fn iota(state: [[Vec<bool>; 5]; 5], i_r: isize) -> [[Vec<bool>; 5]; 5] {
 
     let mut r_c = vec![false; w];
 
-    for j in 0..l {
+    for j in 0..=l {
         r_c[2usize.pow(j as u32) - 1] = rc(j + 7 * i_r);
     }
 
     for (z, r_c) in r_c.iter().enumerate() {
-        a[2][2][z] ^= r_c
+        a[0][0][z] ^= r_c
     }
 
     a
@@ -336,8 +460,8 @@ pub fn rc(t: isize) -> bool {
 
     let mut r = vec![true, false, false, false, false, false, false, false];
 
-    for _ in 1..(t % 255) {
-        r.insert(0, true);
+    for _ in 1..=(t % 255) {
+        r.insert(0, false);
         r[0] ^= r[8];
         r[4] ^= r[8];
         r[5] ^= r[8];
@@ -347,3 +471,298 @@ pub fn rc(t: isize) -> bool {
 
     r[0]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex;
+
+    fn sha3(msg: &[u8], d: usize) -> Vec<u8> {
+        bstr_to_bytes(&Sha3::new(bytes_to_bstr(msg)).keccak(2 * d).bstr)
+    }
+
+    #[test]
+    fn sha3_224() {
+        assert_eq!(
+            sha3(b"", 224),
+            unhex("6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7")
+        );
+        assert_eq!(
+            sha3(b"abc", 224),
+            unhex("e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf")
+        );
+        assert_eq!(
+            sha3(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                224
+            ),
+            unhex("8a24108b154ada21c9fd5574494479ba5c7e7ab76ef264ead0fcce33")
+        );
+        assert_eq!(
+            sha3(&[0xa3; 200], 224),
+            unhex("9376816aba503f72f96ce7eb65ac095deee3be4bf9bbc2a1cb7e11e0")
+        );
+    }
+
+    #[test]
+    fn sha3_256() {
+        assert_eq!(
+            sha3(b"", 256),
+            unhex("a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a")
+        );
+        assert_eq!(
+            sha3(b"abc", 256),
+            unhex("3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532")
+        );
+        assert_eq!(
+            sha3(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                256
+            ),
+            unhex("41c0dba2a9d6240849100376a8235e2c82e1b9998a999e21db32dd97496d3376")
+        );
+        assert_eq!(
+            sha3(&[0xa3; 200], 256),
+            unhex("79f38adec5c20307a98ef76e8324afbfd46cfd81b22e3973c65fa1bd9de31787")
+        );
+    }
+
+    #[test]
+    fn sha3_384() {
+        assert_eq!(
+            sha3(b"", 384),
+            unhex(concat!(
+                "0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2a",
+                "c3713831264adb47fb6bd1e058d5f004",
+            ))
+        );
+        assert_eq!(
+            sha3(b"abc", 384),
+            unhex(concat!(
+                "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b2",
+                "98d88cea927ac7f539f1edf228376d25",
+            ))
+        );
+        assert_eq!(
+            sha3(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                384
+            ),
+            unhex(concat!(
+                "991c665755eb3a4b6bbdfb75c78a492e8c56a22c5c4d7e429bfdbc32b9d4ad5a",
+                "a04a1f076e62fea19eef51acd0657c22",
+            ))
+        );
+        assert_eq!(
+            sha3(&[0xa3; 200], 384),
+            unhex(concat!(
+                "1881de2ca7e41ef95dc4732b8f5f002b189cc1e42b74168ed1732649ce1dbcdd",
+                "76197a31fd55ee989f2d7050dd473e8f",
+            ))
+        );
+    }
+
+    #[test]
+    fn sha3_512() {
+        assert_eq!(
+            sha3(b"", 512),
+            unhex(concat!(
+                "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a6",
+                "15b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26",
+            ))
+        );
+        assert_eq!(
+            sha3(b"abc", 512),
+            unhex(concat!(
+                "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e",
+                "10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0",
+            ))
+        );
+        assert_eq!(
+            sha3(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                512
+            ),
+            unhex(concat!(
+                "04a371e84ecfb5b8b77cb48610fca8182dd457ce6f326a0fd3d7ec2f1e91636d",
+                "ee691fbe0c985302ba1b0d8dc78c086346b533b49c030d99a27daf1139d6e75e",
+            ))
+        );
+        assert_eq!(
+            sha3(&[0xa3; 200], 512),
+            unhex(concat!(
+                "e76dfad22084a8b1467fcf2ffa58361bec7628edf5f3fdc0e4805dc48caeeca8",
+                "1b7c13c30adf52a3659584739a2df46be589c51ca1a4a8416df6545a1ce8ba00",
+            ))
+        );
+    }
+
+    #[test]
+    fn shake_128() {
+        assert_eq!(
+            shake128(b"", 64),
+            unhex(concat!(
+                "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26",
+                "3cb1eea988004b93103cfb0aeefd2a686e01fa4a58e8a3639ca8a1e3f9ae57e2",
+            ))
+        );
+        assert_eq!(
+            shake128(b"abc", 64),
+            unhex(concat!(
+                "5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8",
+                "44c50af32acd3f2cdd066568706f509bc1bdde58295dae3f891a9a0fca578378",
+            ))
+        );
+        assert_eq!(
+            shake128(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                64
+            ),
+            unhex(concat!(
+                "1a96182b50fb8c7e74e0a707788f55e98209b8d91fade8f32f8dd5cff7bf21f5",
+                "4ee5f19550825a6e070030519e944263ac1c6765287065621f9fcb3201723e32",
+            ))
+        );
+        assert_eq!(
+            shake128(&[0xa3; 200], 64),
+            unhex(concat!(
+                "131ab8d2b594946b9c81333f9bb6e0ce75c3b93104fa3469d3917457385da037",
+                "cf232ef7164a6d1eb448c8908186ad852d3f85a5cf28da1ab6fe343817197846",
+            ))
+        );
+    }
+
+    #[test]
+    fn shake_256() {
+        assert_eq!(
+            shake256(b"", 64),
+            unhex(concat!(
+                "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f",
+                "d75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be",
+            ))
+        );
+        assert_eq!(
+            shake256(b"abc", 64),
+            unhex(concat!(
+                "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739",
+                "d5a15bef186a5386c75744c0527e1faa9f8726e462a12a4feb06bd8801e751e4",
+            ))
+        );
+        assert_eq!(
+            shake256(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                64
+            ),
+            unhex(concat!(
+                "4d8c2dd2435a0128eefbb8c36f6f87133a7911e18d979ee1ae6be5d4fd2e3329",
+                "40d8688a4e6a59aa8060f1f9bc996c05aca3c696a8b66279dc672c740bb224ec",
+            ))
+        );
+        assert_eq!(
+            shake256(&[0xa3; 200], 64),
+            unhex(concat!(
+                "cd8a920ed141aa0407a22d59288652e9d9f1a7ee0c1e7c1ca699424da84a904d",
+                "2d700caae7396ece96604440577da4f3aa22aeb8857f961c4cd8e06f0ae6610b",
+            ))
+        );
+    }
+
+    #[test]
+    fn keccak() {
+        assert_eq!(
+            keccak256(b"").to_vec(),
+            unhex("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+        );
+        assert_eq!(
+            keccak256(b"abc").to_vec(),
+            unhex("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45")
+        );
+    }
+
+    /// The first and last rounds of SHA3-256 of the empty message, from the
+    /// NIST example with intermediate values.
+    #[test]
+    fn trace() {
+        let mut sha = Sha3::new(vec![]).traced();
+        sha.keccak(512);
+
+        let trace = sha.take_trace().unwrap();
+        assert_eq!(trace.len(), 24);
+        assert_eq!(
+            bstr_to_bytes(&trace[0].theta),
+            unhex(concat!(
+                "0700000000000000060000000000000000000000000000800000000000000000",
+                "0c00000000000000010000000000000006000000000000000000000000000080",
+                "00000000000000000c0000000000000001000000000000000600000000000000",
+                "000000000000008000000000000000000c000000000000000100000000000000",
+                "0600000000000080000000000000008000000000000000000c00000000000000",
+                "0100000000000000060000000000000000000000000000800000000000000000",
+                "0c00000000000000",
+            ))
+        );
+        assert_eq!(
+            bstr_to_bytes(&trace[0].rho),
+            unhex(concat!(
+                "07000000000000000c0000000000000000000000000000200000000000000000",
+                "0000006000000000000000001000000000000000006000002000000000000000",
+                "00000000000000000000c0000000000008000000000000000018000000000000",
+                "0000000000040000000000000000000000000000000600000000000000020000",
+                "0000000000d0000000400000000000000000000000000000000c000000000000",
+                "0000040000000000180000000000000000000000000000100000000000000000",
+                "0000030000000000",
+            ))
+        );
+        assert_eq!(
+            bstr_to_bytes(&trace[0].pi),
+            unhex(concat!(
+                "0700000000000000000000000060000000000000000400000000000000000000",
+                "000003000000000000000000000000000000c000000000000800000000000000",
+                "0000000000d0000000000000000000100c000000000000002000000000000000",
+                "0000000000000000000c00000000000000000400000000000000006000000000",
+                "0000000010000000001800000000000000400000000000000000000000000000",
+                "0000000000000020000000000000000000000000000600000000000000020000",
+                "1800000000000000",
+            ))
+        );
+        assert_eq!(
+            bstr_to_bytes(&trace[0].chi),
+            unhex(concat!(
+                "0700000000040000000000000060000000000300000400000700000000000000",
+                "000003000060000008000000000000000000c00000d000000800000000000010",
+                "0000000000d000000000c000000000100c00000000000000200c000000000000",
+                "00000400000000000c0c00000000000020000400000000000018006000000000",
+                "0040000010000000001800000000000000400060000000000000000010000000",
+                "0000000000060020000000000000000018000000000600000000000000020020",
+                "1800000000000000",
+            ))
+        );
+        assert_eq!(
+            bstr_to_bytes(&trace[0].iota),
+            unhex(concat!(
+                "0600000000040000000000000060000000000300000400000700000000000000",
+                "000003000060000008000000000000000000c00000d000000800000000000010",
+                "0000000000d000000000c000000000100c00000000000000200c000000000000",
+                "00000400000000000c0c00000000000020000400000000000018006000000000",
+                "0040000010000000001800000000000000400060000000000000000010000000",
+                "0000000000060020000000000000000018000000000600000000000000020020",
+                "1800000000000000",
+            ))
+        );
+        assert_eq!(
+            bstr_to_bytes(&trace[23].iota),
+            unhex(concat!(
+                "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+                "5266beb7346bf3e26695ccca215987ff89bab376577bd9803b316afc55bdde28",
+                "cc8ee4f1193dac03e934e4c1ec3a1978791ee8af23a987c2331f6001e34a6821",
+                "5fe7099e467e2e28b8b682c2d21e7dd14e43afadd2e050f0b089a96afbf67553",
+                "1ef1fa3260b9c6c2b2a155f0d34d6863b2c28e988b3908d926d30b3e90103f91",
+                "1798474d6634fc3358de8f071a5c712b79973651927c0b145eebbdaaa7437385",
+                "e5707bfb0e6e1392",
+            ))
+        );
+        assert_eq!(
+            trace.iter().map(|round| round.i_r).collect::<Vec<_>>(),
+            (0..24).collect::<Vec<_>>()
+        );
+    }
+}
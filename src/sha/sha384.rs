@@ -0,0 +1,95 @@
+use super::{sha512::Sha512, Sha2Round};
+
+/// SHA-384: SHA-512 with its own initial hash value, truncated to 48 bytes.
+pub struct Sha384(Sha512);
+
+impl Sha384 {
+    pub fn new(msg: &[u8]) -> Sha384 {
+        Sha384(Sha512::with_initial_hash(
+            [
+                0xCBBB9D5DC1059ED8,
+                0x629A292A367CD507,
+                0x9159015A3070DD17,
+                0x152FECD8F70E5939,
+                0x67332667FFC00B31,
+                0x8EB44A8768581511,
+                0xDB0C2E0D64F98FA7,
+                0x47B5481DBEFA4FA4,
+            ],
+            msg,
+        ))
+    }
+
+    /// Append `msg` to the message, compressing every complete block.
+    pub fn update(&mut self, msg: &[u8]) {
+        self.0.update(msg);
+    }
+
+    pub fn result(&mut self) -> [u8; 48] {
+        self.0.result()[..48].try_into().unwrap()
+    }
+
+    /// Record the working variables after every round of [`Sha384::result`].
+    pub fn traced(self) -> Sha384 {
+        Sha384(self.0.traced())
+    }
+
+    /// Take the rounds recorded so far, if tracing was enabled.
+    pub fn take_trace(&mut self) -> Option<Vec<Sha2Round<u64>>> {
+        self.0.take_trace()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sha::print_digest, test_util::unhex};
+
+    /// The one-block message "abc" of FIPS 180-4, appendix D.1, against the
+    /// intermediate values of the NIST SHA-384 example.
+    #[test]
+    fn trace() {
+        let mut sha = Sha384::new(b"abc").traced();
+
+        assert_eq!(
+            sha.result().to_vec(),
+            unhex(concat!(
+                "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed",
+                "8086072ba1e7cc2358baeca134c825a7",
+            ))
+        );
+
+        let trace = sha.take_trace().unwrap();
+        assert_eq!(trace.len(), 80);
+        assert!(trace
+            .iter()
+            .enumerate()
+            .all(|(t, round)| round.block == 0 && round.t == t));
+        assert_eq!(
+            trace[0].to_string(),
+            concat!(
+                "t= 0: 470994ad30873f88 cbbb9d5dc1059ed8 629a292a367cd507 9159015a3070dd17",
+                " bd03f724be6075f9 67332667ffc00b31 8eb44a8768581511 db0c2e0d64f98fa7",
+            )
+        );
+        assert_eq!(
+            trace[79].to_string(),
+            concat!(
+                "t=79: ff44d7e1849dbfb3 5306143f64497b00 95d33150de6df44c 055b73814cf102b4",
+                " 1952e0c3a227c0f2 ca06a219cc701096 c7f7bff08ebf0d30 c4b149710f5d6a71",
+            )
+        );
+    }
+
+    /// The digest keeps its leading zeros, such as the second byte here.
+    #[test]
+    fn print() {
+        assert_eq!(
+            print_digest(384, "abc"),
+            concat!(
+                "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed",
+                "8086072ba1e7cc2358baeca134c825a7",
+            )
+        );
+    }
+}
@@ -1,13 +1,19 @@
+use super::Sha2Round;
+
 pub struct Sha512 {
     pub hash: [u64; 8],
     state: Vec<[u64; 16]>,
+    buffer: Vec<u8>,
+    len: u64,
+    blocks: usize,
     schedule: [u64; 80],
+    trace: Option<Vec<Sha2Round<u64>>>,
 }
 
 impl Sha512 {
     pub fn new(msg: &[u8]) -> Sha512 {
-        Sha512 {
-            hash: [
+        Sha512::with_initial_hash(
+            [
                 0x6A09E667F3BCC908,
                 0xBB67AE8584CAA73B,
                 0x3C6EF372FE94F82B,
@@ -17,12 +23,52 @@ impl Sha512 {
                 0x1F83D9ABFB41BD6B,
                 0x5BE0CD19137E2179,
             ],
-            state: preprocess(msg),
+            msg,
+        )
+    }
+
+    /// The SHA-512 compression with another initial hash value, for the
+    /// truncated variants.
+    pub(crate) fn with_initial_hash(hash: [u64; 8], msg: &[u8]) -> Sha512 {
+        let mut sha = Sha512 {
+            hash,
+            state: vec![],
+            buffer: vec![],
+            len: 0,
+            blocks: 0,
             schedule: [0u64; 80],
+            trace: None,
+        };
+        sha.absorb(msg);
+
+        sha
+    }
+
+    /// Append `msg` to the message, compressing every complete block.
+    pub fn update(&mut self, msg: &[u8]) {
+        self.absorb(msg);
+        self.compute();
+    }
+
+    /// Queue the complete blocks of `msg` for compression.
+    fn absorb(&mut self, msg: &[u8]) {
+        self.len += msg.len() as u64;
+        self.buffer.extend_from_slice(msg);
+
+        let full = self.buffer.len() / 128 * 128;
+
+        for block in self.buffer[..full].chunks_exact(128) {
+            self.state.push(std::array::from_fn(|i| {
+                u64::from_be_bytes(block[8 * i..8 * i + 8].try_into().unwrap())
+            }));
         }
+
+        self.buffer.drain(..full);
     }
 
     pub fn result(&mut self) -> [u8; 64] {
+        self.state.append(&mut preprocess(&self.buffer, self.len));
+        self.buffer.clear();
         self.compute();
 
         self.hash
@@ -34,9 +80,21 @@ impl Sha512 {
             .unwrap()
     }
 
+    /// Record the working variables after every round of [`Sha512::result`].
+    pub fn traced(mut self) -> Sha512 {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    /// Take the rounds recorded so far, if tracing was enabled.
+    pub fn take_trace(&mut self) -> Option<Vec<Sha2Round<u64>>> {
+        self.trace.as_mut().map(std::mem::take)
+    }
+
     fn compute(&mut self) {
-        for i in 1..=self.state.len() {
-            self.schedule = prepare_schedule(self.schedule, self.state[i - 1]);
+        for block in std::mem::take(&mut self.state) {
+            self.schedule = prepare_schedule(self.schedule, block);
+            self.blocks += 1;
 
             let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h]: [u64; 8] = self.hash;
 
@@ -57,6 +115,14 @@ impl Sha512 {
                 c = b;
                 b = a;
                 a = t1.wrapping_add(t2);
+
+                if let Some(trace) = &mut self.trace {
+                    trace.push(Sha2Round {
+                        block: self.blocks - 1,
+                        t,
+                        vars: [a, b, c, d, e, f, g, h],
+                    });
+                }
             }
 
             let prev = self.hash;
@@ -74,7 +140,8 @@ impl Sha512 {
     }
 }
 
-fn preprocess(msg: &[u8]) -> Vec<[u64; 16]> {
+/// Pad the final, partial block `msg` of a message of `total` bytes.
+fn preprocess(msg: &[u8], total: u64) -> Vec<[u64; 16]> {
     let len = msg.len() + 128 - msg.len() % 128;
     let mut res = vec![0; len + 128 * (msg.len() % 128 >= 112) as usize];
 
@@ -83,7 +150,7 @@ fn preprocess(msg: &[u8]) -> Vec<[u64; 16]> {
 
     let l = res.len();
     // Please don't try to pass a message >= 2^64 bits of length
-    res[l - 8..].copy_from_slice(&(msg.len() as u64 * 8).to_be_bytes()[..]);
+    res[l - 8..].copy_from_slice(&(total * 8).to_be_bytes()[..]);
 
     res.array_chunks()
         .map(|xs| u64::from_be_bytes(*xs))
@@ -216,3 +283,45 @@ static K: [u64; 80] = [
     0x5fcb6fab3ad6faec,
     0x6c44198c4a475817,
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex;
+
+    /// The one-block message "abc" of FIPS 180-4, appendix C.1, against the
+    /// intermediate values of the NIST SHA-512 example.
+    #[test]
+    fn trace() {
+        let mut sha = Sha512::new(b"abc").traced();
+
+        assert_eq!(
+            sha.result().to_vec(),
+            unhex(concat!(
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a",
+                "2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            ))
+        );
+
+        let trace = sha.take_trace().unwrap();
+        assert_eq!(trace.len(), 80);
+        assert!(trace
+            .iter()
+            .enumerate()
+            .all(|(t, round)| round.block == 0 && round.t == t));
+        assert_eq!(
+            trace[0].to_string(),
+            concat!(
+                "t= 0: f6afceb8bcfcddf5 6a09e667f3bcc908 bb67ae8584caa73b 3c6ef372fe94f82b",
+                " 58cb02347ab51f91 510e527fade682d1 9b05688c2b3e6c1f 1f83d9abfb41bd6b",
+            )
+        );
+        assert_eq!(
+            trace[79].to_string(),
+            concat!(
+                "t=79: 73a54f399fa4b1b2 10d9c4c4295599f6 d67806db8b148677 654ef9abec389ca9",
+                " d08446aa79693ed7 9bb4d39778c07f9e 25c96a7768fb2aa3 ceb9fc3691ce8326",
+            )
+        );
+    }
+}
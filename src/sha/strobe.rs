@@ -0,0 +1,295 @@
+use super::sha3::{bstr_to_bytes, bytes_to_bstr, Sha3};
+
+const FLAG_I: u8 = 1;
+const FLAG_A: u8 = 1 << 1;
+const FLAG_C: u8 = 1 << 2;
+const FLAG_T: u8 = 1 << 3;
+const FLAG_M: u8 = 1 << 4;
+const FLAG_K: u8 = 1 << 5;
+
+/// # STROBE v1.0.2
+/// A protocol framework over Keccak-f[1600]. Every operation is framed by
+/// its flags, so both parties of a protocol end up in the same state iff they
+/// performed the same operations on the same data.
+///
+/// Operations taking `more` continue the previous operation when it is
+/// `true`, which lets data be processed in pieces. Only the `T` (transport)
+/// operations fix the role of this side: the first one decides whether we are
+/// the initiator.
+#[derive(Debug, Clone)]
+pub struct Strobe {
+    st: [u8; 200],
+    rate: usize,
+    pos: usize,
+    pos_begin: u8,
+    cur_flags: u8,
+    i0: Option<u8>,
+}
+
+impl Strobe {
+    /// `sec` is the security level in bits, 128 or 256.
+    pub fn new(proto: &[u8], sec: usize) -> Strobe {
+        assert!(
+            sec == 128 || sec == 256,
+            "STROBE supports 128 or 256 bit security"
+        );
+
+        let rate = 200 - sec / 4 - 2;
+
+        let mut st = [0u8; 200];
+        st[..6].copy_from_slice(&[1, rate as u8 + 2, 1, 0, 1, 96]);
+        st[6..18].copy_from_slice(b"STROBEv1.0.2");
+
+        let mut strobe = Strobe {
+            st,
+            rate,
+            pos: 0,
+            pos_begin: 0,
+            cur_flags: 0,
+            i0: None,
+        };
+        strobe.keccak_f();
+        strobe.meta_ad(proto, false);
+
+        strobe
+    }
+
+    pub fn new128(proto: &[u8]) -> Strobe {
+        Strobe::new(proto, 128)
+    }
+
+    /// Absorb associated data.
+    pub fn ad(&mut self, data: &[u8], more: bool) {
+        self.operate(FLAG_A, &mut data.to_vec(), more);
+    }
+
+    /// [`Strobe::ad`] on framing data, e.g. labels and lengths.
+    pub fn meta_ad(&mut self, data: &[u8], more: bool) {
+        self.operate(FLAG_M | FLAG_A, &mut data.to_vec(), more);
+    }
+
+    /// Overwrite the state with a symmetric key.
+    pub fn key(&mut self, data: &[u8], more: bool) {
+        self.operate(FLAG_A | FLAG_C, &mut data.to_vec(), more);
+    }
+
+    /// Fill `dest` with pseudorandom bytes.
+    pub fn prf(&mut self, dest: &mut [u8], more: bool) {
+        dest.fill(0);
+        self.operate(FLAG_I | FLAG_A | FLAG_C, dest, more);
+    }
+
+    /// Absorb cleartext that is sent to the other party.
+    pub fn send_clr(&mut self, data: &[u8], more: bool) {
+        self.operate(FLAG_A | FLAG_T, &mut data.to_vec(), more);
+    }
+
+    /// Absorb cleartext received from the other party.
+    pub fn recv_clr(&mut self, data: &[u8], more: bool) {
+        self.operate(FLAG_I | FLAG_A | FLAG_T, &mut data.to_vec(), more);
+    }
+
+    /// Encrypt `data` in place.
+    pub fn send_enc(&mut self, data: &mut [u8], more: bool) {
+        self.operate(FLAG_A | FLAG_C | FLAG_T, data, more);
+    }
+
+    /// Decrypt `data` in place.
+    pub fn recv_enc(&mut self, data: &mut [u8], more: bool) {
+        self.operate(FLAG_I | FLAG_A | FLAG_C | FLAG_T, data, more);
+    }
+
+    /// Fill `dest` with a MAC over the transcript so far.
+    pub fn send_mac(&mut self, dest: &mut [u8], more: bool) {
+        dest.fill(0);
+        self.operate(FLAG_C | FLAG_T, dest, more);
+    }
+
+    /// Check a MAC produced by the other party's [`Strobe::send_mac`].
+    pub fn recv_mac(&mut self, mac: &[u8], more: bool) -> bool {
+        let mut mac = mac.to_vec();
+        self.operate(FLAG_I | FLAG_C | FLAG_T, &mut mac, more);
+
+        mac.iter().fold(0, |acc, x| acc | x) == 0
+    }
+
+    /// Zero `len` bytes of the state, preventing rollback.
+    pub fn ratchet(&mut self, len: usize, more: bool) {
+        self.operate(FLAG_C, &mut vec![0; len], more);
+    }
+
+    fn operate(&mut self, flags: u8, data: &mut [u8], more: bool) {
+        assert!(flags & FLAG_K == 0, "K flag is not supported");
+
+        if more {
+            assert_eq!(
+                self.cur_flags, flags,
+                "`more` must continue the same operation"
+            );
+        } else {
+            self.begin_op(flags);
+        }
+
+        let cafter = flags & (FLAG_C | FLAG_I | FLAG_T) == FLAG_C | FLAG_T;
+        let cbefore = flags & FLAG_C != 0 && !cafter;
+
+        self.duplex(data, cbefore, cafter);
+    }
+
+    fn begin_op(&mut self, flags: u8) {
+        let mut adjusted = flags;
+
+        if flags & FLAG_T != 0 {
+            let i0 = *self.i0.get_or_insert(flags & FLAG_I);
+            adjusted ^= i0;
+        }
+
+        let old_begin = self.pos_begin;
+        self.pos_begin = self.pos as u8 + 1;
+        self.cur_flags = flags;
+
+        self.duplex(&mut [old_begin, adjusted], false, false);
+
+        if flags & (FLAG_C | FLAG_K) != 0 && self.pos != 0 {
+            self.run_f();
+        }
+    }
+
+    /// `cbefore` combines the input with the state before absorbing it (to
+    /// decrypt or overwrite), `cafter` outputs the state after absorbing it
+    /// (to encrypt).
+    fn duplex(&mut self, data: &mut [u8], cbefore: bool, cafter: bool) {
+        for byte in data.iter_mut() {
+            if cbefore {
+                *byte ^= self.st[self.pos];
+            }
+
+            self.st[self.pos] ^= *byte;
+
+            if cafter {
+                *byte = self.st[self.pos];
+            }
+
+            self.pos += 1;
+
+            if self.pos == self.rate {
+                self.run_f();
+            }
+        }
+    }
+
+    fn run_f(&mut self) {
+        self.st[self.pos] ^= self.pos_begin;
+        self.st[self.pos + 1] ^= 0x04;
+        self.st[self.rate + 1] ^= 0x80;
+
+        self.keccak_f();
+
+        self.pos = 0;
+        self.pos_begin = 0;
+    }
+
+    fn keccak_f(&mut self) {
+        let st = bstr_to_bytes(&Sha3::keccak_f(bytes_to_bstr(&self.st)));
+        self.st.copy_from_slice(&st);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex;
+
+    /// An initiator and a responder through every operation. The outputs
+    /// were computed with an independent implementation of the STROBE
+    /// specification, which reproduces the merlin crate's
+    /// `equivalence_simple`.
+    #[test]
+    fn session() {
+        let mut a = Strobe::new128(b"strobe test");
+        let mut b = Strobe::new128(b"strobe test");
+
+        for s in [&mut a, &mut b] {
+            s.key(&(0..32).collect::<Vec<u8>>(), false);
+            s.meta_ad(b"nonce", false);
+            s.ad(&[7; 12], false);
+        }
+
+        a.send_clr(b"hello", false);
+        b.recv_clr(b"hello", false);
+
+        // Longer than the rate, so it spans two permutations.
+        let msg = (0..170).collect::<Vec<u8>>();
+        let mut data = msg.clone();
+        a.send_enc(&mut data, false);
+        assert_eq!(
+            data,
+            unhex(concat!(
+                "97db51d7093b5e0a89b29ebd862c988f41706925467ac24942c9b115f9680858",
+                "d573f718f7629df138ae741a5ec53e4f00c8656ab3ec5fa62162c72a7d68a3d9",
+                "38da8c58195facbc586febc91708cdec3d4b86d2fec180a6e7b0835d5c2ec85c",
+                "29bed48c705c7770d0f8f084a0635d72f8451ac7483040063bceb5ddb4d5babd",
+                "1502180db72f0e6166c6794623168a54f4c00b30b32f3a357c5865ca62f89a10",
+                "fdda44789ffe5a03c373",
+            ))
+        );
+        b.recv_enc(&mut data, false);
+        assert_eq!(data, msg);
+
+        let mut mac = [0; 16];
+        a.send_mac(&mut mac, false);
+        assert_eq!(mac.to_vec(), unhex("ffd0eb821ea1f54a099caa57007142e2"));
+        assert!(b.recv_mac(&mac, false));
+
+        // The responder's sends are the initiator's receives.
+        let mut reply = *b"reply";
+        b.send_enc(&mut reply, false);
+        assert_eq!(reply.to_vec(), unhex("78a66cb7c4"));
+        a.recv_enc(&mut reply, false);
+        assert_eq!(&reply, b"reply");
+
+        a.ratchet(32, false);
+        b.ratchet(32, false);
+
+        let (mut prf_a, mut prf_b) = ([0; 32], [0; 32]);
+        a.prf(&mut prf_a, false);
+        b.prf(&mut prf_b, false);
+        assert_eq!(
+            prf_a.to_vec(),
+            unhex("2369d9b5dda94d989370377c64e772b074716aa3fddc9c5cdb085ead173cc8dc")
+        );
+        assert_eq!(prf_a, prf_b);
+    }
+
+    #[test]
+    fn bad_mac() {
+        let mut a = Strobe::new128(b"strobe test");
+        let mut b = Strobe::new128(b"strobe test");
+
+        let mut mac = [0; 16];
+        a.send_mac(&mut mac, false);
+        mac[0] ^= 1;
+
+        assert!(!b.recv_mac(&mac, false));
+    }
+
+    /// Continuing an operation with `more` is the same as doing it at once,
+    /// and different from starting a new one.
+    #[test]
+    fn more() {
+        let prf = |pieces: &[(&[u8], bool)]| {
+            let mut s = Strobe::new128(b"strobe test");
+            for (data, more) in pieces {
+                s.ad(data, *more);
+            }
+
+            let mut out = [0; 16];
+            s.prf(&mut out, false);
+            out
+        };
+
+        let whole = prf(&[(b"hello world", false)]);
+        assert_eq!(prf(&[(b"hello ", false), (b"world", true)]), whole);
+        assert_ne!(prf(&[(b"hello ", false), (b"world", false)]), whole);
+    }
+}
@@ -0,0 +1,91 @@
+use super::sha3::{bstr_to_bytes, bytes_to_bstr, pad, Sha3};
+
+/// # TurboSHAKE (RFC 9861)
+/// A sponge over Keccak-p[1600, 12], with a domain separation byte `d` in
+/// `0x01..=0x7F` appended to the message before padding.
+///
+/// TurboSHAKE128 has capacity 256, TurboSHAKE256 has capacity 512.
+#[derive(Debug, Clone)]
+pub struct TurboShake {
+    capacity: usize,
+    msg: Vec<u8>,
+    d: u8,
+}
+
+impl TurboShake {
+    pub fn new(capacity: usize, msg: &[u8], d: u8) -> TurboShake {
+        assert!(
+            (0x01..=0x7F).contains(&d),
+            "domain byte must be in 0x01..=0x7F"
+        );
+
+        TurboShake {
+            capacity,
+            msg: msg.to_vec(),
+            d,
+        }
+    }
+
+    pub fn new128(msg: &[u8], d: u8) -> TurboShake {
+        TurboShake::new(256, msg, d)
+    }
+
+    pub fn new256(msg: &[u8], d: u8) -> TurboShake {
+        TurboShake::new(512, msg, d)
+    }
+
+    /// Squeeze `l` bytes of output.
+    pub fn result(&self, l: usize) -> Vec<u8> {
+        let mut bstr = bytes_to_bstr(&self.msg);
+        bstr.append(&mut domain_suffix(self.d));
+
+        bstr_to_bytes(&Sha3::sponge(
+            |s| Sha3::keccak_p(s, 12),
+            pad,
+            1600 - self.capacity,
+            bstr,
+            8 * l,
+        ))
+    }
+}
+
+/// The bits of `d` below its highest set bit. That bit is the leading `1` of
+/// pad10*1, so `M || d || pad` is `M || suffix || pad10*1`.
+pub(crate) fn domain_suffix(d: u8) -> Vec<bool> {
+    let top = 7 - d.leading_zeros() as usize;
+
+    (0..top).map(|i| (d >> i) & 1 == 1).collect()
+}
+
+pub fn turboshake128(msg: &[u8], d: u8, l: usize) -> Vec<u8> {
+    TurboShake::new128(msg, d).result(l)
+}
+
+pub fn turboshake256(msg: &[u8], d: u8, l: usize) -> Vec<u8> {
+    TurboShake::new256(msg, d).result(l)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unhex;
+
+    /// RFC 9861, section 5, with the empty message and `D = 0x1F`.
+    #[test]
+    fn empty() {
+        assert_eq!(
+            turboshake128(&[], 0x1F, 64),
+            unhex(concat!(
+                "1e415f1c5983aff2169217277d17bb538cd945a397ddec541f1ce41af2c1b74c",
+                "3e8ccae2a4dae56c84a04c2385c03c15e8193bdf58737363321691c05462c8df",
+            ))
+        );
+        assert_eq!(
+            turboshake256(&[], 0x1F, 64),
+            unhex(concat!(
+                "367a329dafea871c7802ec67f905ae13c57695dc2c6663c61035f59a18f8e7db",
+                "11edc0e12e91ea60eb6b32df06dd7f002fbafabb6e13ec1cc20d995547600db0",
+            ))
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use super::{
+    duplex::Permutation,
+    sha3::{bstr_to_bytes, bytes_to_bstr},
+};
+
+/// # Xoodoo
+/// A 384-bit permutation on 3 planes of 4 32-bit lanes, with a round
+/// function modelled on Keccak-p. `Xoodoo::default()` is Xoodoo[12].
+///
+/// Lane `x` of plane `y` is `state[x + 4 * y]`; in byte form lanes are
+/// little-endian.
+#[derive(Debug, Clone, Copy)]
+pub struct Xoodoo {
+    pub n_r: usize,
+}
+
+impl Default for Xoodoo {
+    fn default() -> Xoodoo {
+        Xoodoo { n_r: 12 }
+    }
+}
+
+impl Xoodoo {
+    pub fn permute_lanes(&self, state: &mut [u32; 12]) {
+        for c in &RC[12 - self.n_r..] {
+            rnd(state, *c);
+        }
+    }
+
+    pub fn permute_bytes(&self, bytes: &mut [u8; 48]) {
+        let mut state = [0u32; 12];
+
+        for (lane, xs) in state.iter_mut().zip(bytes.chunks_exact(4)) {
+            *lane = u32::from_le_bytes(xs.try_into().unwrap());
+        }
+
+        self.permute_lanes(&mut state);
+
+        for (lane, xs) in state.iter().zip(bytes.chunks_exact_mut(4)) {
+            xs.copy_from_slice(&lane.to_le_bytes());
+        }
+    }
+}
+
+impl Permutation for Xoodoo {
+    fn width(&self) -> usize {
+        384
+    }
+
+    fn permute(&self, bstr: Vec<bool>) -> Vec<bool> {
+        let mut bytes: [u8; 48] = bstr_to_bytes(&bstr).try_into().unwrap();
+        self.permute_bytes(&mut bytes);
+
+        bytes_to_bstr(&bytes)
+    }
+}
+
+fn rnd(a: &mut [u32; 12], c: u32) {
+    // Theta
+    let p: [u32; 4] = std::array::from_fn(|x| a[x] ^ a[x + 4] ^ a[x + 8]);
+    let e: [u32; 4] =
+        std::array::from_fn(|x| p[(x + 3) % 4].rotate_left(5) ^ p[(x + 3) % 4].rotate_left(14));
+
+    for (i, lane) in a.iter_mut().enumerate() {
+        *lane ^= e[i % 4];
+    }
+
+    // Rho-west
+    a[4..8].rotate_right(1);
+
+    for lane in &mut a[8..12] {
+        *lane = lane.rotate_left(11);
+    }
+
+    // Iota
+    a[0] ^= c;
+
+    // Chi
+    for x in 0..4 {
+        let (a0, a1, a2) = (a[x], a[x + 4], a[x + 8]);
+
+        a[x] ^= !a1 & a2;
+        a[x + 4] ^= !a2 & a0;
+        a[x + 8] ^= !a0 & a1;
+    }
+
+    // Rho-east
+    for lane in &mut a[4..8] {
+        *lane = lane.rotate_left(1);
+    }
+
+    a[8..12].rotate_right(2);
+
+    for lane in &mut a[8..12] {
+        *lane = lane.rotate_left(8);
+    }
+}
+
+// Constants
+
+static RC: [u32; 12] = [
+    0x058, 0x038, 0x3C0, 0x0D0, 0x120, 0x014, 0x060, 0x02C, 0x380, 0x0F0, 0x1A0, 0x012,
+];
@@ -0,0 +1,133 @@
+//! # Feldman verifiable secret sharing
+//! Shamir's scheme over the scalars of ristretto255, with the dealer
+//! publishing `[a_j] B` for every coefficient `a_j` of the polynomial. A
+//! shareholder with `y = f(x)` checks `[y] B = sum [x^j] [a_j] B` and so
+//! knows that its share is consistent with everyone else's, without trusting
+//! the dealer.
+//!
+//! The commitments reveal `[s] B` for the secret `s`, so it must be a
+//! uniformly random scalar, e.g. a private key, rather than arbitrary data.
+
+use super::polynomial;
+use crate::{
+    ecc::{edwards25519::Scalar, ristretto255::RistrettoPoint},
+    rng,
+};
+
+/// The value `f(x)` of the dealer's polynomial at `x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Share {
+    /// The non-zero evaluation point.
+    pub x: u16,
+    pub y: Scalar,
+}
+
+/// The commitments `[a_0] B, ..., [a_{t-1}] B` to the coefficients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commitment(pub Vec<RistrettoPoint>);
+
+/// Split `secret` into `shares` shares with `x = 1..=shares`, any
+/// `threshold` of which recover it, and commit to the polynomial.
+///
+/// Panics unless `1 <= threshold <= shares`.
+pub fn split(secret: &Scalar, threshold: u16, shares: u16) -> (Vec<Share>, Commitment) {
+    assert!(1 <= threshold && threshold <= shares, "invalid threshold");
+
+    let mut coefficients = vec![*secret];
+    coefficients.extend((1..threshold).map(|_| Scalar::from_le_bytes(&rng::bytes::<64>())));
+
+    let shares = (1..=shares)
+        .map(|x| Share {
+            x,
+            y: polynomial::evaluate(&coefficients, &Scalar::from_u64(x as u64)),
+        })
+        .collect();
+
+    let commitment = Commitment(coefficients.iter().map(RistrettoPoint::mul_base).collect());
+
+    (shares, commitment)
+}
+
+/// Recover the secret by Lagrange interpolation at 0. `None` if `shares` is
+/// empty or has repeated or zero `x`.
+pub fn combine(shares: &[Share]) -> Option<Scalar> {
+    if shares.is_empty() || shares.iter().any(|s| s.x == 0) {
+        return None;
+    }
+
+    let points = shares
+        .iter()
+        .map(|s| (Scalar::from_u64(s.x as u64), s.y))
+        .collect::<Vec<_>>();
+
+    polynomial::interpolate(&points)
+}
+
+impl Commitment {
+    /// Whether `share` lies on the committed polynomial.
+    pub fn verify(&self, share: &Share) -> bool {
+        let expected = polynomial::evaluate_commitment(&self.0, &Scalar::from_u64(share.x as u64));
+
+        share.x != 0 && RistrettoPoint::mul_base(&share.y) == expected
+    }
+
+    /// `[s] B` for the shared secret `s`.
+    pub fn public_key(&self) -> RistrettoPoint {
+        self.0[0]
+    }
+
+    /// The threshold `t`.
+    pub fn threshold(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_scalar() -> Scalar {
+        Scalar::from_le_bytes(&rng::bytes::<64>())
+    }
+
+    #[test]
+    fn round_trip() {
+        let secret = random_scalar();
+
+        for (threshold, count) in [(1, 1), (1, 4), (3, 3), (3, 10)] {
+            let (shares, commitment) = split(&secret, threshold, count);
+            let t = threshold as usize;
+
+            assert_eq!(commitment.threshold(), t);
+            assert_eq!(commitment.public_key(), RistrettoPoint::mul_base(&secret));
+            assert!(shares.iter().all(|share| commitment.verify(share)));
+
+            assert_eq!(combine(&shares), Some(secret));
+            assert_eq!(combine(&shares[count as usize - t..]), Some(secret));
+
+            if threshold > 1 {
+                assert_ne!(combine(&shares[..t - 1]), Some(secret));
+            }
+        }
+    }
+
+    #[test]
+    fn bad_shares() {
+        let (shares, commitment) = split(&random_scalar(), 2, 3);
+
+        assert_eq!(combine(&[]), None);
+        assert_eq!(combine(&[shares[0], shares[1], shares[0]]), None);
+        assert_eq!(combine(&[shares[0], Share { x: 0, ..shares[1] }]), None);
+
+        let tampered = Share {
+            y: shares[1].y + Scalar::ONE,
+            ..shares[1]
+        };
+        assert!(!commitment.verify(&tampered));
+        assert!(!commitment.verify(&Share { x: 3, ..shares[1] }));
+        assert!(!commitment.verify(&Share { x: 0, ..shares[0] }));
+
+        let (_, other) = split(&random_scalar(), 2, 3);
+        assert!(!other.verify(&shares[0]));
+    }
+}
@@ -0,0 +1,9 @@
+//! # Secret sharing
+//! Splitting a secret into shares so that any `t` of them recover it and
+//! fewer reveal nothing: Shamir's scheme over GF(2^8) for byte strings, and
+//! Feldman's verifiable variant over ristretto255 scalars, on top of the
+//! [`polynomial`] arithmetic that [`crate::ecc::frost`] shares.
+
+pub mod feldman;
+pub mod polynomial;
+pub mod shamir;
@@ -0,0 +1,84 @@
+//! # Polynomials over prime fields
+//! The arithmetic behind sharing a scalar: evaluating the dealer's
+//! polynomial, Lagrange interpolation at 0, and evaluating Feldman
+//! commitments to the coefficients in the group.
+
+use crate::ecc::{
+    group::{self, Group},
+    modular::{Modulus, Residue},
+};
+
+/// `f(x)` for the polynomial with `coefficients`, constant term first.
+pub fn evaluate<M: Modulus<N>, const N: usize>(
+    coefficients: &[Residue<M, N>],
+    x: &Residue<M, N>,
+) -> Residue<M, N> {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Residue::ZERO, |acc, a| acc * *x + *a)
+}
+
+/// The Lagrange coefficient `prod x_j / (x_j - x_i)` of `x_i` at 0 over the
+/// points `xs`, or `None` if `x_i` is not one of them or one repeats.
+pub fn lagrange_coefficient<M: Modulus<N>, const N: usize>(
+    xs: &[Residue<M, N>],
+    x_i: &Residue<M, N>,
+) -> Option<Residue<M, N>> {
+    let i = xs.iter().position(|x| x == x_i)?;
+
+    distinct(xs).then(|| basis_at_zero(xs, i))
+}
+
+/// `f(0)` from the points `(x_i, f(x_i))`, or `None` if an `x_i` repeats.
+pub fn interpolate<M: Modulus<N>, const N: usize>(
+    points: &[(Residue<M, N>, Residue<M, N>)],
+) -> Option<Residue<M, N>> {
+    let xs = points.iter().map(|(x, _)| *x).collect::<Vec<_>>();
+
+    distinct(&xs).then(|| {
+        points
+            .iter()
+            .enumerate()
+            .fold(Residue::ZERO, |acc, (i, (_, y))| {
+                acc + *y * basis_at_zero(&xs, i)
+            })
+    })
+}
+
+/// `[f(x)] B = sum [x^j] [a_j] B` from the commitments `[a_j] B` to the
+/// coefficients. Variable time: `x` and the commitments must be public.
+pub fn evaluate_commitment<G: Group, M: Modulus<N>, const N: usize>(
+    commitment: &[G],
+    x: &Residue<M, N>,
+) -> G {
+    let powers = commitment
+        .iter()
+        .scan(Residue::ONE, |power, _| {
+            let x_j = *power;
+            *power = *power * *x;
+
+            Some(x_j)
+        })
+        .collect::<Vec<_>>();
+
+    group::vartime_multiscalar_mul(&powers, commitment)
+}
+
+/// Whether no two of `xs` are equal.
+fn distinct<M: Modulus<N>, const N: usize>(xs: &[Residue<M, N>]) -> bool {
+    xs.iter().enumerate().all(|(j, x)| !xs[..j].contains(x))
+}
+
+/// `l_i(0)` for distinct `xs`.
+fn basis_at_zero<M: Modulus<N>, const N: usize>(xs: &[Residue<M, N>], i: usize) -> Residue<M, N> {
+    let (numerator, denominator) = xs
+        .iter()
+        .enumerate()
+        .filter(|(j, _)| *j != i)
+        .fold((Residue::ONE, Residue::ONE), |(num, den), (_, x_j)| {
+            (num * *x_j, den * (*x_j - xs[i]))
+        });
+
+    numerator * denominator.invert()
+}
@@ -0,0 +1,184 @@
+//! # Shamir secret sharing over GF(2^8)
+//! Every byte of the secret is the constant term of its own random
+//! polynomial of degree `t - 1`, and share `x` holds the values of all of
+//! them at `x`. Field elements are bytes modulo the AES polynomial
+//! `x^8 + x^4 + x^3 + x + 1`, multiplied without tables so that the time
+//! taken doesn't depend on the secret.
+//!
+//! Shares are not authenticated: a corrupted share recombines to a wrong
+//! secret without any error. See [`super::feldman`] for verifiable shares.
+
+use crate::rng;
+
+/// The values at `x` of the polynomials for each byte of the secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    /// The non-zero evaluation point.
+    pub x: u8,
+    pub y: Vec<u8>,
+}
+
+impl Share {
+    /// `x || y`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [&[self.x], &self.y[..]].concat()
+    }
+
+    /// `None` for an empty string or `x = 0`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Share> {
+        let (&x, y) = bytes.split_first()?;
+
+        (x != 0).then(|| Share { x, y: y.to_vec() })
+    }
+}
+
+/// Split `secret` into `shares` shares with `x = 1..=shares`, any
+/// `threshold` of which recover it.
+///
+/// Panics unless `1 <= threshold <= shares`.
+pub fn split(secret: &[u8], threshold: u8, shares: u8) -> Vec<Share> {
+    assert!(1 <= threshold && threshold <= shares, "invalid threshold");
+
+    // coefficients[i] holds the coefficients of x^(i + 1) for every byte.
+    let coefficients = (1..threshold)
+        .map(|_| {
+            let mut c = vec![0u8; secret.len()];
+            rng::fill(&mut c);
+            c
+        })
+        .collect::<Vec<_>>();
+
+    (1..=shares)
+        .map(|x| Share {
+            x,
+            y: (0..secret.len())
+                .map(|j| {
+                    // Horner's rule, highest degree first.
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |acc, c| mul(acc, x) ^ c[j])
+                })
+                .zip(secret)
+                .map(|(acc, s)| mul(acc, x) ^ s)
+                .collect(),
+        })
+        .collect()
+}
+
+/// Recover the secret by Lagrange interpolation at 0. `None` if `shares` is
+/// empty, has repeated `x`, or values of different lengths. With fewer
+/// shares than the threshold the result is garbage.
+pub fn combine(shares: &[Share]) -> Option<Vec<u8>> {
+    let len = shares.first()?.y.len();
+
+    for (i, share) in shares.iter().enumerate() {
+        if share.x == 0 || share.y.len() != len || shares[..i].iter().any(|s| s.x == share.x) {
+            return None;
+        }
+    }
+
+    let mut secret = vec![0u8; len];
+
+    for share in shares {
+        // l_i(0) = prod x_j / (x_j - x_i), and subtraction is xor.
+        let (num, den) = shares
+            .iter()
+            .filter(|s| s.x != share.x)
+            .fold((1, 1), |(num, den), s| {
+                (mul(num, s.x), mul(den, s.x ^ share.x))
+            });
+        let l = mul(num, invert(den));
+
+        for (s, y) in secret.iter_mut().zip(&share.y) {
+            *s ^= mul(l, *y);
+        }
+    }
+
+    Some(secret)
+}
+
+/// Multiplication in GF(2^8), in constant time.
+fn mul(mut a: u8, b: u8) -> u8 {
+    let mut p = 0;
+
+    for i in 0..8 {
+        p ^= a & 0u8.wrapping_sub((b >> i) & 1);
+        // Multiply a by x, reducing by x^8 = x^4 + x^3 + x + 1.
+        a = (a << 1) ^ (0x1b & 0u8.wrapping_sub(a >> 7));
+    }
+
+    p
+}
+
+/// `a^254 = a^-1` for non-zero `a`.
+fn invert(a: u8) -> u8 {
+    let mut result = 1;
+    let mut base = a;
+
+    for i in 0..8 {
+        if (254 >> i) & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let secret = b"attack at dawn, bring snacks".to_vec();
+
+        for (threshold, count) in [(1, 1), (1, 5), (3, 5), (5, 5), (2, 255), (255, 255)] {
+            let shares = split(&secret, threshold, count);
+            let t = threshold as usize;
+
+            assert_eq!(shares.len(), count as usize);
+            assert_eq!(combine(&shares), Some(secret.clone()));
+            assert_eq!(combine(&shares[..t]), Some(secret.clone()));
+            assert_eq!(combine(&shares[count as usize - t..]), Some(secret.clone()));
+
+            if threshold > 1 {
+                assert_ne!(combine(&shares[..t - 1]), Some(secret.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn bad_shares() {
+        let shares = split(b"secret", 2, 3);
+
+        assert_eq!(combine(&[]), None);
+        assert_eq!(combine(&[shares[0].clone(), shares[0].clone()]), None);
+        assert_eq!(
+            combine(&[shares[0].clone(), shares[1].clone(), shares[0].clone()]),
+            None
+        );
+
+        let mut short = shares[1].clone();
+        short.y.pop();
+        assert_eq!(combine(&[shares[0].clone(), short]), None);
+
+        assert_eq!(
+            Share::from_bytes(&shares[2].to_bytes()),
+            Some(shares[2].clone())
+        );
+        assert_eq!(Share::from_bytes(&[0, 1, 2]), None);
+        assert_eq!(Share::from_bytes(&[]), None);
+    }
+
+    #[test]
+    fn field() {
+        // The example of FIPS 197, section 4.2.
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+
+        for a in 1..=255 {
+            assert_eq!(mul(a, invert(a)), 1);
+        }
+    }
+}
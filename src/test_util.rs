@@ -0,0 +1,17 @@
+//! # Test helpers
+//! Hex decoding for known-answer tests.
+
+/// Panics unless `s` is an even number of hex digits.
+pub fn unhex(s: &str) -> Vec<u8> {
+    assert!(s.len().is_multiple_of(2), "odd number of hex digits");
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).expect("not a hex digit"))
+        .collect()
+}
+
+/// [`unhex`] into an array, panicking unless `s` has exactly `N` bytes.
+pub fn unhex_array<const N: usize>(s: &str) -> [u8; N] {
+    unhex(s).try_into().expect("wrong length")
+}